@@ -1,18 +1,43 @@
 pub mod address;
 pub mod block;
 pub mod event_handling;
+pub mod keymap;
+pub mod notification;
 pub mod statistics;
 pub mod transaction;
 use crate::{
-    ethers::types::{BlockWithTransactionReceipts, ERC20Token, TransactionWithReceipt},
-    network::IoEvent,
+    config::{Config, LayoutConfig},
+    ethers::{
+        signatures::Signatures,
+        storage::{self, StorageLayout},
+        types::{
+            AddressInfo, AddressTransactions, BlockWithTransactionReceipts, DecodedCall,
+            DecodedEvent, ERC20Token, StorageSlot, TokenBalance, TransactionWithReceipt,
+        },
+    },
+    network::{
+        chain::{ChainContext, ExplorerStatus},
+        IoEvent, Message, RequestId, NEW_BLOCK_HIGHLIGHT_SECS,
+    },
     route::{ActiveBlock, Route, RouteId},
+    ui::theme::Theme,
     widget::StatefulList,
 };
-use ethers::core::types::{Address, NameOrAddress, Transaction, TransactionReceipt, TxHash, U64};
+use chrono::{DateTime, Utc};
+use ethers::core::types::{
+    Address, Block, NameOrAddress, Transaction, TransactionReceipt, TxHash, U64,
+};
+use keymap::Keymap;
+use log::warn;
+use notification::Notification;
 use ratatui::widgets::{ListState, ScrollbarState, TableState};
 use statistics::Statistics;
-use std::{collections::HashMap, fs::File, io::Read, sync::mpsc::Sender};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+};
+use tokio::sync::mpsc::{error::TrySendError, Sender};
 
 pub enum InputMode {
     Normal,
@@ -21,15 +46,28 @@ pub enum InputMode {
 
 pub struct App {
     routes: Vec<Route>,
-    io_tx: Option<Sender<IoEvent>>,
+    io_tx: Option<Sender<(RequestId, IoEvent)>>,
+    next_request_id: RequestId,
+    /// The route each in-flight request was dispatched from, and the request.
+    in_flight_requests: HashMap<RequestId, (u64, IoEvent)>,
     pub endpoint: String,
     pub is_loading: bool,
     pub is_toggled: bool,
     pub show_popup: bool,
     pub statistics: Statistics,
     pub latest_blocks: Option<StatefulList<BlockWithTransactionReceipts<Transaction>>>,
+    /// How many of the latest blocks couldn't be loaded.
+    pub failed_latest_blocks: usize,
     pub latest_transactions: Option<StatefulList<TransactionWithReceipt>>,
+    /// When each block pushed by the new-head subscription arrived, for highlighting.
+    pub new_block_arrivals: HashMap<U64, DateTime<Utc>>,
     pub address2ens_id: HashMap<Address, Option<String>>,
+    /// Requests that failed even after retrying.
+    pub failed_requests: usize,
+    /// Transactions whose receipt couldn't be fetched.
+    pub failed_receipts: HashSet<TxHash>,
+    /// Failed requests, oldest first.
+    pub notifications: Vec<Notification>,
     //Search
     pub input_mode: InputMode,
     pub input: String,
@@ -45,6 +83,17 @@ pub struct App {
     pub source_code_scroll: u16,
     pub abi_scroll_state: ScrollbarState,
     pub abi_scroll: u16,
+    pub address_transactions_table_state: TableState,
+    pub token_balances_table_state: TableState,
+    pub bytecode_scroll_state: ScrollbarState,
+    pub bytecode_scroll: u16,
+    /// The slot being typed into the storage inspector.
+    pub storage_slot_input: String,
+    pub is_editing_storage_slot: bool,
+    /// Why the last slot entered couldn't be parsed.
+    pub storage_slot_error: Option<String>,
+    pub storage_scroll_state: ScrollbarState,
+    pub storage_scroll: u16,
     //Transaction Detail
     pub transaction_detail_list_state: ListState,
     pub input_data_detail_list_state: ListState,
@@ -52,35 +101,55 @@ pub struct App {
     pub input_data_scroll: u16,
     pub decoded_input_data_scroll_state: ScrollbarState,
     pub decoded_input_data_scroll: u16,
+    pub logs_scroll_state: ScrollbarState,
+    pub logs_scroll: u16,
+    //Chain
+    pub chain: ChainContext,
+    pub explorer_status: ExplorerStatus,
+
     //Token Data
     pub erc20_tokens: Vec<ERC20Token>,
+
+    //Storage Layouts
+    pub storage_layouts: HashMap<Address, StorageLayout>,
+
+    //Signatures
+    pub signatures: Arc<Signatures>,
+
+    //Layout
+    pub layout: LayoutConfig,
+    pub theme: Theme,
+
+    //Keymap
+    pub keymap: Keymap,
 }
 
 impl App {
-    pub fn new(io_tx: Sender<IoEvent>, endpoint: &str) -> App {
-        let erc20_tokens = File::open("./data/tokens.json").map_or(vec![], |file| {
-            let mut buffer = String::new();
-            let mut file = std::io::BufReader::new(file);
-            if file.read_to_string(&mut buffer).is_ok() {
-                let tokens: Result<Vec<ERC20Token>, serde_json::Error> =
-                    serde_json::from_str(&buffer);
-                tokens.map_or(vec![], |tokens| tokens)
-            } else {
-                vec![]
-            }
-        });
+    /// The configured signature files are loaded on top of the bundled
+    /// `data/signatures.json` and `data/events.json`.
+    pub fn new(io_tx: Sender<(RequestId, IoEvent)>, config: &Config) -> App {
+        let chain = ChainContext::default();
+        let erc20_tokens = chain.erc20_tokens();
+        let storage_layouts = chain.load_storage_layouts();
 
         App {
             routes: vec![Route::default()],
-            endpoint: endpoint.to_owned(),
+            endpoint: config.endpoints[0].to_owned(),
             is_loading: false,
-            is_toggled: false,
+            is_toggled: config.layout.sidebar_toggled,
             show_popup: false,
             io_tx: Some(io_tx),
+            next_request_id: 0,
+            in_flight_requests: HashMap::new(),
             statistics: Statistics::new(),
             latest_blocks: None,
+            failed_latest_blocks: 0,
             latest_transactions: None,
+            new_block_arrivals: HashMap::new(),
             address2ens_id: HashMap::new(),
+            failed_requests: 0,
+            failed_receipts: HashSet::new(),
+            notifications: vec![],
             input_mode: InputMode::Normal,
             input: "".to_owned(),
             cursor_position: 0,
@@ -96,6 +165,15 @@ impl App {
             abi_scroll_state: ScrollbarState::default(),
             source_code_scroll: 0,
             abi_scroll: 0,
+            address_transactions_table_state: TableState::default(),
+            token_balances_table_state: TableState::default(),
+            bytecode_scroll_state: ScrollbarState::default(),
+            bytecode_scroll: 0,
+            storage_slot_input: "".to_owned(),
+            is_editing_storage_slot: false,
+            storage_slot_error: None,
+            storage_scroll_state: ScrollbarState::default(),
+            storage_scroll: 0,
             //Transaction Detail
             transaction_detail_list_state: ListState::default(),
             input_data_detail_list_state: ListState::default(),
@@ -103,11 +181,257 @@ impl App {
             input_data_scroll: 0,
             decoded_input_data_scroll_state: ScrollbarState::default(),
             decoded_input_data_scroll: 0,
+            logs_scroll_state: ScrollbarState::default(),
+            logs_scroll: 0,
+            //Chain
+            explorer_status: chain.explorer_status(),
+            chain,
             //Token Data
             erc20_tokens,
+            //Storage Layouts
+            storage_layouts,
+            signatures: Arc::new(Signatures::load(
+                &[
+                    PathBuf::from("./data/signatures.json"),
+                    PathBuf::from("./data/events.json"),
+                ]
+                .iter()
+                .chain(&config.signatures)
+                .collect::<Vec<_>>(),
+            )),
+            //Layout
+            layout: config.layout,
+            theme: config.theme(),
+            //Keymap
+            keymap: config.keys.to_owned(),
+        }
+    }
+
+    /// Whether the screen shows something that changes with time alone, i.e. the age of the
+    /// latest blocks or a notification that will expire.
+    pub fn shows_elapsed_time(&self) -> bool {
+        !self.is_toggled
+            || self
+                .notifications
+                .last()
+                .is_some_and(|notification| notification.is_recent())
+    }
+
+    /// Whether the screen shows a spinner, i.e. a request is in flight or a pane is still
+    /// waiting for what it shows without having failed. Spinners move on every tick.
+    pub fn is_spinning(&self) -> bool {
+        let is_waiting_for = |loaded: bool, predicate: fn(&IoEvent) -> bool| {
+            !loaded && self.find_failure(predicate).is_none()
+        };
+        self.is_loading
+            || is_waiting_for(self.latest_blocks.is_some(), |io_event| {
+                matches!(io_event, IoEvent::GetLatestBlocks { .. })
+            })
+            || is_waiting_for(self.latest_transactions.is_some(), |io_event| {
+                matches!(io_event, IoEvent::GetLatestTransactions { .. })
+            })
+            || is_waiting_for(
+                !self
+                    .statistics
+                    .is_incomplete(self.explorer_status == ExplorerStatus::Enabled),
+                |io_event| matches!(io_event, IoEvent::GetStatistics),
+            )
+    }
+
+    /// Applies what the network task found out.
+    pub fn handle_message(&mut self, message: Message) {
+        match message {
+            Message::Chain(chain) => self.set_chain(*chain),
+            Message::Statistics(statistics) => {
+                self.statistics = *statistics;
+            }
+            Message::Route {
+                request_id,
+                route_id,
+            } => {
+                if self.open_route(request_id, Route::new(*route_id, ActiveBlock::Main)) {
+                    self.load_selected_address_tab();
+                }
+            }
+            Message::DecodedInputData {
+                transaction_hash,
+                decoded_input_data,
+            } => self
+                .update_transaction_with_decoded_input_data(transaction_hash, decoded_input_data),
+            Message::DecodedLogs {
+                transaction_hash,
+                decoded_logs,
+            } => self.update_transaction_with_decoded_logs(transaction_hash, decoded_logs),
+            Message::TransactionReceipts(receipts) => {
+                self.update_block_with_transaction_receipts(receipts);
+            }
+            Message::FailedReceipts {
+                transactions,
+                failed,
+            } => {
+                for transaction_hash in transactions {
+                    self.failed_receipts.remove(&transaction_hash);
+                }
+                self.failed_receipts.extend(failed);
+            }
+            Message::LatestBlocks { blocks, failed } => {
+                self.latest_blocks = Some(StatefulList::with_items(blocks));
+                self.failed_latest_blocks = failed;
+            }
+            Message::LatestTransactions(transactions) => {
+                self.latest_transactions = Some(StatefulList::with_items(transactions));
+            }
+            Message::NewHeads {
+                blocks,
+                transactions,
+                n,
+            } => self.update_with_new_heads(blocks, transactions, n),
+            Message::EnsIds(ens_ids) => {
+                for (address, ens_id) in ens_ids {
+                    if ens_id.is_some() {
+                        self.address2ens_id.insert(address, ens_id);
+                    } else {
+                        self.address2ens_id.entry(address).or_insert(ens_id);
+                    }
+                }
+            }
+            Message::AddressTransactions(transactions) => {
+                self.update_address_with_transactions(transactions);
+            }
+            Message::TokenBalances {
+                address,
+                token_balances,
+            } => self.update_address_with_token_balances(address, token_balances),
+            Message::StorageAt {
+                address,
+                slot,
+                value,
+            } => {
+                self.update_address_info(address, |address_info| AddressInfo {
+                    storage_slots: address_info
+                        .storage_slots
+                        .into_iter()
+                        .map(|storage_slot| {
+                            if storage_slot.slot == slot {
+                                StorageSlot {
+                                    value: Some(value),
+                                    ..storage_slot
+                                }
+                            } else {
+                                storage_slot
+                            }
+                        })
+                        .collect(),
+                    ..address_info
+                });
+            }
+            Message::ExplorerStatus(status) => {
+                self.explorer_status = status;
+            }
+            Message::BackendStatus {
+                failed_requests,
+                endpoint,
+            } => {
+                self.failed_requests = failed_requests;
+                if let Some(endpoint) = endpoint {
+                    self.endpoint = endpoint;
+                }
+            }
+            Message::Failed {
+                request_id,
+                io_event,
+                endpoint,
+                message,
+            } => {
+                let endpoint = endpoint.unwrap_or(self.endpoint.to_owned());
+                let notification = Notification::new(*io_event, &endpoint, message);
+                // Requests that would have opened a page leave a failed page in its place, from
+                // which they can be retried, unless the user has moved on.
+                if let (Some(request_id), true) = (request_id, notification.io_event.opens_route())
+                {
+                    self.open_route(
+                        request_id,
+                        Route::new(RouteId::Failed(notification.to_owned()), ActiveBlock::Main),
+                    );
+                }
+                self.notify(notification);
+            }
+            Message::Finished { request_id } => {
+                self.finish_request(request_id);
+                self.is_loading = !self.in_flight_requests.is_empty();
+            }
         }
     }
 
+    /// Prepends blocks pushed by the new-head subscription to Latest Blocks, and replaces Latest
+    /// Transactions with the transactions of the newest one.
+    fn update_with_new_heads(
+        &mut self,
+        blocks: Vec<Block<Transaction>>,
+        transactions: Vec<TransactionWithReceipt>,
+        n: usize,
+    ) {
+        // Initial setup or a manual refresh is still in flight.
+        let Some(latest_blocks) = self.latest_blocks.as_mut() else {
+            return;
+        };
+        let latest_known_number = latest_blocks
+            .items
+            .iter()
+            .filter_map(|block| block.block.number)
+            .max();
+        let blocks = blocks
+            .into_iter()
+            .filter(|block| block.number > latest_known_number)
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return;
+        }
+
+        let now = Utc::now();
+        self.new_block_arrivals
+            .retain(|_, arrival| (now - *arrival).num_seconds() < NEW_BLOCK_HIGHLIGHT_SECS);
+        for block in &blocks {
+            if let Some(number) = block.number {
+                self.new_block_arrivals.insert(number, now);
+            }
+        }
+
+        latest_blocks.prepend(
+            blocks
+                .into_iter()
+                .map(|block| BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: None,
+                })
+                .collect(),
+            n,
+        );
+
+        let selected = self
+            .latest_transactions
+            .as_ref()
+            .and_then(|latest_transactions| latest_transactions.state.selected());
+        let mut latest_transactions = StatefulList::with_items(transactions);
+        if !latest_transactions.items.is_empty() {
+            latest_transactions.state.select(selected.map(|i| {
+                std::cmp::min(
+                    i,
+                    latest_transactions.items.len() - 1 + latest_transactions.header_size,
+                )
+            }));
+        }
+        self.latest_transactions = Some(latest_transactions);
+    }
+
+    /// Switches to the chain the endpoint serves, along with its token list.
+    pub fn set_chain(&mut self, chain: ChainContext) {
+        self.erc20_tokens = chain.erc20_tokens();
+        self.storage_layouts = chain.load_storage_layouts();
+        self.explorer_status = chain.explorer_status();
+        self.chain = chain;
+    }
+
     pub fn pop_current_route(&mut self) {
         if self.routes.len() > 1 {
             self.routes.pop();
@@ -128,7 +452,7 @@ impl App {
         let current_route = self.get_current_route();
         self.routes.pop();
         self.routes
-            .push(Route::new(current_route.get_id(), active_block));
+            .push(current_route.with_active_block(active_block));
     }
 
     pub fn update_block_with_transaction_receipts(
@@ -163,30 +487,261 @@ impl App {
                         None
                     };
 
-                    Route::new(
-                        match route.get_id() {
-                            RouteId::Block(_) => RouteId::Block(block),
-                            RouteId::TransactionsOfBlock(_) => RouteId::TransactionsOfBlock(block),
-                            RouteId::WithdrawalsOfBlock(_) => RouteId::WithdrawalsOfBlock(block),
-                            _ => unreachable!(),
-                        },
-                        route.get_active_block(),
-                    )
+                    route.with_id(match route.get_id() {
+                        RouteId::Block(_) => RouteId::Block(block),
+                        RouteId::TransactionsOfBlock(_) => RouteId::TransactionsOfBlock(block),
+                        RouteId::WithdrawalsOfBlock(_) => RouteId::WithdrawalsOfBlock(block),
+                        _ => unreachable!(),
+                    })
                 }
                 _ => route.to_owned(),
             })
             .collect::<Vec<_>>();
     }
 
+    pub fn update_address_with_transactions(&mut self, transactions: AddressTransactions) {
+        let address = transactions.address;
+        if self.update_address_info(address, |address_info| AddressInfo {
+            transactions: Some(transactions.to_owned()),
+            ..address_info
+        }) {
+            self.address_transactions_table_state.select(None);
+        }
+    }
+
+    pub fn update_address_with_token_balances(
+        &mut self,
+        address: Address,
+        token_balances: Vec<TokenBalance>,
+    ) {
+        if self.update_address_info(address, |address_info| AddressInfo {
+            token_balances: Some(token_balances.to_owned()),
+            ..address_info
+        }) {
+            self.token_balances_table_state.select(None);
+        }
+    }
+
+    /// Applies `f` to the pages of `address`. Returns whether there were any.
+    fn update_address_info<F>(&mut self, address: Address, f: F) -> bool
+    where
+        F: Fn(AddressInfo) -> AddressInfo,
+    {
+        let mut is_updated = false;
+        self.routes = self
+            .routes
+            .iter()
+            .map(|route| match route.get_id() {
+                RouteId::AddressInfo(Some(address_info)) if address_info.address == address => {
+                    is_updated = true;
+                    route.with_id(RouteId::AddressInfo(Some(f(address_info))))
+                }
+                _ => route.to_owned(),
+            })
+            .collect::<Vec<_>>();
+        is_updated
+    }
+
+    pub fn update_transaction_with_decoded_input_data(
+        &mut self,
+        transaction_hash: TxHash,
+        decoded_input_data: Option<DecodedCall>,
+    ) {
+        self.update_transaction(transaction_hash, |transaction| TransactionWithReceipt {
+            decoded_input_data: decoded_input_data.to_owned(),
+            ..transaction
+        });
+    }
+
+    pub fn update_transaction_with_decoded_logs(
+        &mut self,
+        transaction_hash: TxHash,
+        decoded_logs: Vec<Option<DecodedEvent>>,
+    ) {
+        self.update_transaction(transaction_hash, |transaction| TransactionWithReceipt {
+            decoded_logs: decoded_logs.to_owned(),
+            ..transaction
+        });
+    }
+
+    fn update_transaction<F>(&mut self, transaction_hash: TxHash, f: F)
+    where
+        F: Fn(TransactionWithReceipt) -> TransactionWithReceipt,
+    {
+        self.routes = self
+            .routes
+            .iter()
+            .map(|route| match route.get_id() {
+                RouteId::Transaction(Some(transaction))
+                | RouteId::InputDataOfTransaction(Some(transaction))
+                    if transaction.transaction.hash == transaction_hash =>
+                {
+                    let transaction = Some(f(transaction));
+                    route.with_id(match route.get_id() {
+                        RouteId::Transaction(_) => RouteId::Transaction(transaction),
+                        RouteId::InputDataOfTransaction(_) => {
+                            RouteId::InputDataOfTransaction(transaction)
+                        }
+                        _ => unreachable!(),
+                    })
+                }
+                _ => route.to_owned(),
+            })
+            .collect::<Vec<_>>();
+    }
+
+    pub fn notify(&mut self, notification: Notification) {
+        if self.notifications.len() >= Notification::CAPACITY {
+            self.notifications.remove(0);
+        }
+        self.notifications.push(notification);
+    }
+
+    /// The latest failure of a request matching `predicate`.
+    pub fn find_failure(&self, predicate: impl Fn(&IoEvent) -> bool) -> Option<&Notification> {
+        self.notifications
+            .iter()
+            .rev()
+            .find(|notification| predicate(&notification.io_event))
+    }
+
+    /// Replaces the failed route on top with a spinner and sends its request again.
+    pub fn retry(&mut self, notification: Notification) {
+        if let RouteId::Failed(_) = self.get_current_route().get_id() {
+            self.pop_current_route();
+        }
+        self.set_route(Route::new(
+            RouteId::Searching(notification.io_event.to_string()),
+            ActiveBlock::Main,
+        ));
+        self.dispatch(notification.io_event);
+    }
+
+    /// Whether the user is still on the screen that dispatched `request_id`.
+    pub fn is_on_route_of(&self, request_id: RequestId) -> bool {
+        self.in_flight_requests
+            .get(&request_id)
+            .is_some_and(|(key, _)| *key == self.get_current_route().get_key())
+    }
+
+    /// Whether `io_event` has been dispatched and hasn't finished yet.
+    fn is_in_flight(&self, io_event: &IoEvent) -> bool {
+        self.in_flight_requests
+            .values()
+            .any(|(_, in_flight)| in_flight == io_event)
+    }
+
+    /// Loads what the selected tab of the address page on screen shows, unless it's loaded,
+    /// loading or has failed. Tabs that take many requests load once they're opened rather
+    /// than with the page.
+    pub fn load_selected_address_tab(&mut self) {
+        let RouteId::AddressInfo(Some(address_info)) = self.get_current_route().get_id() else {
+            return;
+        };
+        let address = address_info.address;
+        let io_event = match address::SelectableContractDetailItem::from(
+            self.contract_list_state
+                .selected()
+                .unwrap_or(address::SelectableContractDetailItem::ContractSourceCode.into()),
+        ) {
+            address::SelectableContractDetailItem::Transactions
+                if address_info.transactions.is_none() =>
+            {
+                IoEvent::GetAddressTransactions {
+                    address,
+                    page: 0,
+                    until: None,
+                }
+            }
+            address::SelectableContractDetailItem::Tokens
+                if address_info.token_balances.is_none() =>
+            {
+                IoEvent::GetTokenBalances { address }
+            }
+            _ => return,
+        };
+        if self.find_failure(|failed| *failed == io_event).is_none()
+            && !self.is_in_flight(&io_event)
+        {
+            self.dispatch(io_event);
+        }
+    }
+
+    /// Opens `route` in response to `request_id`, in place of the search spinner if there is
+    /// one. Does nothing and returns `false` if the user has left the screen that sent it.
+    pub fn open_route(&mut self, request_id: RequestId, route: Route) -> bool {
+        if !self.is_on_route_of(request_id) {
+            return false;
+        }
+        if let RouteId::Searching(_) = self.get_current_route().get_id() {
+            self.pop_current_route();
+        }
+        self.set_route(route);
+        true
+    }
+
+    pub fn finish_request(&mut self, request_id: RequestId) {
+        self.in_flight_requests.remove(&request_id);
+    }
+
+    /// Leaves the search spinner and aborts the requests sent from it.
+    pub fn cancel_search(&mut self) {
+        let current_route = self.get_current_route();
+        let RouteId::Searching(_) = current_route.get_id() else {
+            return;
+        };
+
+        let request_ids = self
+            .in_flight_requests
+            .iter()
+            .filter(|(_, (key, _))| *key == current_route.get_key())
+            .map(|(&request_id, _)| request_id)
+            .collect::<Vec<_>>();
+        for request_id in request_ids {
+            self.in_flight_requests.remove(&request_id);
+            self.send(request_id, IoEvent::Cancel { request_id });
+        }
+        self.pop_current_route();
+        self.is_loading = !self.in_flight_requests.is_empty();
+    }
+
     // Send a network event to the network thread
     pub fn dispatch(&mut self, action: IoEvent) {
-        // `is_loading` will be set to false again after the async action has finished in network.rs
+        // `is_loading` will be set to false again once every request in flight has finished.
         self.is_loading = true;
-        if let Some(io_tx) = &self.io_tx {
-            if let Err(e) = io_tx.send(action) {
+        // Sending a request again supersedes its earlier failures.
+        self.notifications
+            .retain(|notification| notification.io_event != action);
+
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+        self.in_flight_requests.insert(
+            request_id,
+            (self.get_current_route().get_key(), action.to_owned()),
+        );
+        self.send(request_id, action);
+    }
+
+    fn send(&mut self, request_id: RequestId, action: IoEvent) {
+        let Some(io_tx) = &self.io_tx else {
+            return;
+        };
+        match io_tx.try_send((request_id, action)) {
+            Ok(()) => {}
+            Err(TrySendError::Full((request_id, action))) => {
+                self.handle_message(Message::Failed {
+                    request_id: Some(request_id),
+                    io_event: Box::new(action),
+                    endpoint: None,
+                    message: "Too many requests are queued. Try again later".to_owned(),
+                });
+                self.finish_request(request_id);
+                self.is_loading = !self.in_flight_requests.is_empty();
+            }
+            Err(TrySendError::Closed(_)) => {
+                warn!("The network task has stopped");
                 self.is_loading = false;
-                println!("Error from dispatch {}", e);
-            };
+            }
         }
     }
 
@@ -243,27 +798,115 @@ impl App {
         self.cursor_position = 0;
     }
 
-    pub fn submit_message(&mut self) -> String {
-        if let Some(token) = ERC20Token::find_by_ticker(&self.erc20_tokens, &self.input) {
-            self.dispatch(IoEvent::GetNameOrAddressInfo {
-                name_or_address: NameOrAddress::Address(token.contract_address),
-                is_searching: true,
-            })
-        } else if let Ok(transaction_hash) = self.input.parse::<TxHash>() {
-            self.dispatch(IoEvent::GetTransactionWithReceipt { transaction_hash });
-        } else if let Ok(i) = self.input.parse::<u64>() {
-            let number = U64::from(i);
-            self.dispatch(IoEvent::GetBlock { number });
-        } else if let Ok(name_or_address) = self.input.parse::<NameOrAddress>() {
-            self.dispatch(IoEvent::GetNameOrAddressInfo {
-                name_or_address,
-                is_searching: true,
-            })
+    /// Reads the slot entered in the storage inspector of `address`.
+    pub fn submit_storage_slot(&mut self, address: Address) {
+        let query = self.storage_slot_input.trim().to_owned();
+        self.storage_slot_input.clear();
+        if query.is_empty() {
+            return;
+        }
+        let slot = match storage::parse_slot(&query, self.storage_layouts.get(&address)) {
+            Ok(slot) => slot,
+            Err(e) => {
+                self.storage_slot_error = Some(format!("{e:#}"));
+                return;
+            }
+        };
+        self.storage_slot_error = None;
+
+        // Entering a slot again reloads it and moves it to the end.
+        self.update_address_info(address, |address_info| {
+            let mut storage_slots = address_info.storage_slots.to_owned();
+            storage_slots.retain(|storage_slot| storage_slot.slot != slot);
+            storage_slots.push(StorageSlot {
+                query: query.to_owned(),
+                slot,
+                value: None,
+            });
+            AddressInfo {
+                storage_slots,
+                ..address_info
+            }
+        });
+        self.dispatch(IoEvent::GetStorageAt { address, slot });
+    }
+
+    /// Searches for the input, showing a spinner until the result arrives.
+    pub fn submit_message(&mut self) {
+        let io_event =
+            if let Some(token) = ERC20Token::find_by_ticker(&self.erc20_tokens, &self.input) {
+                Some(IoEvent::GetNameOrAddressInfo {
+                    name_or_address: NameOrAddress::Address(token.contract_address),
+                })
+            } else if let Ok(transaction_hash) = self.input.parse::<TxHash>() {
+                Some(IoEvent::GetTransactionWithReceipt { transaction_hash })
+            } else if let Ok(i) = self.input.parse::<u64>() {
+                Some(IoEvent::GetBlock {
+                    number: U64::from(i),
+                })
+            } else if let Ok(name_or_address) = self.input.parse::<NameOrAddress>() {
+                Some(IoEvent::GetNameOrAddressInfo { name_or_address })
+            } else {
+                None
+            };
+
+        if let Some(io_event) = io_event {
+            // The spinner goes first, so that the response is tied to it.
+            self.set_route(Route::new(
+                RouteId::Searching(self.input.to_owned()),
+                ActiveBlock::Main,
+            ));
+            self.dispatch(io_event);
         }
 
-        let message = self.input.to_owned();
         self.input.clear();
         self.reset_cursor();
-        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    fn failed(io_event: IoEvent) -> Message {
+        Message::Failed {
+            request_id: None,
+            io_event: Box::new(io_event),
+            endpoint: None,
+            message: "error".to_owned(),
+        }
+    }
+
+    #[test]
+    fn spins_until_the_requests_in_flight_finish() {
+        let (io_tx, _io_rx) = mpsc::channel(16);
+        let mut app = App::new(io_tx, &Config::default());
+        app.dispatch(IoEvent::InitialSetup { n: 1 });
+
+        app.handle_message(Message::LatestBlocks {
+            blocks: vec![],
+            failed: 0,
+        });
+        app.handle_message(Message::LatestTransactions(vec![]));
+        app.handle_message(failed(IoEvent::GetStatistics));
+        assert!(app.is_spinning());
+
+        app.handle_message(Message::Finished { request_id: 0 });
+        assert!(!app.is_spinning());
+    }
+
+    #[test]
+    fn spins_while_a_pane_waits_for_what_it_shows() {
+        let (io_tx, _io_rx) = mpsc::channel(16);
+        let mut app = App::new(io_tx, &Config::default());
+        assert!(app.is_spinning());
+
+        app.handle_message(failed(IoEvent::GetLatestBlocks { n: 1 }));
+        app.handle_message(failed(IoEvent::GetLatestTransactions { n: 1 }));
+        assert!(app.is_spinning(), "statistics are still missing");
+
+        app.handle_message(failed(IoEvent::GetStatistics));
+        assert!(!app.is_spinning());
     }
 }
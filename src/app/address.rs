@@ -4,44 +4,56 @@ use crate::ethers::types::AddressInfo;
 pub enum SelectableContractDetailItem {
     ContractSourceCode, //0
     ContractAbi,        //1
+    Transactions,       //2
+    Tokens,             //3
+    Bytecode,           //4
+    Storage,            //5
 }
 
 impl SelectableContractDetailItem {
     pub fn next(&self, address_info: &AddressInfo) -> Self {
         match self {
-            Self::ContractAbi => {
-                if address_info.contract_source_code.is_some() {
-                    Self::ContractSourceCode
-                } else {
-                    Self::ContractAbi
-                }
-            }
             Self::ContractSourceCode => {
                 if address_info.contract_abi.is_some() {
                     Self::ContractAbi
+                } else {
+                    Self::Transactions
+                }
+            }
+            Self::ContractAbi => Self::Transactions,
+            Self::Transactions => Self::Tokens,
+            Self::Tokens => {
+                if address_info.is_contract() {
+                    Self::Bytecode
                 } else {
                     Self::ContractSourceCode
                 }
             }
+            Self::Bytecode => Self::Storage,
+            Self::Storage => Self::ContractSourceCode,
         }
     }
 
     pub fn previous(&self, address_info: &AddressInfo) -> Self {
         match self {
-            Self::ContractAbi => {
-                if address_info.contract_source_code.is_some() {
-                    Self::ContractSourceCode
+            Self::ContractSourceCode => {
+                if address_info.is_contract() {
+                    Self::Storage
                 } else {
-                    Self::ContractAbi
+                    Self::Tokens
                 }
             }
-            Self::ContractSourceCode => {
+            Self::ContractAbi => Self::ContractSourceCode,
+            Self::Transactions => {
                 if address_info.contract_abi.is_some() {
                     Self::ContractAbi
                 } else {
                     Self::ContractSourceCode
                 }
             }
+            Self::Tokens => Self::Transactions,
+            Self::Bytecode => Self::Tokens,
+            Self::Storage => Self::Bytecode,
         }
     }
 }
@@ -58,6 +70,14 @@ impl From<usize> for SelectableContractDetailItem {
             Self::ContractSourceCode
         } else if i == 1 {
             Self::ContractAbi
+        } else if i == 2 {
+            Self::Transactions
+        } else if i == 3 {
+            Self::Tokens
+        } else if i == 4 {
+            Self::Bytecode
+        } else if i == 5 {
+            Self::Storage
         } else {
             unreachable!()
         }
@@ -69,6 +89,10 @@ impl From<SelectableContractDetailItem> for usize {
         match val {
             SelectableContractDetailItem::ContractSourceCode => 0,
             SelectableContractDetailItem::ContractAbi => 1,
+            SelectableContractDetailItem::Transactions => 2,
+            SelectableContractDetailItem::Tokens => 3,
+            SelectableContractDetailItem::Bytecode => 4,
+            SelectableContractDetailItem::Storage => 5,
         }
     }
 }
@@ -2,11 +2,12 @@ use crate::{
     app::{
         address::SelectableContractDetailItem,
         block::SelectableBlockDetailItem,
+        keymap::Action,
         statistics::Statistics,
         transaction::{SelectableInputDataDetailItem, SelectableTransactionDetailItem},
         App, InputMode,
     },
-    ethers::types::BlockWithTransactionReceipts,
+    ethers::types::{AddressTransactions, BlockWithTransactionReceipts},
     network::IoEvent,
     route::{ActiveBlock, Route, RouteId},
 };
@@ -24,641 +25,853 @@ where
     match event {
         event::Event::Key(key) => {
             debug!("{:?}", key.code);
-            if let ActiveBlock::SearchBar = app.get_current_route().get_active_block() {
+            let is_search_bar_active = matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::SearchBar
+            );
+            if is_search_bar_active {
+                if let InputMode::Editing = app.input_mode {
+                    // Keys are typed into the search bar rather than mapped to actions.
+                    if key.kind == event::KeyEventKind::Press {
+                        edit_search(key.code, app);
+                    }
+                    return false;
+                }
+            }
+            if app.is_editing_storage_slot {
+                if key.kind == event::KeyEventKind::Press {
+                    edit_storage_slot(key.code, app);
+                }
+                return false;
+            }
+
+            let Some(action) = app.keymap.action(&key) else {
+                return false;
+            };
+            debug!("{:?}", action);
+            match action {
+                Action::Quit => {
+                    return true;
+                }
+                Action::Back => {
+                    app.pop_current_route();
+                }
+                Action::ToggleSidebar => {
+                    if !is_search_bar_active {
+                        focus_main(app);
+                    }
+                    app.is_toggled = !app.is_toggled;
+                }
+                Action::FocusSearch => {
+                    app.change_active_block(ActiveBlock::SearchBar);
+                }
+                Action::EditSearch => {
+                    if is_search_bar_active {
+                        app.input_mode = InputMode::Editing;
+                    }
+                }
+                Action::FocusLatestBlocks => {
+                    app.change_active_block(ActiveBlock::LatestBlocks);
+                }
+                Action::FocusLatestTransactions => {
+                    app.change_active_block(ActiveBlock::LatestTransactions);
+                }
+                Action::ShowHelp => {
+                    app.show_popup = true;
+                }
+                Action::Cancel => {
+                    if app.show_popup {
+                        app.show_popup = false;
+                    } else if !is_search_bar_active {
+                        app.cancel_search();
+                    }
+                }
+                _ if is_search_bar_active => {}
+                Action::NextItem => next_item(app),
+                Action::PrevItem => previous_item(app),
+                Action::NextPane => next_pane(app),
+                Action::PrevPane => previous_pane(app),
+                Action::NextPage => next_page(app),
+                Action::PrevPage => previous_page(app),
+                Action::Select => select(app),
+                Action::Refresh => refresh(app, terminal),
+            }
+        }
+        event::Event::Paste(data) => {
+            if app.is_editing_storage_slot {
+                app.storage_slot_input.push_str(&data);
+            } else if let ActiveBlock::SearchBar = app.get_current_route().get_active_block() {
                 match app.input_mode {
-                    InputMode::Normal => match key.code {
-                        event::KeyCode::Char('e') => {
-                            if key.modifiers == event::KeyModifiers::CONTROL {
-                                app.is_toggled = !app.is_toggled;
-                            }
-                        }
-                        event::KeyCode::Char('i') => {
-                            app.input_mode = InputMode::Editing;
+                    InputMode::Normal => {}
+                    InputMode::Editing => {
+                        app.paste(data);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    false
+}
+
+fn edit_search(code: event::KeyCode, app: &mut App) {
+    match code {
+        event::KeyCode::Enter => {
+            app.submit_message();
+            app.input_mode = InputMode::Normal;
+        }
+        event::KeyCode::Char(to_insert) => {
+            app.enter_char(to_insert);
+        }
+        event::KeyCode::Backspace => {
+            app.delete_char();
+        }
+        event::KeyCode::Left => {
+            app.move_cursor_left();
+        }
+        event::KeyCode::Right => {
+            app.move_cursor_right();
+        }
+        event::KeyCode::Esc => {
+            app.input_mode = InputMode::Normal;
+        }
+        _ => {}
+    }
+}
+
+fn edit_storage_slot(code: event::KeyCode, app: &mut App) {
+    match code {
+        event::KeyCode::Enter => {
+            app.is_editing_storage_slot = false;
+            if let RouteId::AddressInfo(Some(address_info)) = app.get_current_route().get_id() {
+                app.submit_storage_slot(address_info.address);
+            }
+        }
+        event::KeyCode::Char(to_insert) => {
+            app.storage_slot_input.push(to_insert);
+        }
+        event::KeyCode::Backspace => {
+            app.storage_slot_input.pop();
+        }
+        event::KeyCode::Esc => {
+            app.is_editing_storage_slot = false;
+        }
+        _ => {}
+    }
+}
+
+/// Moves the focus from the sidebar to the detail pane before the sidebar is hidden.
+fn focus_main(app: &mut App) {
+    match app.get_current_route().get_active_block() {
+        ActiveBlock::LatestBlocks => {
+            let latest_blocks = app.latest_blocks.clone();
+            if let Some(blocks) = latest_blocks {
+                if let Some(i) = blocks.get_selected_item_index() {
+                    app.dispatch(IoEvent::GetTransactionReceipts {
+                        transactions: blocks.items[i].block.transactions.to_owned(),
+                    });
+                }
+            }
+            app.change_active_block(ActiveBlock::Main);
+        }
+        ActiveBlock::LatestTransactions => {
+            app.change_active_block(ActiveBlock::Main);
+        }
+        _ => {}
+    }
+}
+
+fn select(app: &mut App) {
+    match app.get_current_route().get_active_block() {
+        ActiveBlock::LatestBlocks => {
+            let latest_blocks = app.latest_blocks.clone();
+            if let Some(blocks) = latest_blocks {
+                if let Some(i) = blocks.get_selected_item_index() {
+                    let block = blocks.items[i].to_owned();
+                    app.set_route(Route::new(
+                        RouteId::Block(Some(block.to_owned())),
+                        ActiveBlock::Main,
+                    ));
+                    app.dispatch(IoEvent::GetTransactionReceipts {
+                        transactions: block.block.transactions.to_owned(),
+                    });
+
+                    let mut addresses = vec![];
+                    for transaction in block.block.transactions {
+                        addresses.push(transaction.from);
+                        if let Some(to) = transaction.to {
+                            addresses.push(to);
                         }
-                        event::KeyCode::Char('q') => {
-                            return true;
+                    }
+
+                    app.dispatch(IoEvent::LookupAddresses { addresses });
+                }
+            }
+        }
+        ActiveBlock::LatestTransactions => {
+            let latest_transactions = app.latest_transactions.clone();
+            if let Some(transactions) = latest_transactions {
+                if let Some(i) = transactions.get_selected_item_index() {
+                    app.set_route(Route::new(
+                        RouteId::Transaction(Some(transactions.items[i].to_owned())),
+                        ActiveBlock::Main,
+                    ));
+                    app.dispatch(IoEvent::GetDecodedInputData {
+                        transaction: transactions.items[i].transaction.to_owned(),
+                    });
+                    app.dispatch(IoEvent::GetDecodedLogs {
+                        transaction_receipt: transactions.items[i].transaction_receipt.to_owned(),
+                    });
+                }
+            }
+        }
+        ActiveBlock::Main => match app.get_current_route().get_id() {
+            RouteId::Block(block) => {
+                if let Some(i) = app.block_detail_list_state.selected() {
+                    match SelectableBlockDetailItem::from(i) {
+                        SelectableBlockDetailItem::Transactions => {
+                            app.set_route(Route::new(
+                                RouteId::TransactionsOfBlock(block.to_owned()),
+                                ActiveBlock::Main,
+                            ));
                         }
-                        event::KeyCode::Char('1') => {
-                            app.change_active_block(ActiveBlock::LatestBlocks);
+                        SelectableBlockDetailItem::Withdrawls => {
+                            app.set_route(Route::new(
+                                RouteId::WithdrawalsOfBlock(block.to_owned()),
+                                ActiveBlock::Main,
+                            ));
                         }
-                        event::KeyCode::Char('2') => {
-                            app.change_active_block(ActiveBlock::LatestTransactions);
+                        SelectableBlockDetailItem::FeeRecipient => {
+                            if let Some(BlockWithTransactionReceipts {
+                                block,
+                                transaction_receipts: _,
+                            }) = block.as_ref()
+                            {
+                                if let Some(address) = block.author {
+                                    app.dispatch(IoEvent::GetNameOrAddressInfo {
+                                        name_or_address: NameOrAddress::Address(address),
+                                    });
+                                }
+                            }
                         }
-                        event::KeyCode::Char('p') => {
-                            if key.modifiers == event::KeyModifiers::CONTROL {
-                                app.pop_current_route();
+                        SelectableBlockDetailItem::ParentHash => {
+                            if let Some(BlockWithTransactionReceipts {
+                                block,
+                                transaction_receipts: _,
+                            }) = block.as_ref()
+                            {
+                                app.dispatch(IoEvent::GetBlockByHash {
+                                    hash: block.parent_hash,
+                                });
                             }
                         }
-                        event::KeyCode::Char('?') => {
-                            app.show_popup = true;
+                    }
+                }
+            }
+            RouteId::TransactionsOfBlock(block) => {
+                if let Some(BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: _,
+                }) = block.as_ref()
+                {
+                    if let Some(i) = app.transactions_table_state.selected() {
+                        if let Some(transaction) = block.transactions.get(i) {
+                            app.dispatch(IoEvent::GetTransactionWithReceipt {
+                                transaction_hash: transaction.hash,
+                            });
                         }
-                        event::KeyCode::Esc => {
-                            app.show_popup = false;
+                    }
+                }
+            }
+            RouteId::AddressInfo(Some(address_info)) => match selected_contract_detail_item(app) {
+                SelectableContractDetailItem::Transactions => {
+                    if let (Some(transactions), Some(i)) = (
+                        address_info.transactions,
+                        app.address_transactions_table_state.selected(),
+                    ) {
+                        if let Some(transaction) = transactions.transactions.get(i) {
+                            app.dispatch(IoEvent::GetTransactionWithReceipt {
+                                transaction_hash: transaction.transaction.hash,
+                            });
                         }
-                        _ => {}
-                    },
-                    InputMode::Editing if key.kind == event::KeyEventKind::Press => {
-                        match key.code {
-                            event::KeyCode::Enter => {
-                                let message = app.submit_message();
-                                app.input_mode = InputMode::Normal;
-                                app.set_route(Route::new(
-                                    RouteId::Searching(message),
-                                    ActiveBlock::Main,
-                                ));
-                            }
-                            event::KeyCode::Char(to_insert) => {
-                                app.enter_char(to_insert);
-                            }
-                            event::KeyCode::Backspace => {
-                                app.delete_char();
-                            }
-                            event::KeyCode::Left => {
-                                app.move_cursor_left();
-                            }
-                            event::KeyCode::Right => {
-                                app.move_cursor_right();
-                            }
-                            event::KeyCode::Esc => {
-                                app.input_mode = InputMode::Normal;
-                            }
-                            _ => {}
+                    }
+                }
+                SelectableContractDetailItem::Tokens => {
+                    if let (Some(token_balances), Some(i)) = (
+                        address_info.token_balances,
+                        app.token_balances_table_state.selected(),
+                    ) {
+                        if let Some(token_balance) = token_balances.get(i) {
+                            app.dispatch(IoEvent::GetNameOrAddressInfo {
+                                name_or_address: NameOrAddress::Address(
+                                    token_balance.token.contract_address,
+                                ),
+                            });
                         }
                     }
-                    _ => {}
-                }
-            } else {
-                match key.code {
-                    event::KeyCode::Enter => match app.get_current_route().get_active_block() {
-                        ActiveBlock::LatestBlocks => {
-                            let latest_blocks = app.latest_blocks.clone();
-                            if let Some(blocks) = latest_blocks {
-                                if let Some(i) = blocks.get_selected_item_index() {
-                                    let block = blocks.items[i].to_owned();
-                                    app.set_route(Route::new(
-                                        RouteId::Block(Some(block.to_owned())),
-                                        ActiveBlock::Main,
-                                    ));
-                                    app.dispatch(IoEvent::GetTransactionReceipts {
-                                        transactions: block.block.transactions.to_owned(),
-                                    });
-
-                                    let mut addresses = vec![];
-                                    for transaction in block.block.transactions {
-                                        addresses.push(transaction.from);
-                                        if let Some(to) = transaction.to {
-                                            addresses.push(to);
-                                        }
-                                    }
-
-                                    app.dispatch(IoEvent::LookupAddresses { addresses });
-                                }
+                }
+                SelectableContractDetailItem::Storage => {
+                    app.is_editing_storage_slot = true;
+                }
+                _ => {}
+            },
+            RouteId::Transaction(transaction) => {
+                if let Some(i) = app.transaction_detail_list_state.selected() {
+                    match SelectableTransactionDetailItem::from(i) {
+                        SelectableTransactionDetailItem::From => {
+                            if let Some(transaction) = transaction.as_ref() {
+                                app.dispatch(IoEvent::GetNameOrAddressInfo {
+                                    name_or_address: NameOrAddress::Address(
+                                        transaction.transaction.from,
+                                    ),
+                                });
                             }
                         }
-                        ActiveBlock::LatestTransactions => {
-                            let latest_transactions = app.latest_transactions.clone();
-                            if let Some(transactions) = latest_transactions {
-                                if let Some(i) = transactions.get_selected_item_index() {
-                                    app.set_route(Route::new(
-                                        RouteId::Transaction(Some(
-                                            transactions.items[i].to_owned(),
-                                        )),
-                                        ActiveBlock::Main,
-                                    ));
-                                    app.dispatch(IoEvent::GetDecodedInputData {
-                                        transaction: transactions.items[i].transaction.to_owned(),
+                        SelectableTransactionDetailItem::To => {
+                            if let Some(transaction) = transaction.as_ref() {
+                                if let Some(address) = transaction.transaction.to {
+                                    app.dispatch(IoEvent::GetNameOrAddressInfo {
+                                        name_or_address: NameOrAddress::Address(address),
                                     });
                                 }
                             }
                         }
-                        ActiveBlock::Main => match app.get_current_route().get_id() {
-                            RouteId::Block(block) => {
-                                if let Some(i) = app.block_detail_list_state.selected() {
-                                    match SelectableBlockDetailItem::from(i) {
-                                        SelectableBlockDetailItem::Transactions => {
-                                            app.set_route(Route::new(
-                                                RouteId::TransactionsOfBlock(block.to_owned()),
-                                                ActiveBlock::Main,
-                                            ));
-                                        }
-                                        SelectableBlockDetailItem::Withdrawls => {
-                                            app.set_route(Route::new(
-                                                RouteId::WithdrawalsOfBlock(block.to_owned()),
-                                                ActiveBlock::Main,
-                                            ));
-                                        }
-                                        SelectableBlockDetailItem::FeeRecipient => {
-                                            if let Some(BlockWithTransactionReceipts {
-                                                block,
-                                                transaction_receipts: _,
-                                            }) = block.as_ref()
-                                            {
-                                                if let Some(address) = block.author {
-                                                    app.dispatch(IoEvent::GetNameOrAddressInfo {
-                                                        name_or_address: NameOrAddress::Address(
-                                                            address,
-                                                        ),
-                                                        is_searching: false,
-                                                    });
-                                                }
-                                            }
-                                        }
-                                        SelectableBlockDetailItem::ParentHash => {
-                                            if let Some(BlockWithTransactionReceipts {
-                                                block,
-                                                transaction_receipts: _,
-                                            }) = block.as_ref()
-                                            {
-                                                app.dispatch(IoEvent::GetBlockByHash {
-                                                    hash: block.parent_hash,
-                                                });
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            RouteId::TransactionsOfBlock(block) => {
-                                if let Some(BlockWithTransactionReceipts {
-                                    block,
-                                    transaction_receipts: _,
-                                }) = block.as_ref()
-                                {
-                                    if let Some(i) = app.transactions_table_state.selected() {
-                                        if let Some(transaction) = block.transactions.get(i) {
-                                            app.dispatch(IoEvent::GetTransactionWithReceipt {
-                                                transaction_hash: transaction.hash,
-                                            });
-                                        }
-                                    }
-                                }
-                            }
-                            RouteId::Transaction(transaction) => {
-                                if let Some(i) = app.transaction_detail_list_state.selected() {
-                                    match SelectableTransactionDetailItem::from(i) {
-                                        SelectableTransactionDetailItem::From => {
-                                            if let Some(transaction) = transaction.as_ref() {
-                                                app.dispatch(IoEvent::GetNameOrAddressInfo {
-                                                    name_or_address: NameOrAddress::Address(
-                                                        transaction.transaction.from,
-                                                    ),
-                                                    is_searching: false,
-                                                });
-                                            }
-                                        }
-                                        SelectableTransactionDetailItem::To => {
-                                            if let Some(transaction) = transaction.as_ref() {
-                                                if let Some(address) = transaction.transaction.to {
-                                                    app.dispatch(IoEvent::GetNameOrAddressInfo {
-                                                        name_or_address: NameOrAddress::Address(
-                                                            address,
-                                                        ),
-                                                        is_searching: false,
-                                                    });
-                                                }
-                                            }
-                                        }
-                                        SelectableTransactionDetailItem::InputData => {
-                                            app.set_route(Route::new(
-                                                RouteId::InputDataOfTransaction(
-                                                    transaction.to_owned(),
-                                                ),
-                                                ActiveBlock::Main,
-                                            ));
-                                        }
-                                    }
-                                }
-                            }
-                            _ => {}
-                        },
-                        _ => {}
-                    },
-                    event::KeyCode::Char('e') => {
-                        if key.modifiers == event::KeyModifiers::CONTROL {
-                            match app.get_current_route().get_active_block() {
-                                ActiveBlock::LatestBlocks => {
-                                    let latest_blocks = app.latest_blocks.clone();
-                                    if let Some(blocks) = latest_blocks {
-                                        if let Some(i) = blocks.get_selected_item_index() {
-                                            app.dispatch(IoEvent::GetTransactionReceipts {
-                                                transactions: blocks.items[i]
-                                                    .block
-                                                    .transactions
-                                                    .to_owned(),
-                                            });
-                                        }
-                                    }
-                                    app.change_active_block(ActiveBlock::Main);
-                                }
-                                ActiveBlock::LatestTransactions => {
-                                    app.change_active_block(ActiveBlock::Main);
-                                }
-                                _ => {}
-                            }
-
-                            app.is_toggled = !app.is_toggled;
+                        SelectableTransactionDetailItem::InputData => {
+                            app.set_route(Route::new(
+                                RouteId::InputDataOfTransaction(transaction.to_owned()),
+                                ActiveBlock::Main,
+                            ));
                         }
                     }
-                    event::KeyCode::Char('p') => {
-                        if key.modifiers == event::KeyModifiers::CONTROL {
-                            app.pop_current_route();
-                        }
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+fn next_item(app: &mut App) {
+    match app.get_current_route().get_active_block() {
+        ActiveBlock::LatestBlocks => {
+            if let Some(latest_blocks) = app.latest_blocks.as_mut() {
+                latest_blocks.next();
+                let latest_blocks = app.latest_blocks.clone();
+                if let Some(blocks) = latest_blocks {
+                    if let Some(i) = blocks.get_selected_item_index() {
+                        app.set_route(Route::new(
+                            RouteId::Block(Some(blocks.items[i].to_owned())),
+                            ActiveBlock::LatestBlocks,
+                        ));
                     }
-                    event::KeyCode::Char('q') => {
-                        return true;
-                    }
-                    event::KeyCode::Char('s') => {
-                        app.change_active_block(ActiveBlock::SearchBar);
-                    }
-                    event::KeyCode::Char('1') => {
-                        app.change_active_block(ActiveBlock::LatestBlocks);
-                    }
-                    event::KeyCode::Char('2') => {
-                        app.change_active_block(ActiveBlock::LatestTransactions);
-                    }
-                    event::KeyCode::Char('j') => match app.get_current_route().get_active_block() {
-                        ActiveBlock::LatestBlocks => {
-                            if let Some(latest_blocks) = app.latest_blocks.as_mut() {
-                                latest_blocks.next();
-                                let latest_blocks = app.latest_blocks.clone();
-                                if let Some(blocks) = latest_blocks {
-                                    if let Some(i) = blocks.get_selected_item_index() {
-                                        app.set_route(Route::new(
-                                            RouteId::Block(Some(blocks.items[i].to_owned())),
-                                            ActiveBlock::LatestBlocks,
-                                        ));
-                                    }
-                                }
-                            }
+                }
+            }
+        }
+        ActiveBlock::LatestTransactions => {
+            if let Some(latest_transactions) = app.latest_transactions.as_mut() {
+                latest_transactions.next();
+                let latest_transactions = app.latest_transactions.clone();
+                if let Some(transactions) = latest_transactions {
+                    if let Some(i) = transactions.get_selected_item_index() {
+                        app.set_route(Route::new(
+                            RouteId::Transaction(Some(transactions.items[i].to_owned())),
+                            ActiveBlock::LatestTransactions,
+                        ));
+                    }
+                }
+            }
+        }
+        ActiveBlock::Main => match app.get_current_route().get_id() {
+            RouteId::Block(block) => {
+                if let Some(BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: _,
+                }) = block.as_ref()
+                {
+                    if let Some(i) = app.block_detail_list_state.selected() {
+                        app.block_detail_list_state
+                            .select(Some(SelectableBlockDetailItem::from(i).next(block).into()));
+                    } else {
+                        app.block_detail_list_state
+                            .select(Some(SelectableBlockDetailItem::Transactions.into()));
+                    }
+                }
+            }
+            RouteId::TransactionsOfBlock(block) => {
+                if let Some(BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: _,
+                }) = block.as_ref()
+                {
+                    if !block.transactions.is_empty() {
+                        if let Some(i) = app.transactions_table_state.selected() {
+                            app.transactions_table_state
+                                .select(Some((i + 1) % block.transactions.len()));
+                        } else {
+                            app.transactions_table_state.select(Some(0));
                         }
-                        ActiveBlock::LatestTransactions => {
-                            if let Some(latest_transactions) = app.latest_transactions.as_mut() {
-                                latest_transactions.next();
-                                let latest_transactions = app.latest_transactions.clone();
-                                if let Some(transactions) = latest_transactions {
-                                    if let Some(i) = transactions.get_selected_item_index() {
-                                        app.set_route(Route::new(
-                                            RouteId::Transaction(Some(
-                                                transactions.items[i].to_owned(),
-                                            )),
-                                            ActiveBlock::LatestTransactions,
-                                        ));
-                                    }
-                                }
-                            }
+                    }
+                }
+            }
+            RouteId::WithdrawalsOfBlock(block) => {
+                if let Some(BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: _,
+                }) = block.as_ref()
+                {
+                    if let Some(withdrawals) = block.withdrawals.as_ref() {
+                        if let Some(i) = app.withdrawals_table_state.selected() {
+                            app.withdrawals_table_state
+                                .select(Some((i + 1) % withdrawals.len()));
+                        } else {
+                            app.withdrawals_table_state.select(Some(0));
                         }
-                        ActiveBlock::Main => match app.get_current_route().get_id() {
-                            RouteId::Block(block) => {
-                                if let Some(BlockWithTransactionReceipts {
-                                    block,
-                                    transaction_receipts: _,
-                                }) = block.as_ref()
-                                {
-                                    if let Some(i) = app.block_detail_list_state.selected() {
-                                        app.block_detail_list_state.select(Some(
-                                            SelectableBlockDetailItem::from(i).next(block).into(),
-                                        ));
-                                    } else {
-                                        app.block_detail_list_state.select(Some(
-                                            SelectableBlockDetailItem::Transactions.into(),
-                                        ));
-                                    }
-                                }
-                            }
-                            RouteId::TransactionsOfBlock(block) => {
-                                if let Some(BlockWithTransactionReceipts {
-                                    block,
-                                    transaction_receipts: _,
-                                }) = block.as_ref()
-                                {
-                                    if !block.transactions.is_empty() {
-                                        if let Some(i) = app.transactions_table_state.selected() {
-                                            app.transactions_table_state
-                                                .select(Some((i + 1) % block.transactions.len()));
-                                        } else {
-                                            app.transactions_table_state.select(Some(0));
-                                        }
-                                    }
-                                }
-                            }
-                            RouteId::WithdrawalsOfBlock(block) => {
-                                if let Some(BlockWithTransactionReceipts {
-                                    block,
-                                    transaction_receipts: _,
-                                }) = block.as_ref()
-                                {
-                                    if let Some(withdrawals) = block.withdrawals.as_ref() {
-                                        if let Some(i) = app.withdrawals_table_state.selected() {
-                                            app.withdrawals_table_state
-                                                .select(Some((i + 1) % withdrawals.len()));
-                                        } else {
-                                            app.withdrawals_table_state.select(Some(0));
-                                        }
-                                    }
-                                }
-                            }
-                            RouteId::Transaction(transaction) => {
-                                if let Some(transaction) = transaction.as_ref() {
-                                    if let Some(i) = app.transaction_detail_list_state.selected() {
-                                        app.transaction_detail_list_state.select(Some(
-                                            SelectableTransactionDetailItem::from(i)
-                                                .next(transaction)
-                                                .into(),
-                                        ));
-                                    } else {
-                                        app.transaction_detail_list_state.select(Some(
-                                            SelectableTransactionDetailItem::From.into(),
-                                        ));
-                                    }
-                                }
-                            }
-                            RouteId::InputDataOfTransaction(_) => {
-                                match SelectableInputDataDetailItem::from(
-                                    app.input_data_detail_list_state
-                                        .selected()
-                                        .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
-                                ) {
-                                    SelectableInputDataDetailItem::InputData => {
-                                        app.input_data_scroll =
-                                            app.input_data_scroll.saturating_add(1);
-                                        app.input_data_scroll_state = app
-                                            .input_data_scroll_state
-                                            .position(app.input_data_scroll);
-                                    }
-                                    SelectableInputDataDetailItem::DecodedInputData => {
-                                        app.decoded_input_data_scroll =
-                                            app.decoded_input_data_scroll.saturating_add(1);
-                                        app.decoded_input_data_scroll_state = app
-                                            .decoded_input_data_scroll_state
-                                            .position(app.decoded_input_data_scroll);
-                                    }
-                                }
-                            }
-                            RouteId::AddressInfo(_) => match SelectableContractDetailItem::from(
-                                app.contract_list_state.selected().unwrap_or(
-                                    SelectableContractDetailItem::ContractSourceCode.into(),
-                                ),
-                            ) {
-                                SelectableContractDetailItem::ContractSourceCode => {
-                                    app.source_code_scroll =
-                                        app.source_code_scroll.saturating_add(1);
-                                    app.source_code_scroll_state = app
-                                        .source_code_scroll_state
-                                        .position(app.source_code_scroll);
-                                }
-                                SelectableContractDetailItem::ContractAbi => {
-                                    app.abi_scroll = app.abi_scroll.saturating_add(1);
-                                    app.abi_scroll_state =
-                                        app.abi_scroll_state.position(app.abi_scroll);
-                                }
-                            },
-                            _ => {}
-                        },
-                        _ => {}
-                    },
-                    event::KeyCode::Char('k') => match app.get_current_route().get_active_block() {
-                        ActiveBlock::LatestBlocks => {
-                            if let Some(latest_blocks) = app.latest_blocks.as_mut() {
-                                latest_blocks.previous();
-                                let latest_blocks = app.latest_blocks.clone();
-                                if let Some(blocks) = latest_blocks {
-                                    if let Some(i) = blocks.get_selected_item_index() {
-                                        app.set_route(Route::new(
-                                            RouteId::Block(Some(blocks.items[i].to_owned())),
-                                            ActiveBlock::LatestBlocks,
-                                        ));
-                                    }
-                                }
+                    }
+                }
+            }
+            RouteId::Transaction(transaction) => {
+                if let Some(transaction) = transaction.as_ref() {
+                    if let Some(i) = app.transaction_detail_list_state.selected() {
+                        app.transaction_detail_list_state.select(Some(
+                            SelectableTransactionDetailItem::from(i)
+                                .next(transaction)
+                                .into(),
+                        ));
+                    } else {
+                        app.transaction_detail_list_state
+                            .select(Some(SelectableTransactionDetailItem::From.into()));
+                    }
+                }
+            }
+            RouteId::InputDataOfTransaction(_) => {
+                match SelectableInputDataDetailItem::from(
+                    app.input_data_detail_list_state
+                        .selected()
+                        .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
+                ) {
+                    SelectableInputDataDetailItem::InputData => {
+                        app.input_data_scroll = app.input_data_scroll.saturating_add(1);
+                        app.input_data_scroll_state =
+                            app.input_data_scroll_state.position(app.input_data_scroll);
+                    }
+                    SelectableInputDataDetailItem::DecodedInputData => {
+                        app.decoded_input_data_scroll =
+                            app.decoded_input_data_scroll.saturating_add(1);
+                        app.decoded_input_data_scroll_state = app
+                            .decoded_input_data_scroll_state
+                            .position(app.decoded_input_data_scroll);
+                    }
+                    SelectableInputDataDetailItem::Logs => {
+                        app.logs_scroll = app.logs_scroll.saturating_add(1);
+                        app.logs_scroll_state = app.logs_scroll_state.position(app.logs_scroll);
+                    }
+                }
+            }
+            RouteId::AddressInfo(address_info) => match SelectableContractDetailItem::from(
+                app.contract_list_state
+                    .selected()
+                    .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
+            ) {
+                SelectableContractDetailItem::ContractSourceCode => {
+                    app.source_code_scroll = app.source_code_scroll.saturating_add(1);
+                    app.source_code_scroll_state = app
+                        .source_code_scroll_state
+                        .position(app.source_code_scroll);
+                }
+                SelectableContractDetailItem::ContractAbi => {
+                    app.abi_scroll = app.abi_scroll.saturating_add(1);
+                    app.abi_scroll_state = app.abi_scroll_state.position(app.abi_scroll);
+                }
+                SelectableContractDetailItem::Bytecode => {
+                    app.bytecode_scroll = app.bytecode_scroll.saturating_add(1);
+                    app.bytecode_scroll_state =
+                        app.bytecode_scroll_state.position(app.bytecode_scroll);
+                }
+                SelectableContractDetailItem::Storage => {
+                    app.storage_scroll = app.storage_scroll.saturating_add(1);
+                    app.storage_scroll_state =
+                        app.storage_scroll_state.position(app.storage_scroll);
+                }
+                SelectableContractDetailItem::Transactions => {
+                    if let Some(transactions) =
+                        address_info.and_then(|address_info| address_info.transactions)
+                    {
+                        let len = transactions.transactions.len();
+                        if len > 0 {
+                            if let Some(i) = app.address_transactions_table_state.selected() {
+                                app.address_transactions_table_state
+                                    .select(Some((i + 1) % len));
+                            } else {
+                                app.address_transactions_table_state.select(Some(0));
                             }
                         }
-                        ActiveBlock::LatestTransactions => {
-                            if let Some(latest_transactions) = app.latest_transactions.as_mut() {
-                                latest_transactions.previous();
-                                let latest_transactions = app.latest_transactions.clone();
-                                if let Some(transactions) = latest_transactions {
-                                    if let Some(i) = transactions.get_selected_item_index() {
-                                        app.set_route(Route::new(
-                                            RouteId::Transaction(Some(
-                                                transactions.items[i].to_owned(),
-                                            )),
-                                            ActiveBlock::LatestTransactions,
-                                        ));
-                                    }
-                                }
+                    }
+                }
+                SelectableContractDetailItem::Tokens => {
+                    if let Some(token_balances) =
+                        address_info.and_then(|address_info| address_info.token_balances)
+                    {
+                        let len = token_balances.len();
+                        if len > 0 {
+                            if let Some(i) = app.token_balances_table_state.selected() {
+                                app.token_balances_table_state.select(Some((i + 1) % len));
+                            } else {
+                                app.token_balances_table_state.select(Some(0));
                             }
                         }
-                        ActiveBlock::Main => match app.get_current_route().get_id() {
-                            RouteId::Block(block) => {
-                                if let Some(BlockWithTransactionReceipts {
-                                    block,
-                                    transaction_receipts: _,
-                                }) = block.as_ref()
-                                {
-                                    if let Some(i) = app.block_detail_list_state.selected() {
-                                        app.block_detail_list_state.select(Some(
-                                            SelectableBlockDetailItem::from(i)
-                                                .previous(block)
-                                                .into(),
-                                        ));
-                                    } else {
-                                        app.block_detail_list_state.select(Some(
-                                            SelectableBlockDetailItem::Transactions.into(),
-                                        ));
-                                    }
-                                }
-                            }
-                            RouteId::TransactionsOfBlock(block) => {
-                                if let Some(BlockWithTransactionReceipts {
-                                    block,
-                                    transaction_receipts: _,
-                                }) = block.as_ref()
-                                {
-                                    if !block.transactions.is_empty() {
-                                        if let Some(i) = app.transactions_table_state.selected() {
-                                            app.transactions_table_state.select(Some(
-                                                (i + block.transactions.len() - 1)
-                                                    % block.transactions.len(),
-                                            ));
-                                        } else {
-                                            app.transactions_table_state.select(Some(0));
-                                        }
-                                    }
-                                }
-                            }
-                            RouteId::WithdrawalsOfBlock(block) => {
-                                if let Some(BlockWithTransactionReceipts {
-                                    block,
-                                    transaction_receipts: _,
-                                }) = block.as_ref()
-                                {
-                                    if let Some(withdrawals) = block.withdrawals.as_ref() {
-                                        if let Some(i) = app.withdrawals_table_state.selected() {
-                                            app.withdrawals_table_state.select(Some(
-                                                (i + withdrawals.len() - 1) % withdrawals.len(),
-                                            ));
-                                        } else {
-                                            app.withdrawals_table_state.select(Some(0));
-                                        }
-                                    }
-                                }
-                            }
-                            RouteId::Transaction(Some(transaction)) => {
-                                if let Some(i) = app.transaction_detail_list_state.selected() {
-                                    app.transaction_detail_list_state.select(Some(
-                                        SelectableTransactionDetailItem::from(i)
-                                            .previous(&transaction)
-                                            .into(),
-                                    ));
-                                } else {
-                                    app.transaction_detail_list_state
-                                        .select(Some(SelectableTransactionDetailItem::From.into()));
-                                }
-                            }
-                            RouteId::InputDataOfTransaction(_) => {
-                                match SelectableInputDataDetailItem::from(
-                                    app.input_data_detail_list_state
-                                        .selected()
-                                        .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
-                                ) {
-                                    SelectableInputDataDetailItem::InputData => {
-                                        app.input_data_scroll =
-                                            app.input_data_scroll.saturating_sub(1);
-                                        app.input_data_scroll_state = app
-                                            .input_data_scroll_state
-                                            .position(app.input_data_scroll);
-                                    }
-                                    SelectableInputDataDetailItem::DecodedInputData => {
-                                        app.decoded_input_data_scroll =
-                                            app.decoded_input_data_scroll.saturating_sub(1);
-                                        app.decoded_input_data_scroll_state = app
-                                            .decoded_input_data_scroll_state
-                                            .position(app.decoded_input_data_scroll);
-                                    }
-                                }
-                            }
-                            RouteId::AddressInfo(_) => match SelectableContractDetailItem::from(
-                                app.contract_list_state.selected().unwrap_or(
-                                    SelectableContractDetailItem::ContractSourceCode.into(),
-                                ),
-                            ) {
-                                SelectableContractDetailItem::ContractSourceCode => {
-                                    app.source_code_scroll =
-                                        app.source_code_scroll.saturating_sub(1);
-                                    app.source_code_scroll_state = app
-                                        .source_code_scroll_state
-                                        .position(app.source_code_scroll);
-                                }
-                                SelectableContractDetailItem::ContractAbi => {
-                                    app.abi_scroll = app.abi_scroll.saturating_sub(1);
-                                    app.abi_scroll_state =
-                                        app.abi_scroll_state.position(app.abi_scroll);
-                                }
-                            },
-                            _ => {}
-                        },
-                        _ => {}
-                    },
-                    event::KeyCode::Char('r') => match app.get_current_route().get_active_block() {
-                        ActiveBlock::LatestBlocks => {
-                            let height = terminal.size().unwrap().height as usize;
-                            app.statistics = Statistics::new();
-                            app.latest_blocks = None;
-                            app.dispatch(IoEvent::GetStatistics);
-                            app.dispatch(IoEvent::GetLatestBlocks {
-                                n: (height - 3 * 4) / 2 - 4,
-                            });
+                    }
+                }
+            },
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+fn previous_item(app: &mut App) {
+    match app.get_current_route().get_active_block() {
+        ActiveBlock::LatestBlocks => {
+            if let Some(latest_blocks) = app.latest_blocks.as_mut() {
+                latest_blocks.previous();
+                let latest_blocks = app.latest_blocks.clone();
+                if let Some(blocks) = latest_blocks {
+                    if let Some(i) = blocks.get_selected_item_index() {
+                        app.set_route(Route::new(
+                            RouteId::Block(Some(blocks.items[i].to_owned())),
+                            ActiveBlock::LatestBlocks,
+                        ));
+                    }
+                }
+            }
+        }
+        ActiveBlock::LatestTransactions => {
+            if let Some(latest_transactions) = app.latest_transactions.as_mut() {
+                latest_transactions.previous();
+                let latest_transactions = app.latest_transactions.clone();
+                if let Some(transactions) = latest_transactions {
+                    if let Some(i) = transactions.get_selected_item_index() {
+                        app.set_route(Route::new(
+                            RouteId::Transaction(Some(transactions.items[i].to_owned())),
+                            ActiveBlock::LatestTransactions,
+                        ));
+                    }
+                }
+            }
+        }
+        ActiveBlock::Main => match app.get_current_route().get_id() {
+            RouteId::Block(block) => {
+                if let Some(BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: _,
+                }) = block.as_ref()
+                {
+                    if let Some(i) = app.block_detail_list_state.selected() {
+                        app.block_detail_list_state.select(Some(
+                            SelectableBlockDetailItem::from(i).previous(block).into(),
+                        ));
+                    } else {
+                        app.block_detail_list_state
+                            .select(Some(SelectableBlockDetailItem::Transactions.into()));
+                    }
+                }
+            }
+            RouteId::TransactionsOfBlock(block) => {
+                if let Some(BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: _,
+                }) = block.as_ref()
+                {
+                    if !block.transactions.is_empty() {
+                        if let Some(i) = app.transactions_table_state.selected() {
+                            app.transactions_table_state.select(Some(
+                                (i + block.transactions.len() - 1) % block.transactions.len(),
+                            ));
+                        } else {
+                            app.transactions_table_state.select(Some(0));
                         }
-                        ActiveBlock::LatestTransactions => {
-                            let height = terminal.size().unwrap().height as usize;
-                            app.latest_transactions = None;
-                            app.dispatch(IoEvent::GetLatestTransactions {
-                                n: (height - 3 * 4) / 2 - 4,
-                            });
+                    }
+                }
+            }
+            RouteId::WithdrawalsOfBlock(block) => {
+                if let Some(BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: _,
+                }) = block.as_ref()
+                {
+                    if let Some(withdrawals) = block.withdrawals.as_ref() {
+                        if let Some(i) = app.withdrawals_table_state.selected() {
+                            app.withdrawals_table_state
+                                .select(Some((i + withdrawals.len() - 1) % withdrawals.len()));
+                        } else {
+                            app.withdrawals_table_state.select(Some(0));
                         }
-                        _ => {}
-                    },
-                    event::KeyCode::Right => {
-                        if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                            match app.get_current_route().get_id() {
-                                RouteId::AddressInfo(Some(address_info)) => {
-                                    app.contract_list_state.select(Some(
-                                        SelectableContractDetailItem::from(
-                                            app.contract_list_state.selected().unwrap_or(
-                                                SelectableContractDetailItem::ContractSourceCode
-                                                    .into(),
-                                            ),
-                                        )
-                                        .next(&address_info)
-                                        .into(),
-                                    ))
-                                }
-                                RouteId::Transaction(Some(_))
-                                | RouteId::InputDataOfTransaction(Some(_)) => {
-                                    app.input_data_detail_list_state.select(Some(
-                                        SelectableInputDataDetailItem::from(
-                                            app.input_data_detail_list_state.selected().unwrap_or(
-                                                SelectableInputDataDetailItem::InputData.into(),
-                                            ),
-                                        )
-                                        .next()
-                                        .into(),
-                                    ));
-                                }
-                                _ => {}
+                    }
+                }
+            }
+            RouteId::Transaction(Some(transaction)) => {
+                if let Some(i) = app.transaction_detail_list_state.selected() {
+                    app.transaction_detail_list_state.select(Some(
+                        SelectableTransactionDetailItem::from(i)
+                            .previous(&transaction)
+                            .into(),
+                    ));
+                } else {
+                    app.transaction_detail_list_state
+                        .select(Some(SelectableTransactionDetailItem::From.into()));
+                }
+            }
+            RouteId::InputDataOfTransaction(_) => {
+                match SelectableInputDataDetailItem::from(
+                    app.input_data_detail_list_state
+                        .selected()
+                        .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
+                ) {
+                    SelectableInputDataDetailItem::InputData => {
+                        app.input_data_scroll = app.input_data_scroll.saturating_sub(1);
+                        app.input_data_scroll_state =
+                            app.input_data_scroll_state.position(app.input_data_scroll);
+                    }
+                    SelectableInputDataDetailItem::DecodedInputData => {
+                        app.decoded_input_data_scroll =
+                            app.decoded_input_data_scroll.saturating_sub(1);
+                        app.decoded_input_data_scroll_state = app
+                            .decoded_input_data_scroll_state
+                            .position(app.decoded_input_data_scroll);
+                    }
+                    SelectableInputDataDetailItem::Logs => {
+                        app.logs_scroll = app.logs_scroll.saturating_sub(1);
+                        app.logs_scroll_state = app.logs_scroll_state.position(app.logs_scroll);
+                    }
+                }
+            }
+            RouteId::AddressInfo(address_info) => match SelectableContractDetailItem::from(
+                app.contract_list_state
+                    .selected()
+                    .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
+            ) {
+                SelectableContractDetailItem::ContractSourceCode => {
+                    app.source_code_scroll = app.source_code_scroll.saturating_sub(1);
+                    app.source_code_scroll_state = app
+                        .source_code_scroll_state
+                        .position(app.source_code_scroll);
+                }
+                SelectableContractDetailItem::ContractAbi => {
+                    app.abi_scroll = app.abi_scroll.saturating_sub(1);
+                    app.abi_scroll_state = app.abi_scroll_state.position(app.abi_scroll);
+                }
+                SelectableContractDetailItem::Bytecode => {
+                    app.bytecode_scroll = app.bytecode_scroll.saturating_sub(1);
+                    app.bytecode_scroll_state =
+                        app.bytecode_scroll_state.position(app.bytecode_scroll);
+                }
+                SelectableContractDetailItem::Storage => {
+                    app.storage_scroll = app.storage_scroll.saturating_sub(1);
+                    app.storage_scroll_state =
+                        app.storage_scroll_state.position(app.storage_scroll);
+                }
+                SelectableContractDetailItem::Transactions => {
+                    if let Some(transactions) =
+                        address_info.and_then(|address_info| address_info.transactions)
+                    {
+                        let len = transactions.transactions.len();
+                        if len > 0 {
+                            if let Some(i) = app.address_transactions_table_state.selected() {
+                                app.address_transactions_table_state
+                                    .select(Some((i + len - 1) % len));
+                            } else {
+                                app.address_transactions_table_state.select(Some(0));
                             }
                         }
                     }
-                    event::KeyCode::Left => {
-                        if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                            match app.get_current_route().get_id() {
-                                RouteId::AddressInfo(Some(address_info)) => {
-                                    app.contract_list_state.select(Some(
-                                        SelectableContractDetailItem::from(
-                                            app.contract_list_state.selected().unwrap_or(
-                                                SelectableContractDetailItem::ContractSourceCode
-                                                    .into(),
-                                            ),
-                                        )
-                                        .previous(&address_info)
-                                        .into(),
-                                    ))
-                                }
-                                RouteId::Transaction(Some(_))
-                                | RouteId::InputDataOfTransaction(Some(_)) => {
-                                    app.input_data_detail_list_state.select(Some(
-                                        SelectableInputDataDetailItem::from(
-                                            app.input_data_detail_list_state.selected().unwrap_or(
-                                                SelectableInputDataDetailItem::InputData.into(),
-                                            ),
-                                        )
-                                        .previous()
-                                        .into(),
-                                    ));
-                                }
-                                _ => {}
+                }
+                SelectableContractDetailItem::Tokens => {
+                    if let Some(token_balances) =
+                        address_info.and_then(|address_info| address_info.token_balances)
+                    {
+                        let len = token_balances.len();
+                        if len > 0 {
+                            if let Some(i) = app.token_balances_table_state.selected() {
+                                app.token_balances_table_state
+                                    .select(Some((i + len - 1) % len));
+                            } else {
+                                app.token_balances_table_state.select(Some(0));
                             }
                         }
                     }
-                    event::KeyCode::Char('?') => {
-                        app.show_popup = true;
-                    }
-                    event::KeyCode::Esc => {
-                        app.show_popup = false;
-                    }
-                    _ => {}
                 }
+            },
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+fn next_pane(app: &mut App) {
+    if let ActiveBlock::Main = app.get_current_route().get_active_block() {
+        match app.get_current_route().get_id() {
+            RouteId::AddressInfo(Some(address_info)) => app.contract_list_state.select(Some(
+                SelectableContractDetailItem::from(
+                    app.contract_list_state
+                        .selected()
+                        .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
+                )
+                .next(&address_info)
+                .into(),
+            )),
+            RouteId::Transaction(Some(_)) | RouteId::InputDataOfTransaction(Some(_)) => {
+                app.input_data_detail_list_state.select(Some(
+                    SelectableInputDataDetailItem::from(
+                        app.input_data_detail_list_state
+                            .selected()
+                            .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
+                    )
+                    .next()
+                    .into(),
+                ));
             }
+            _ => {}
         }
-        event::Event::Paste(data) => {
-            if let ActiveBlock::SearchBar = app.get_current_route().get_active_block() {
-                match app.input_mode {
-                    InputMode::Normal => {}
-                    InputMode::Editing => {
-                        app.paste(data);
-                    }
-                }
+    }
+    app.load_selected_address_tab();
+}
+
+fn previous_pane(app: &mut App) {
+    if let ActiveBlock::Main = app.get_current_route().get_active_block() {
+        match app.get_current_route().get_id() {
+            RouteId::AddressInfo(Some(address_info)) => app.contract_list_state.select(Some(
+                SelectableContractDetailItem::from(
+                    app.contract_list_state
+                        .selected()
+                        .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
+                )
+                .previous(&address_info)
+                .into(),
+            )),
+            RouteId::Transaction(Some(_)) | RouteId::InputDataOfTransaction(Some(_)) => {
+                app.input_data_detail_list_state.select(Some(
+                    SelectableInputDataDetailItem::from(
+                        app.input_data_detail_list_state
+                            .selected()
+                            .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
+                    )
+                    .previous()
+                    .into(),
+                ));
             }
+            _ => {}
+        }
+    }
+    app.load_selected_address_tab();
+}
+
+/// Loads the older transactions of the address on screen.
+fn next_page(app: &mut App) {
+    if let Some(transactions) = shown_address_transactions(app) {
+        if transactions.has_next_page() {
+            app.dispatch(IoEvent::GetAddressTransactions {
+                address: transactions.address,
+                page: transactions.page + 1,
+                until: Some(transactions.until),
+            });
+        }
+    }
+}
+
+/// Loads the newer transactions of the address on screen.
+fn previous_page(app: &mut App) {
+    if let Some(transactions) = shown_address_transactions(app) {
+        if transactions.page > 0 {
+            app.dispatch(IoEvent::GetAddressTransactions {
+                address: transactions.address,
+                page: transactions.page - 1,
+                until: Some(transactions.until),
+            });
         }
+    }
+}
+
+/// The transactions of the address on screen, if their tab is open.
+fn shown_address_transactions(app: &App) -> Option<AddressTransactions> {
+    if !matches!(
+        app.get_current_route().get_active_block(),
+        ActiveBlock::Main
+    ) {
+        return None;
+    }
+    let RouteId::AddressInfo(Some(address_info)) = app.get_current_route().get_id() else {
+        return None;
+    };
+    match selected_contract_detail_item(app) {
+        SelectableContractDetailItem::Transactions => address_info.transactions,
+        _ => None,
+    }
+}
+
+fn selected_contract_detail_item(app: &App) -> SelectableContractDetailItem {
+    SelectableContractDetailItem::from(
+        app.contract_list_state
+            .selected()
+            .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
+    )
+}
+
+/// Reloads the focused pane, or retries the failed request shown in the detail pane.
+fn refresh<B: Backend>(app: &mut App, terminal: &Terminal<B>) {
+    match app.get_current_route().get_active_block() {
+        ActiveBlock::LatestBlocks => {
+            let height = terminal.size().unwrap().height as usize;
+            app.statistics = Statistics::new();
+            app.latest_blocks = None;
+            app.dispatch(IoEvent::GetStatistics);
+            app.dispatch(IoEvent::GetLatestBlocks {
+                n: (height - 3 * 4) / 2 - 4,
+            });
+        }
+        ActiveBlock::LatestTransactions => {
+            let height = terminal.size().unwrap().height as usize;
+            app.latest_transactions = None;
+            app.dispatch(IoEvent::GetLatestTransactions {
+                n: (height - 3 * 4) / 2 - 4,
+            });
+        }
+        ActiveBlock::Main => match app.get_current_route().get_id() {
+            RouteId::Failed(notification) => app.retry(notification),
+            RouteId::AddressInfo(Some(address_info)) => match selected_contract_detail_item(app) {
+                SelectableContractDetailItem::Transactions => {
+                    // Retry the page that failed, or start over from the latest block.
+                    let io_event = app
+                        .find_failure(|io_event| {
+                            matches!(
+                                io_event,
+                                IoEvent::GetAddressTransactions { address, .. }
+                                    if *address == address_info.address
+                            )
+                        })
+                        .map(|notification| notification.io_event.to_owned())
+                        .unwrap_or(IoEvent::GetAddressTransactions {
+                            address: address_info.address,
+                            page: 0,
+                            until: None,
+                        });
+                    app.dispatch(io_event);
+                }
+                SelectableContractDetailItem::Tokens => {
+                    app.dispatch(IoEvent::GetTokenBalances {
+                        address: address_info.address,
+                    });
+                }
+                SelectableContractDetailItem::Storage => {
+                    for storage_slot in address_info.storage_slots {
+                        app.dispatch(IoEvent::GetStorageAt {
+                            address: address_info.address,
+                            slot: storage_slot.slot,
+                        });
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        },
         _ => {}
     }
-    false
 }
@@ -0,0 +1,503 @@
+use crate::route::{ActiveBlock, RouteId};
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// What a key press does, independent of the key it's bound to.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    /// Goes back to the previous page.
+    Back,
+    ToggleSidebar,
+    FocusSearch,
+    /// Starts typing in the search bar.
+    EditSearch,
+    FocusLatestBlocks,
+    FocusLatestTransactions,
+    NextItem,
+    PrevItem,
+    /// Switches to the next tab or pane, e.g. from the source code to the ABI of a contract.
+    NextPane,
+    PrevPane,
+    /// Loads the next page of a paginated list, e.g. the transactions of an address.
+    NextPage,
+    PrevPage,
+    Select,
+    /// Reloads the focused pane, or retries a failed request.
+    Refresh,
+    ShowHelp,
+    /// Closes the help, or cancels the search in progress.
+    Cancel,
+}
+
+impl Action {
+    /// Every action, in the order the help lists them.
+    pub const ALL: [Action; 17] = [
+        Action::Select,
+        Action::NextItem,
+        Action::PrevItem,
+        Action::NextPane,
+        Action::PrevPane,
+        Action::NextPage,
+        Action::PrevPage,
+        Action::Refresh,
+        Action::EditSearch,
+        Action::FocusSearch,
+        Action::FocusLatestBlocks,
+        Action::FocusLatestTransactions,
+        Action::ToggleSidebar,
+        Action::Back,
+        Action::Cancel,
+        Action::ShowHelp,
+        Action::Quit,
+    ];
+
+    /// A word or two for the navigation bar.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Back => "Back",
+            Action::ToggleSidebar => "Sidebar",
+            Action::FocusSearch => "Search",
+            Action::EditSearch => "Edit",
+            Action::FocusLatestBlocks => "Blocks",
+            Action::FocusLatestTransactions => "Transactions",
+            Action::NextItem => "Down",
+            Action::PrevItem => "Up",
+            Action::NextPane => "Next pane",
+            Action::PrevPane => "Previous pane",
+            Action::NextPage => "Next page",
+            Action::PrevPage => "Previous page",
+            Action::Select => "Open",
+            Action::Refresh => "Refresh",
+            Action::ShowHelp => "Help",
+            Action::Cancel => "Cancel",
+        }
+    }
+
+    /// What the action does on `route_id` with `active_block` focused, or `None` if it does
+    /// nothing there.
+    pub fn describe(self, route_id: &RouteId, active_block: ActiveBlock) -> Option<&'static str> {
+        match (self, active_block, route_id) {
+            (Action::Quit, _, _) => Some("Quit"),
+            (Action::Back, _, _) => Some("Go back to the previous screen"),
+            (Action::ToggleSidebar, _, _) => Some("Toggle the sidebar"),
+            (Action::FocusSearch, ActiveBlock::SearchBar, _) => None,
+            (Action::FocusSearch, _, _) => Some("Focus on the search bar"),
+            (Action::EditSearch, ActiveBlock::SearchBar, _) => Some("Start typing a search"),
+            (Action::EditSearch, _, _) => None,
+            (Action::FocusLatestBlocks, _, _) => Some("Focus on the latest blocks"),
+            (Action::FocusLatestTransactions, _, _) => Some("Focus on the latest transactions"),
+            (Action::ShowHelp, _, _) => Some("Show this help"),
+            (Action::Cancel, ActiveBlock::SearchBar, _) => Some("Close this help"),
+            (Action::Cancel, _, RouteId::Searching(_)) => Some("Cancel the search"),
+            (Action::Cancel, _, _) => Some("Close this help"),
+            (_, ActiveBlock::SearchBar, _) => None,
+
+            (Action::NextItem, ActiveBlock::LatestBlocks, _) => Some("Select the next block"),
+            (Action::PrevItem, ActiveBlock::LatestBlocks, _) => Some("Select the previous block"),
+            (Action::Select, ActiveBlock::LatestBlocks, _) => Some("Open the selected block"),
+            (Action::Refresh, ActiveBlock::LatestBlocks, _) => {
+                Some("Reload the latest blocks and statistics")
+            }
+            (Action::NextItem, ActiveBlock::LatestTransactions, _) => {
+                Some("Select the next transaction")
+            }
+            (Action::PrevItem, ActiveBlock::LatestTransactions, _) => {
+                Some("Select the previous transaction")
+            }
+            (Action::Select, ActiveBlock::LatestTransactions, _) => {
+                Some("Open the selected transaction")
+            }
+            (Action::Refresh, ActiveBlock::LatestTransactions, _) => {
+                Some("Reload the latest transactions")
+            }
+            (_, ActiveBlock::LatestBlocks | ActiveBlock::LatestTransactions, _) => None,
+
+            (Action::NextItem, _, RouteId::Block(Some(_)) | RouteId::Transaction(Some(_))) => {
+                Some("Select the next field")
+            }
+            (Action::PrevItem, _, RouteId::Block(Some(_)) | RouteId::Transaction(Some(_))) => {
+                Some("Select the previous field")
+            }
+            (Action::Select, _, RouteId::Block(Some(_)) | RouteId::Transaction(Some(_))) => {
+                Some("Open the selected field")
+            }
+            (Action::NextItem, _, RouteId::TransactionsOfBlock(Some(_))) => {
+                Some("Select the next transaction")
+            }
+            (Action::PrevItem, _, RouteId::TransactionsOfBlock(Some(_))) => {
+                Some("Select the previous transaction")
+            }
+            (Action::Select, _, RouteId::TransactionsOfBlock(Some(_))) => {
+                Some("Open the selected transaction")
+            }
+            (Action::NextItem, _, RouteId::WithdrawalsOfBlock(Some(_))) => {
+                Some("Select the next withdrawal")
+            }
+            (Action::PrevItem, _, RouteId::WithdrawalsOfBlock(Some(_))) => {
+                Some("Select the previous withdrawal")
+            }
+            (
+                Action::NextItem,
+                _,
+                RouteId::InputDataOfTransaction(Some(_)) | RouteId::AddressInfo(Some(_)),
+            ) => Some("Scroll down the focused pane"),
+            (
+                Action::PrevItem,
+                _,
+                RouteId::InputDataOfTransaction(Some(_)) | RouteId::AddressInfo(Some(_)),
+            ) => Some("Scroll up the focused pane"),
+            (Action::NextPane, _, RouteId::AddressInfo(Some(_))) => Some("Focus on the next pane"),
+            (Action::PrevPane, _, RouteId::AddressInfo(Some(_))) => {
+                Some("Focus on the previous pane")
+            }
+            (Action::Select, _, RouteId::AddressInfo(Some(_))) => {
+                Some("Open the selected transaction or token, or enter a storage slot")
+            }
+            (Action::NextPage, _, RouteId::AddressInfo(Some(_))) => Some("Load older transactions"),
+            (Action::PrevPage, _, RouteId::AddressInfo(Some(_))) => Some("Load newer transactions"),
+            (
+                Action::NextPane,
+                _,
+                RouteId::Transaction(Some(_)) | RouteId::InputDataOfTransaction(Some(_)),
+            ) => Some("Switch to the next tab of the input data"),
+            (
+                Action::PrevPane,
+                _,
+                RouteId::Transaction(Some(_)) | RouteId::InputDataOfTransaction(Some(_)),
+            ) => Some("Switch to the previous tab of the input data"),
+            (Action::Refresh, _, RouteId::Failed(_)) => Some("Retry the failed request"),
+            (Action::Refresh, _, RouteId::AddressInfo(Some(_))) => {
+                Some("Reload the transactions, token balances or storage slots")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A key with its modifiers, written like `ctrl+e`, `enter` or `?` in the config file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    const fn key(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    const fn ctrl(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    /// Terminals report `?` or `G` with or without Shift, so it's ignored for characters.
+    fn normalize(self) -> Self {
+        match self.code {
+            KeyCode::Char(_) => Self::new(self.code, self.modifiers - KeyModifiers::SHIFT),
+            _ => self,
+        }
+    }
+}
+
+impl From<&KeyEvent> for KeyBinding {
+    fn from(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers).normalize()
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // An empty key after the last `+` means the key is `+` itself, as in `ctrl++`.
+        let (modifiers, key) = match s.rsplit_once('+') {
+            Some((modifiers, "")) => (modifiers.strip_suffix('+').unwrap_or(modifiers), "+"),
+            Some((modifiers, key)) => (modifiers, key),
+            None => ("", s),
+        };
+
+        let mut binding = KeyBinding::new(KeyCode::Null, KeyModifiers::NONE);
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            binding.modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("Unknown modifier `{modifier}` in `{s}`"),
+            };
+        }
+
+        let mut chars = key.chars();
+        binding.code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(n) => KeyCode::F(n),
+                    None => bail!("Unknown key `{key}` in `{s}`"),
+                },
+            },
+        };
+        Ok(binding.normalize())
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "F{n}"),
+            KeyCode::BackTab => write!(f, "BackTab"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// Which action each key triggers. Actions set in the `[keys]` section of the config file lose
+/// their default keys, and take over keys that were bound to other actions.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "HashMap<Action, Vec<KeyBinding>>")]
+pub struct Keymap {
+    bindings: Vec<(KeyBinding, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: vec![
+                (KeyBinding::key('q'), Action::Quit),
+                (KeyBinding::ctrl('p'), Action::Back),
+                (KeyBinding::ctrl('e'), Action::ToggleSidebar),
+                (KeyBinding::key('s'), Action::FocusSearch),
+                (KeyBinding::key('i'), Action::EditSearch),
+                (KeyBinding::key('1'), Action::FocusLatestBlocks),
+                (KeyBinding::key('2'), Action::FocusLatestTransactions),
+                (KeyBinding::key('j'), Action::NextItem),
+                (
+                    KeyBinding::new(KeyCode::Down, KeyModifiers::NONE),
+                    Action::NextItem,
+                ),
+                (KeyBinding::key('k'), Action::PrevItem),
+                (
+                    KeyBinding::new(KeyCode::Up, KeyModifiers::NONE),
+                    Action::PrevItem,
+                ),
+                (
+                    KeyBinding::new(KeyCode::Right, KeyModifiers::NONE),
+                    Action::NextPane,
+                ),
+                (
+                    KeyBinding::new(KeyCode::Left, KeyModifiers::NONE),
+                    Action::PrevPane,
+                ),
+                (
+                    KeyBinding::new(KeyCode::Enter, KeyModifiers::NONE),
+                    Action::Select,
+                ),
+                (KeyBinding::key(']'), Action::NextPage),
+                (KeyBinding::key('['), Action::PrevPage),
+                (KeyBinding::key('r'), Action::Refresh),
+                (KeyBinding::key('?'), Action::ShowHelp),
+                (
+                    KeyBinding::new(KeyCode::Esc, KeyModifiers::NONE),
+                    Action::Cancel,
+                ),
+            ],
+        }
+    }
+}
+
+impl TryFrom<HashMap<Action, Vec<KeyBinding>>> for Keymap {
+    type Error = anyhow::Error;
+
+    fn try_from(overrides: HashMap<Action, Vec<KeyBinding>>) -> Result<Self> {
+        let mut overrides = overrides.into_iter().collect::<Vec<_>>();
+        overrides.sort_by_key(|(action, _)| *action);
+
+        let mut keymap = Self::default();
+        let mut overridden: Vec<(KeyBinding, Action)> = vec![];
+        for (action, keys) in overrides {
+            keymap.bindings.retain(|(_, bound)| *bound != action);
+            for key in keys {
+                if let Some((_, other)) = overridden.iter().find(|(bound, _)| *bound == key) {
+                    bail!("`{key}` is bound to both {other:?} and {action:?}");
+                }
+                overridden.push((key, action));
+            }
+        }
+
+        keymap
+            .bindings
+            .retain(|(key, _)| overridden.iter().all(|(bound, _)| bound != key));
+        keymap.bindings.extend(overridden);
+        Ok(keymap)
+    }
+}
+
+impl Keymap {
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        let key = KeyBinding::from(key);
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == key)
+            .map(|(_, action)| *action)
+    }
+    /// The keys bound to `action`, in the order they were bound.
+    pub fn keys(&self, action: Action) -> impl Iterator<Item = KeyBinding> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, bound)| *bound == action)
+            .map(|(key, _)| *key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> KeyBinding {
+        s.parse().unwrap()
+    }
+
+    fn action(keymap: &Keymap, code: KeyCode, modifiers: KeyModifiers) -> Option<Action> {
+        keymap.action(&KeyEvent::new(code, modifiers))
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(parse("q"), KeyBinding::key('q'));
+        assert_eq!(parse("ctrl+e"), KeyBinding::ctrl('e'));
+        assert_eq!(parse("Control+E"), KeyBinding::ctrl('E'));
+        assert_eq!(parse("ctrl++"), KeyBinding::ctrl('+'));
+        assert_eq!(parse("+"), KeyBinding::key('+'));
+        assert_eq!(parse("space"), KeyBinding::key(' '));
+        assert_eq!(
+            parse("alt+Enter"),
+            KeyBinding::new(KeyCode::Enter, KeyModifiers::ALT)
+        );
+        assert_eq!(
+            parse("f5"),
+            KeyBinding::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse("shift+tab"),
+            KeyBinding::new(KeyCode::Tab, KeyModifiers::SHIFT)
+        );
+        // Shift is part of the character.
+        assert_eq!(parse("shift+?"), KeyBinding::key('?'));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert!("hyper+x".parse::<KeyBinding>().is_err());
+        assert!("ctrl+enterr".parse::<KeyBinding>().is_err());
+        assert!("".parse::<KeyBinding>().is_err());
+    }
+
+    #[test]
+    fn shows_keys_as_written() {
+        assert_eq!(parse("ctrl+e").to_string(), "Ctrl+e");
+        assert_eq!(parse("space").to_string(), "Space");
+        assert_eq!(parse("f12").to_string(), "F12");
+        assert_eq!(parse("esc").to_string(), "Esc");
+    }
+
+    #[test]
+    fn ignores_shift_on_characters() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            action(&keymap, KeyCode::Char('?'), KeyModifiers::SHIFT),
+            Some(Action::ShowHelp)
+        );
+    }
+
+    #[test]
+    fn overrides_replace_default_keys() {
+        let keymap: Keymap = toml::from_str(
+            r#"
+            quit = ["ctrl+c"]
+            refresh = ["q", "f5"]
+            "#,
+        )
+        .unwrap();
+
+        let none = KeyModifiers::NONE;
+        assert_eq!(
+            action(&keymap, KeyCode::Char('c'), KeyModifiers::CONTROL),
+            Some(Action::Quit)
+        );
+        // `q` is taken over by Refresh, which loses `r`.
+        assert_eq!(
+            action(&keymap, KeyCode::Char('q'), none),
+            Some(Action::Refresh)
+        );
+        assert_eq!(action(&keymap, KeyCode::Char('r'), none), None);
+        assert_eq!(
+            keymap.keys(Action::Refresh).collect::<Vec<_>>(),
+            [parse("q"), parse("f5")]
+        );
+        // Other actions keep their default keys.
+        assert_eq!(
+            action(&keymap, KeyCode::Char('j'), none),
+            Some(Action::NextItem)
+        );
+        assert_eq!(action(&keymap, KeyCode::Down, none), Some(Action::NextItem));
+    }
+
+    #[test]
+    fn rejects_a_key_bound_to_two_actions() {
+        let res = toml::from_str::<Keymap>(
+            r#"
+            quit = ["x"]
+            refresh = ["x"]
+            "#,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_actions() {
+        assert!(toml::from_str::<Keymap>(r#"explode = ["x"]"#).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use crate::network::IoEvent;
+use chrono::{DateTime, Utc};
+
+/// A request that failed, kept so that it can be shown and retried.
+#[derive(Clone)]
+pub struct Notification {
+    pub io_event: IoEvent,
+    pub endpoint: String,
+    pub message: String,
+    pub time: DateTime<Utc>,
+}
+
+impl Notification {
+    /// How long a notification stays in the navigation bar.
+    pub const DISPLAY_SECS: i64 = 10;
+    pub const CAPACITY: usize = 32;
+
+    pub fn new(io_event: IoEvent, endpoint: &str, message: String) -> Self {
+        Self {
+            io_event,
+            endpoint: endpoint.to_owned(),
+            message,
+            time: Utc::now(),
+        }
+    }
+
+    pub fn is_recent(&self) -> bool {
+        (Utc::now() - self.time).num_seconds() < Self::DISPLAY_SECS
+    }
+}
+
+impl std::fmt::Display for Notification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to load {} from {}: {}",
+            self.io_event, self.endpoint, self.message
+        )
+    }
+}
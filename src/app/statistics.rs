@@ -22,6 +22,18 @@ impl Statistics {
         }
     }
 
+    /// Whether a statistic read from the node is still missing. Those from the explorer are
+    /// only waited for if `from_explorer` is set, as they never come without one.
+    pub fn is_incomplete(&self, from_explorer: bool) -> bool {
+        self.last_safe_block.is_none()
+            || self.last_finalized_block.is_none()
+            || (from_explorer
+                && (self.ethusd.is_none()
+                    || self.node_count.is_none()
+                    || self.suggested_base_fee.is_none()
+                    || self.med_gas_price.is_none()))
+    }
+
     pub const ETHUSD_INDEX: usize = 0;
     pub const SUGGESTED_BASE_FEE_INDEX: usize = 1;
     pub const LAST_SAFE_BLOCK_INDEX: usize = 2;
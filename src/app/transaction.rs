@@ -63,20 +63,23 @@ impl From<SelectableTransactionDetailItem> for usize {
 pub enum SelectableInputDataDetailItem {
     InputData,        //0
     DecodedInputData, //1
+    Logs,             //2
 }
 
 impl SelectableInputDataDetailItem {
     pub fn next(&self) -> Self {
         match self {
             Self::InputData => Self::DecodedInputData,
-            Self::DecodedInputData => Self::InputData,
+            Self::DecodedInputData => Self::Logs,
+            Self::Logs => Self::InputData,
         }
     }
 
     pub fn previous(&self) -> Self {
         match self {
-            Self::InputData => Self::DecodedInputData,
+            Self::InputData => Self::Logs,
             Self::DecodedInputData => Self::InputData,
+            Self::Logs => Self::DecodedInputData,
         }
     }
 }
@@ -87,6 +90,8 @@ impl From<usize> for SelectableInputDataDetailItem {
             Self::InputData
         } else if i == 1 {
             Self::DecodedInputData
+        } else if i == 2 {
+            Self::Logs
         } else {
             unreachable!()
         }
@@ -98,6 +103,7 @@ impl From<SelectableInputDataDetailItem> for usize {
         match val {
             SelectableInputDataDetailItem::InputData => 0,
             SelectableInputDataDetailItem::DecodedInputData => 1,
+            SelectableInputDataDetailItem::Logs => 2,
         }
     }
 }
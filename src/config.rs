@@ -0,0 +1,288 @@
+use crate::{
+    app::keymap::Keymap,
+    ethers::types::ERC20Token,
+    network::{
+        chain::ExplorerConfig,
+        scheduler::{is_positive, SchedulerConfig},
+    },
+    ui::theme::Theme,
+};
+use anyhow::{bail, Context, Result};
+use ethers::core::types::Address;
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::PathBuf, time::Duration};
+
+/// Settings read from `lazy-etherscan/config.toml` in the XDG config directory (e.g.
+/// `~/.config/lazy-etherscan/config.toml`). Command-line flags override them.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub endpoints: Vec<String>,
+    pub cache_dir: Option<PathBuf>,
+    pub log_dir: PathBuf,
+    pub signatures: Vec<PathBuf>,
+    /// Used on every chain unless `chains` says otherwise.
+    pub explorer: ExplorerConfig,
+    /// Settings for a chain, keyed by chain ID.
+    pub chains: HashMap<String, ChainConfig>,
+    pub scheduler: SchedulerConfig,
+    pub refresh: RefreshConfig,
+    pub layout: LayoutConfig,
+    /// `dark`, `light`, `monochrome` or the name of one of `themes`.
+    pub theme: String,
+    pub themes: HashMap<String, Theme>,
+    /// Keys for each action, replacing its default keys.
+    pub keys: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            endpoints: vec!["https://eth.llamarpc.com".to_owned()],
+            cache_dir: None,
+            log_dir: PathBuf::from("logs"),
+            signatures: vec![],
+            explorer: ExplorerConfig::default(),
+            chains: HashMap::new(),
+            scheduler: SchedulerConfig::default(),
+            refresh: RefreshConfig::default(),
+            layout: LayoutConfig::default(),
+            theme: "dark".to_owned(),
+            themes: HashMap::new(),
+            keys: Keymap::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `path`, or the default config file if there is one.
+    pub fn load(path: Option<PathBuf>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None => match Self::default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+
+        let buffer = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: Self = toml::from_str(&buffer)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        let (numerator, denominator) = config.layout.sidebar_ratio;
+        if numerator == 0 || numerator >= denominator {
+            bail!("layout.sidebar_ratio must be a fraction between 0 and 1, e.g. [1, 3]");
+        }
+        if !is_positive(config.scheduler.requests_per_second) {
+            bail!("scheduler.requests_per_second must be a positive number");
+        }
+        // `tokio::time::interval` panics on a zero period.
+        if config.refresh.ui_poll_interval_ms == 0 {
+            bail!("refresh.ui_poll_interval_ms must be positive");
+        }
+        if config.refresh.new_heads_poll_interval_secs == 0 {
+            bail!("refresh.new_heads_poll_interval_secs must be positive");
+        }
+        if Theme::builtin(&config.theme).is_none() && !config.themes.contains_key(&config.theme) {
+            bail!("Unknown theme `{}`", config.theme);
+        }
+        Ok(config)
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("lazy-etherscan").join("config.toml"))
+    }
+
+    /// The selected theme, or the monochrome one if `NO_COLOR` is set.
+    pub fn theme(&self) -> Theme {
+        if Theme::is_no_color() {
+            return Theme::MONOCHROME;
+        }
+        self.themes
+            .get(&self.theme)
+            .copied()
+            .or_else(|| Theme::builtin(&self.theme))
+            .unwrap_or_default()
+    }
+
+    pub fn chain(&self, chain_id: u64) -> ChainConfig {
+        let chain = self
+            .chains
+            .get(&chain_id.to_string())
+            .cloned()
+            .unwrap_or_default();
+        ChainConfig {
+            explorer: ExplorerConfig {
+                api_url: chain
+                    .explorer
+                    .api_url
+                    .or_else(|| self.explorer.api_url.to_owned()),
+                api_key: chain
+                    .explorer
+                    .api_key
+                    .or_else(|| self.explorer.api_key.to_owned()),
+            },
+            ..chain
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChainConfig {
+    pub explorer: ExplorerConfig,
+    /// Defaults to `data/tokens.json` on mainnet and `data/tokens/<chain ID>.json` elsewhere.
+    pub token_list: Option<PathBuf>,
+    /// Tokens to add to the token list, e.g. ones a treasury holds that the list is missing.
+    pub tokens: Vec<ERC20Token>,
+    /// solc storage layouts to name the slots of contracts with, keyed by contract address.
+    pub storage_layouts: HashMap<Address, PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RefreshConfig {
+    /// How often animations such as spinners are redrawn. Other changes are drawn right away.
+    pub ui_poll_interval_ms: u64,
+    /// How often the latest block number is polled when the endpoint can't push new heads.
+    pub new_heads_poll_interval_secs: u64,
+}
+
+impl Default for RefreshConfig {
+    fn default() -> Self {
+        Self {
+            ui_poll_interval_ms: 250,
+            new_heads_poll_interval_secs: 4,
+        }
+    }
+}
+
+impl RefreshConfig {
+    pub fn ui_poll_interval(&self) -> Duration {
+        Duration::from_millis(self.ui_poll_interval_ms)
+    }
+
+    pub fn new_heads_poll_interval(&self) -> Duration {
+        Duration::from_secs(self.new_heads_poll_interval_secs)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LayoutConfig {
+    /// Whether the sidebar starts hidden, as with `<Ctrl+e>`.
+    pub sidebar_toggled: bool,
+    /// Width of the sidebar as a fraction of the screen, e.g. `[1, 3]`.
+    pub sidebar_ratio: (u32, u32),
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            sidebar_toggled: false,
+            sidebar_ratio: (1, 3),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Loads `toml` from a file of its own.
+    fn load(name: &str, toml: &str) -> Result<Config> {
+        let path =
+            std::env::temp_dir().join(format!("lazy-etherscan-{}-{name}.toml", std::process::id()));
+        fs::write(&path, toml).unwrap();
+        let config = Config::load(Some(path.to_owned()));
+        fs::remove_file(path).unwrap();
+        config
+    }
+
+    #[test]
+    fn file_overrides_defaults() {
+        let config = load(
+            "overrides",
+            r#"
+            endpoints = ["http://localhost:8545"]
+            theme = "light"
+
+            [scheduler]
+            requests_per_second = 5
+
+            [keys]
+            quit = ["ctrl+c"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.endpoints, ["http://localhost:8545"]);
+        assert_eq!(config.theme, "light");
+        assert_eq!(config.scheduler.requests_per_second, 5.0);
+        // Settings left out keep their defaults, even in a section that is there.
+        assert_eq!(config.scheduler.max_retries, 5);
+        assert_eq!(config.log_dir, PathBuf::from("logs"));
+        assert_eq!(config.layout.sidebar_ratio, (1, 3));
+    }
+
+    #[test]
+    fn chain_settings_override_global_ones() {
+        let config = load(
+            "chains",
+            r#"
+            [explorer]
+            api_url = "https://explorer.example"
+            api_key = "global"
+
+            [chains.5.explorer]
+            api_key = "goerli"
+            "#,
+        )
+        .unwrap();
+
+        let goerli = config.chain(5);
+        assert_eq!(goerli.explorer.api_key.as_deref(), Some("goerli"));
+        assert_eq!(
+            goerli.explorer.api_url.as_deref(),
+            Some("https://explorer.example")
+        );
+        assert_eq!(config.chain(1).explorer.api_key.as_deref(), Some("global"));
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        for (name, toml) in [
+            ("unknown-key", "endpoint = \"http://localhost:8545\""),
+            ("zero-rate", "[scheduler]\nrequests_per_second = 0"),
+            ("sidebar-ratio", "[layout]\nsidebar_ratio = [3, 3]"),
+            ("ui-poll", "[refresh]\nui_poll_interval_ms = 0"),
+            (
+                "new-heads-poll",
+                "[refresh]\nnew_heads_poll_interval_secs = 0",
+            ),
+            ("theme", "theme = \"solarized\""),
+            ("keys", "[keys]\nquit = [\"x\"]\nrefresh = [\"x\"]"),
+        ] {
+            assert!(load(name, toml).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn accepts_custom_themes() {
+        let config = load(
+            "custom-theme",
+            r#"
+            theme = "mine"
+
+            [themes.mine]
+            "#,
+        );
+        assert!(config.is_ok());
+    }
+
+    #[test]
+    fn fails_on_a_missing_file() {
+        assert!(Config::load(Some(PathBuf::from("/nonexistent/config.toml"))).is_err());
+    }
+}
@@ -1,13 +1,12 @@
 pub mod types {
+    use super::abi::ContractAbi;
     use ethers::{
-        core::{
-            abi::Abi,
-            types::{Address, Block, Transaction, TransactionReceipt, U256},
-        },
+        core::types::{Address, Block, Bytes, Transaction, TransactionReceipt, H256, U256, U64},
         etherscan::contract::ContractMetadata,
+        utils::format_units,
     };
-    use serde::{Deserialize, Deserializer};
-    use std::cmp::PartialEq;
+    use serde::{Deserialize, Deserializer, Serialize};
+    use std::{cmp::PartialEq, fmt};
     use url::Url;
 
     #[derive(Clone, Debug)]
@@ -15,19 +14,166 @@ pub mod types {
         pub address: Address,
         pub ens_id: Option<String>,
         pub avatar_url: Option<Url>,
-        pub contract_abi: Option<Abi>,
+        pub contract_abi: Option<ContractAbi>,
         pub contract_source_code: Option<ContractMetadata>,
         pub balance: U256,
+        /// The deployed bytecode, empty for externally owned accounts.
+        pub code: Bytes,
+        /// Set for proxies, whose `contract_abi` and `contract_source_code` are then those of
+        /// the implementation if it's verified.
+        pub proxy: Option<Proxy>,
+        /// `None` until the first page has been loaded.
+        pub transactions: Option<AddressTransactions>,
+        /// Tokens the address holds, by the number of tokens held, most first. `None` until
+        /// loaded.
+        pub token_balances: Option<Vec<TokenBalance>>,
+        /// Slots read with the storage inspector, in the order they were entered.
+        pub storage_slots: Vec<StorageSlot>,
+    }
+
+    /// A contract that delegates its calls to an implementation contract.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Proxy {
+        pub kind: ProxyKind,
+        pub implementation: Address,
+        /// The account allowed to upgrade a transparent proxy.
+        pub admin: Option<Address>,
+        /// The beacon a beacon proxy asks for its implementation.
+        pub beacon: Option<Address>,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum ProxyKind {
+        /// The implementation is in the EIP-1967 slot, as in UUPS proxies.
+        Eip1967,
+        /// An OpenZeppelin transparent proxy, with an admin in the EIP-1967 slot or in the slot
+        /// of the older `org.zeppelinos.proxy.admin`.
+        Transparent,
+        /// The implementation is asked from a beacon, whose address is in the EIP-1967 slot.
+        Beacon,
+        /// The implementation is in the `PROXIABLE` slot of EIP-1822.
+        Eip1822,
+        /// An EIP-1167 minimal proxy, whose implementation is part of its bytecode.
+        Eip1167,
+    }
+
+    impl fmt::Display for ProxyKind {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ProxyKind::Eip1967 => write!(f, "EIP-1967"),
+                ProxyKind::Transparent => write!(f, "Transparent"),
+                ProxyKind::Beacon => write!(f, "Beacon"),
+                ProxyKind::Eip1822 => write!(f, "EIP-1822"),
+                ProxyKind::Eip1167 => write!(f, "EIP-1167 minimal"),
+            }
+        }
+    }
+
+    /// A storage slot of a contract, as entered in the storage inspector.
+    #[derive(Clone, Debug)]
+    pub struct StorageSlot {
+        /// What was entered, e.g. `_balances[0x...]`.
+        pub query: String,
+        pub slot: H256,
+        /// `None` until loaded.
+        pub value: Option<H256>,
+    }
+
+    /// How much of an ERC-20 token an address holds.
+    #[derive(Clone, Debug)]
+    pub struct TokenBalance {
+        pub token: ERC20Token,
+        pub balance: U256,
+        /// `None` for tokens without `decimals()`, whose balance is shown unscaled.
+        pub decimals: Option<u8>,
+    }
+
+    impl AddressInfo {
+        pub fn is_contract(&self) -> bool {
+            !self.code.is_empty()
+        }
+    }
+
+    impl TokenBalance {
+        /// The balance in whole tokens, e.g. `1.5` for 1.5 USDT.
+        pub fn amount(&self) -> String {
+            format_units(self.balance, u32::from(self.decimals.unwrap_or_default()))
+                .unwrap_or_else(|_| self.balance.to_string())
+        }
+    }
+
+    /// A page of the normal transactions sent from or to an address, newest first.
+    #[derive(Clone, Debug)]
+    pub struct AddressTransactions {
+        pub address: Address,
+        /// Counted from 0.
+        pub page: usize,
+        /// The newest block pages are counted from, so that they don't shift as blocks arrive.
+        pub until: U64,
+        pub source: TransactionHistorySource,
+        pub transactions: Vec<TransactionWithReceipt>,
+    }
+
+    /// Where the transactions of an address were listed from.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum TransactionHistorySource {
+        /// The explorer's `txlist`, a full page of which holds `page_size` transactions.
+        Explorer { page_size: usize },
+        /// Blocks `from..=to`, scanned over JSON-RPC because there's no explorer.
+        BlockScan { from: U64, to: U64 },
+    }
+
+    impl AddressTransactions {
+        /// Whether there are older transactions to load.
+        pub fn has_next_page(&self) -> bool {
+            match self.source {
+                TransactionHistorySource::Explorer { page_size } => {
+                    self.transactions.len() >= page_size
+                }
+                TransactionHistorySource::BlockScan { from, .. } => !from.is_zero(),
+            }
+        }
     }
 
     #[derive(Clone, Debug, PartialEq)]
     pub struct TransactionWithReceipt {
         pub transaction: Transaction,
         pub transaction_receipt: TransactionReceipt,
-        pub decoded_input_data: Option<String>,
+        pub decoded_input_data: Option<DecodedCall>,
+        /// Decoded events, in the order of `transaction_receipt.logs`. Empty until decoded.
+        pub decoded_logs: Vec<Option<DecodedEvent>>,
+    }
+
+    /// A function call decoded with the ABI of the called contract.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct DecodedCall {
+        pub name: String,
+        pub signature: String,
+        pub params: Vec<DecodedParam>,
     }
 
+    /// A log decoded with the ABI of the contract that emitted it.
     #[derive(Clone, Debug, PartialEq)]
+    pub struct DecodedEvent {
+        pub name: String,
+        pub signature: String,
+        pub params: Vec<DecodedParam>,
+    }
+
+    /// A decoded parameter. Tuples and arrays have their elements as `children` and no `value`.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct DecodedParam {
+        pub name: String,
+        pub kind: String,
+        pub value: Option<String>,
+        pub children: Vec<DecodedParam>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(bound(
+        serialize = "Block<T>: Serialize",
+        deserialize = "Block<T>: Deserialize<'de>"
+    ))]
     pub struct BlockWithTransactionReceipts<T> {
         pub block: Block<T>,
         pub transaction_receipts: Option<Vec<TransactionReceipt>>,
@@ -100,3 +246,1530 @@ pub mod transaction {
         }
     }
 } /* transaction */
+
+pub mod abi {
+    use super::types::{DecodedCall, DecodedEvent, DecodedParam};
+    use ethers::core::{
+        abi::{Abi, Component, Event, Function, Item, ParamType, RawAbi, Token},
+        types::{Log, I256},
+    };
+    use serde::{Deserialize, Serialize};
+
+    /// A contract ABI, along with the names of the components of its tuple parameters, which
+    /// `Abi` leaves out.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(try_from = "RawAbi", into = "Vec<Item>")]
+    pub struct ContractAbi {
+        pub abi: Abi,
+        items: Vec<Item>,
+    }
+
+    impl TryFrom<RawAbi> for ContractAbi {
+        type Error = serde_json::Error;
+
+        fn try_from(raw_abi: RawAbi) -> Result<Self, Self::Error> {
+            let items = raw_abi.into_iter().collect::<Vec<_>>();
+            Ok(Self {
+                abi: serde_json::from_value(serde_json::to_value(&items)?)?,
+                items,
+            })
+        }
+    }
+
+    impl From<ContractAbi> for Vec<Item> {
+        fn from(contract_abi: ContractAbi) -> Self {
+            contract_abi.items
+        }
+    }
+
+    impl ContractAbi {
+        /// The parameters of the `function` or `event` called `name` with parameters of `kinds`,
+        /// as written in the ABI.
+        fn inputs(&self, item_type: &str, name: &str, kinds: &[&ParamType]) -> &[Component] {
+            self.items
+                .iter()
+                .find(|item| {
+                    item.type_field == item_type
+                        && item.name.as_deref() == Some(name)
+                        && item.inputs.len() == kinds.len()
+                        && item
+                            .inputs
+                            .iter()
+                            .zip(kinds)
+                            .all(|(input, kind)| canonical_type(input) == kind.to_string())
+                })
+                .map_or(&[], |item| &item.inputs)
+        }
+    }
+
+    /// The type of `component` as it appears in signatures, e.g. `(address,uint256)[]` for a
+    /// `tuple[]`.
+    fn canonical_type(component: &Component) -> String {
+        match component.type_field.strip_prefix("tuple") {
+            Some(suffix) => format!(
+                "({}){suffix}",
+                component
+                    .components
+                    .iter()
+                    .map(canonical_type)
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            None => component.type_field.to_owned(),
+        }
+    }
+
+    /// The components of the `i`th of `components`, if it's a tuple or an array of them.
+    fn components_of(components: &[Component], i: usize) -> &[Component] {
+        components
+            .get(i)
+            .map_or(&[], |component| &component.components)
+    }
+
+    /// Decodes `input` as a call to one of the functions in `abi`, picked by its selector.
+    pub fn decode_input(abi: &ContractAbi, input: &[u8]) -> Option<DecodedCall> {
+        let selector = input.get(..4)?;
+        let function = abi
+            .abi
+            .functions()
+            .find(|function| function.short_signature() == selector)?;
+        let tokens = function.decode_input(&input[4..]).ok()?;
+        let kinds = function
+            .inputs
+            .iter()
+            .map(|param| &param.kind)
+            .collect::<Vec<_>>();
+        let inputs = abi.inputs("function", &function.name, &kinds);
+        Some(decoded_call(function, tokens, inputs))
+    }
+
+    /// Decodes `input` as a call to `function` without tolerating leftover data, so that a
+    /// signature guessed from the selector alone is only accepted if it fits.
+    pub fn decode_input_strict(function: &Function, input: &[u8]) -> Option<DecodedCall> {
+        let kinds = function
+            .inputs
+            .iter()
+            .map(|param| param.kind.to_owned())
+            .collect::<Vec<_>>();
+        let tokens = ethers::core::abi::decode_whole(&kinds, input.get(4..)?).ok()?;
+        Some(decoded_call(function, tokens, &[]))
+    }
+
+    /// `inputs` are the parameters of `function` as written in the ABI, if it's known, for the
+    /// names of tuple components.
+    fn decoded_call(function: &Function, tokens: Vec<Token>, inputs: &[Component]) -> DecodedCall {
+        DecodedCall {
+            name: function.name.to_owned(),
+            signature: function.signature(),
+            params: function
+                .inputs
+                .iter()
+                .zip(tokens)
+                .enumerate()
+                .map(|(i, (param, token))| {
+                    let name = if param.name.is_empty() {
+                        format!("[{i}]")
+                    } else {
+                        param.name.to_owned()
+                    };
+                    decode_param(name, &param.kind, token, components_of(inputs, i))
+                })
+                .collect(),
+        }
+    }
+
+    /// Decodes `log` as one of the events in `abi`, picked by its first topic. Anonymous events
+    /// can't be told apart and are left undecoded.
+    pub fn decode_log(abi: &ContractAbi, log: &Log) -> Option<DecodedEvent> {
+        let topic0 = log.topics.first()?;
+        abi.abi
+            .events()
+            .filter(|event| !event.anonymous && event.signature() == *topic0)
+            .find_map(|event| {
+                let kinds = event
+                    .inputs
+                    .iter()
+                    .map(|input| &input.kind)
+                    .collect::<Vec<_>>();
+                let inputs = abi.inputs("event", &event.name, &kinds);
+                decode_event(event, log, false, inputs)
+            })
+    }
+
+    /// Decodes `log` as `event`. Indexed parameters are read from the topics and the others from
+    /// the data. Indexed strings, bytes, arrays and tuples only have their hash in the topic, so
+    /// the hash is shown instead.
+    ///
+    /// With `strict`, leftover data is rejected, so that an event guessed from the first topic
+    /// alone is only accepted if it fits. `inputs` are the parameters of `event` as written in
+    /// the ABI, if it's known, for the names of tuple components.
+    pub fn decode_event(
+        event: &Event,
+        log: &Log,
+        strict: bool,
+        inputs: &[Component],
+    ) -> Option<DecodedEvent> {
+        let mut topics = log.topics.iter();
+        if !event.anonymous && topics.next() != Some(&event.signature()) {
+            return None;
+        }
+        if event.inputs.iter().filter(|input| input.indexed).count() != topics.len() {
+            return None;
+        }
+
+        let data_kinds = event
+            .inputs
+            .iter()
+            .filter(|input| !input.indexed)
+            .map(|input| input.kind.to_owned())
+            .collect::<Vec<_>>();
+        let mut data_tokens = if strict {
+            ethers::core::abi::decode_whole(&data_kinds, &log.data)
+        } else {
+            ethers::core::abi::decode(&data_kinds, &log.data)
+        }
+        .ok()?
+        .into_iter();
+
+        let mut params = vec![];
+        for (i, input) in event.inputs.iter().enumerate() {
+            let name = if input.name.is_empty() {
+                format!("[{i}]")
+            } else {
+                input.name.to_owned()
+            };
+            if input.indexed {
+                let topic = topics.next()?;
+                let param = match input.kind {
+                    ParamType::String
+                    | ParamType::Bytes
+                    | ParamType::Array(_)
+                    | ParamType::FixedArray(_, _)
+                    | ParamType::Tuple(_) => DecodedParam {
+                        name,
+                        kind: input.kind.to_string(),
+                        value: Some(format!("{topic:#x} (hash)")),
+                        children: vec![],
+                    },
+                    _ => {
+                        let token =
+                            ethers::core::abi::decode(&[input.kind.to_owned()], topic.as_bytes())
+                                .ok()?
+                                .pop()?;
+                        decode_param(name, &input.kind, token, &[])
+                    }
+                };
+                params.push(DecodedParam {
+                    kind: format!("{} indexed", param.kind),
+                    ..param
+                });
+            } else {
+                params.push(decode_param(
+                    name,
+                    &input.kind,
+                    data_tokens.next()?,
+                    components_of(inputs, i),
+                ));
+            }
+        }
+
+        Some(DecodedEvent {
+            name: event.name.to_owned(),
+            signature: format!(
+                "{}({})",
+                event.name,
+                event
+                    .inputs
+                    .iter()
+                    .map(|input| input.kind.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            params,
+        })
+    }
+
+    /// Decodes `token` of type `kind`. `components` are those of `kind` in the ABI if it's a
+    /// tuple or an array of them, and name the fields of the tuple. Fields without a name are
+    /// numbered.
+    pub fn decode_param(
+        name: String,
+        kind: &ParamType,
+        token: Token,
+        components: &[Component],
+    ) -> DecodedParam {
+        let (value, children) = match (kind, token) {
+            (ParamType::Tuple(kinds), Token::Tuple(tokens)) => (
+                None,
+                kinds
+                    .iter()
+                    .zip(tokens)
+                    .enumerate()
+                    .map(|(i, (kind, token))| {
+                        let name = components
+                            .get(i)
+                            .map(|component| component.name.to_owned())
+                            .filter(|name| !name.is_empty())
+                            .unwrap_or(format!("[{i}]"));
+                        decode_param(name, kind, token, components_of(components, i))
+                    })
+                    .collect(),
+            ),
+            (
+                ParamType::Array(kind) | ParamType::FixedArray(kind, _),
+                Token::Array(tokens) | Token::FixedArray(tokens),
+            ) => (
+                None,
+                tokens
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, token)| decode_param(format!("[{i}]"), kind, token, components))
+                    .collect(),
+            ),
+            (_, token) => (Some(format_token(token)), vec![]),
+        };
+
+        DecodedParam {
+            name,
+            kind: kind.to_string(),
+            value,
+            children,
+        }
+    }
+
+    fn format_token(token: Token) -> String {
+        match token {
+            Token::Address(address) => format!("{address:#x}"),
+            Token::Bytes(bytes) | Token::FixedBytes(bytes) => {
+                format!("0x{}", ethers::core::utils::hex::encode(bytes))
+            }
+            Token::Int(int) => I256::from_raw(int).to_string(),
+            Token::Uint(uint) => uint.to_string(),
+            Token::Bool(bool) => bool.to_string(),
+            Token::String(string) => format!("{string:?}"),
+            token => token.to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    pub mod tests {
+        use super::*;
+        use ethers::core::{
+            abi::{encode, HumanReadableParser},
+            types::{Address, H256, U256},
+            utils::id,
+        };
+
+        /// `swap((address to, uint256[] amounts) order, bool)` and
+        /// `event Filled(address indexed maker, (address to, uint256 amount) fill)`.
+        pub const ABI: &str = r#"[
+            {
+                "type": "function",
+                "name": "swap",
+                "stateMutability": "nonpayable",
+                "inputs": [
+                    {
+                        "name": "order",
+                        "type": "tuple",
+                        "components": [
+                            { "name": "to", "type": "address" },
+                            { "name": "amounts", "type": "uint256[]" }
+                        ]
+                    },
+                    { "name": "", "type": "bool" }
+                ],
+                "outputs": []
+            },
+            {
+                "type": "event",
+                "name": "Filled",
+                "anonymous": false,
+                "inputs": [
+                    { "name": "maker", "type": "address", "indexed": true },
+                    {
+                        "name": "fill",
+                        "type": "tuple",
+                        "indexed": false,
+                        "components": [
+                            { "name": "to", "type": "address" },
+                            { "name": "amount", "type": "uint256" }
+                        ]
+                    }
+                ]
+            }
+        ]"#;
+
+        /// Calldata for `swap((to, [1, 2]), true)`.
+        pub fn swap_input(to: Address) -> Vec<u8> {
+            let order = Token::Tuple(vec![
+                Token::Address(to),
+                Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+            ]);
+            [
+                &id("swap((address,uint256[]),bool)")[..],
+                &encode(&[order, Token::Bool(true)]),
+            ]
+            .concat()
+        }
+
+        fn param(name: &str, kind: &str, value: &str) -> DecodedParam {
+            DecodedParam {
+                name: name.to_owned(),
+                kind: kind.to_owned(),
+                value: Some(value.to_owned()),
+                children: vec![],
+            }
+        }
+
+        #[test]
+        fn decodes_calls_with_named_tuple_components() {
+            let abi = serde_json::from_str::<ContractAbi>(ABI).unwrap();
+            let to = Address::from_low_u64_be(0xbeef);
+
+            let decoded = decode_input(&abi, &swap_input(to)).unwrap();
+            assert_eq!(decoded.name, "swap");
+            assert_eq!(decoded.signature, "swap((address,uint256[]),bool)");
+            assert_eq!(
+                decoded.params,
+                [
+                    DecodedParam {
+                        name: "order".to_owned(),
+                        kind: "(address,uint256[])".to_owned(),
+                        value: None,
+                        children: vec![
+                            param("to", "address", &format!("{to:#x}")),
+                            DecodedParam {
+                                name: "amounts".to_owned(),
+                                kind: "uint256[]".to_owned(),
+                                value: None,
+                                children: vec![
+                                    param("[0]", "uint256", "1"),
+                                    param("[1]", "uint256", "2"),
+                                ],
+                            },
+                        ],
+                    },
+                    param("[1]", "bool", "true"),
+                ]
+            );
+        }
+
+        #[test]
+        fn leaves_unknown_calls_undecoded() {
+            let abi = serde_json::from_str::<ContractAbi>(ABI).unwrap();
+            assert_eq!(decode_input(&abi, &id("transfer(address,uint256)")), None);
+            assert_eq!(decode_input(&abi, &[0x12, 0x34]), None);
+            // Calldata too short for the arguments.
+            assert_eq!(decode_input(&abi, &swap_input(Address::zero())[..36]), None);
+        }
+
+        /// A `Filled` log from `maker`, filling `amount` to `to`.
+        pub fn filled_log(maker: Address, to: Address, amount: u64) -> Log {
+            Log {
+                topics: vec![
+                    id_topic("Filled(address,(address,uint256))"),
+                    H256::from(maker),
+                ],
+                data: encode(&[Token::Tuple(vec![
+                    Token::Address(to),
+                    Token::Uint(amount.into()),
+                ])])
+                .into(),
+                ..Default::default()
+            }
+        }
+
+        fn id_topic(signature: &str) -> H256 {
+            H256::from(ethers::core::utils::keccak256(signature))
+        }
+
+        #[test]
+        fn decodes_logs_with_named_tuple_components() {
+            let abi = serde_json::from_str::<ContractAbi>(ABI).unwrap();
+            let (maker, to) = (Address::from_low_u64_be(1), Address::from_low_u64_be(2));
+
+            let decoded = decode_log(&abi, &filled_log(maker, to, 3)).unwrap();
+            assert_eq!(decoded.name, "Filled");
+            assert_eq!(decoded.signature, "Filled(address,(address,uint256))");
+            assert_eq!(
+                decoded.params,
+                [
+                    param("maker", "address indexed", &format!("{maker:#x}")),
+                    DecodedParam {
+                        name: "fill".to_owned(),
+                        kind: "(address,uint256)".to_owned(),
+                        value: None,
+                        children: vec![
+                            param("to", "address", &format!("{to:#x}")),
+                            param("amount", "uint256", "3"),
+                        ],
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn leaves_unknown_logs_undecoded() {
+            let abi = serde_json::from_str::<ContractAbi>(ABI).unwrap();
+            let mut log = filled_log(Address::zero(), Address::zero(), 0);
+            log.topics[0] = id_topic("Transfer(address,address,uint256)");
+            assert_eq!(decode_log(&abi, &log), None);
+
+            // A topic missing for the indexed parameter.
+            let mut log = filled_log(Address::zero(), Address::zero(), 0);
+            log.topics.pop();
+            assert_eq!(decode_log(&abi, &log), None);
+        }
+
+        #[test]
+        fn shows_hashes_of_indexed_dynamic_values() {
+            let event =
+                HumanReadableParser::parse_event("event Named(string indexed name)").unwrap();
+            let hash = id_topic("gm");
+            let log = Log {
+                topics: vec![event.signature(), hash],
+                ..Default::default()
+            };
+
+            let decoded = decode_event(&event, &log, true, &[]).unwrap();
+            assert_eq!(
+                decoded.params,
+                [param(
+                    "name",
+                    "string indexed",
+                    &format!("{hash:#x} (hash)")
+                )]
+            );
+        }
+
+        #[test]
+        fn formats_values() {
+            assert_eq!(format_token(Token::Int(U256::MAX)), "-1");
+            assert_eq!(format_token(Token::Bytes(vec![0xab, 0xcd])), "0xabcd");
+            assert_eq!(format_token(Token::String("gm".to_owned())), "\"gm\"");
+        }
+
+        #[test]
+        fn keeps_tuple_names_when_serialized() {
+            let abi = serde_json::from_str::<ContractAbi>(ABI).unwrap();
+            let abi =
+                serde_json::from_str::<ContractAbi>(&serde_json::to_string(&abi).unwrap()).unwrap();
+            let decoded = decode_input(&abi, &swap_input(Address::zero())).unwrap();
+            assert_eq!(decoded.params[0].children[1].name, "amounts");
+        }
+    }
+} /* abi */
+
+pub mod signatures {
+    use super::{
+        abi,
+        types::{DecodedCall, DecodedEvent},
+    };
+    use ethers::core::{
+        abi::{Event, Function, HumanReadableParser},
+        types::{Log, H256},
+    };
+    use log::warn;
+    use std::{collections::HashMap, fs, path::Path};
+
+    /// Function and event signatures, for contracts without a known ABI.
+    ///
+    /// Signature files are JSON arrays of human-readable signatures such as
+    /// `"transfer(address,uint256)"` or
+    /// `"event Transfer(address indexed from, address indexed to, uint256 value)"`. Selectors and
+    /// topics are computed from the signatures, so each can have several candidates.
+    #[derive(Default)]
+    pub struct Signatures {
+        functions: HashMap<[u8; 4], Vec<Function>>,
+        events: HashMap<H256, Vec<Event>>,
+    }
+
+    impl Signatures {
+        /// Loads every file in `paths`; files that can't be read are skipped.
+        pub fn load<P: AsRef<Path>>(paths: &[P]) -> Self {
+            let mut signatures = Self::default();
+            for path in paths {
+                let path = path.as_ref();
+                let res = fs::read_to_string(path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|buffer| Ok(serde_json::from_str::<Vec<String>>(&buffer)?));
+                match res {
+                    Ok(lines) => {
+                        for line in lines {
+                            signatures.insert(&line);
+                        }
+                    }
+                    Err(e) => warn!("Failed to load {}: {e}", path.display()),
+                }
+            }
+            signatures
+        }
+
+        fn insert(&mut self, signature: &str) {
+            if signature.trim_start().starts_with("event ") {
+                let Ok(event) = HumanReadableParser::parse_event(signature) else {
+                    warn!("Invalid event signature: {signature}");
+                    return;
+                };
+                // Names and `indexed` don't change the topic but do change how it's decoded.
+                let candidates = self.events.entry(event.signature()).or_default();
+                if !candidates.contains(&event) {
+                    candidates.push(event);
+                }
+            } else {
+                let Ok(function) = HumanReadableParser::parse_function(signature) else {
+                    warn!("Invalid function signature: {signature}");
+                    return;
+                };
+                let candidates = self
+                    .functions
+                    .entry(function.short_signature())
+                    .or_default();
+                if !candidates
+                    .iter()
+                    .any(|candidate| candidate.signature() == function.signature())
+                {
+                    candidates.push(function);
+                }
+            }
+        }
+
+        pub fn find_functions(&self, selector: &[u8]) -> &[Function] {
+            selector
+                .try_into()
+                .ok()
+                .and_then(|selector: [u8; 4]| self.functions.get(&selector))
+                .map_or(&[], |functions| functions.as_slice())
+        }
+
+        /// Decodes `input` with the first candidate signature of its selector that fits it.
+        pub fn decode_input(&self, input: &[u8]) -> Option<DecodedCall> {
+            self.find_functions(input.get(..4)?)
+                .iter()
+                .find_map(|function| abi::decode_input_strict(function, input))
+        }
+
+        /// Decodes `log` with the first candidate signature of its first topic that fits it,
+        /// e.g. telling an ERC-20 `Transfer` from an ERC-721 one by the number of topics.
+        pub fn decode_log(&self, log: &Log) -> Option<DecodedEvent> {
+            self.events
+                .get(log.topics.first()?)?
+                .iter()
+                .find_map(|event| abi::decode_event(event, log, true, &[]))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ethers::core::{
+            abi::{encode, Token},
+            types::Address,
+            utils::{id, keccak256},
+        };
+
+        fn transfer_input(to: Address, amount: u64) -> Vec<u8> {
+            [
+                &id("transfer(address,uint256)")[..],
+                &encode(&[Token::Address(to), Token::Uint(amount.into())]),
+            ]
+            .concat()
+        }
+
+        #[test]
+        fn decodes_calls_with_the_bundled_signatures() {
+            let signatures = Signatures::load(&["./data/signatures.json"]);
+            let to = Address::from_low_u64_be(1);
+
+            let decoded = signatures.decode_input(&transfer_input(to, 5)).unwrap();
+            assert_eq!(decoded.signature, "transfer(address,uint256)");
+            let values = decoded
+                .params
+                .iter()
+                .map(|param| (param.name.as_str(), param.value.to_owned().unwrap()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                values,
+                [("[0]", format!("{to:#x}")), ("[1]", "5".to_owned())]
+            );
+        }
+
+        #[test]
+        fn rejects_calls_that_do_not_fit_the_signature() {
+            let signatures = Signatures::load(&["./data/signatures.json"]);
+            let mut input = transfer_input(Address::zero(), 5);
+            input.extend_from_slice(&[0; 32]);
+            assert_eq!(signatures.decode_input(&input), None);
+            assert_eq!(signatures.decode_input(&input[..20]), None);
+        }
+
+        fn transfer_log(topics: &[H256], data: Vec<Token>) -> Log {
+            Log {
+                topics: [
+                    &[H256::from(keccak256("Transfer(address,address,uint256)"))],
+                    topics,
+                ]
+                .concat(),
+                data: encode(&data).into(),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn tells_erc20_transfers_from_erc721_ones() {
+            let signatures = Signatures::load(&["./data/events.json"]);
+            let (from, to) = (Address::from_low_u64_be(1), Address::from_low_u64_be(2));
+
+            let erc20 = transfer_log(&[from.into(), to.into()], vec![Token::Uint(7.into())]);
+            let decoded = signatures.decode_log(&erc20).unwrap();
+            let params = decoded
+                .params
+                .iter()
+                .map(|param| (param.name.as_str(), param.kind.as_str()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                params,
+                [
+                    ("from", "address indexed"),
+                    ("to", "address indexed"),
+                    ("value", "uint256")
+                ]
+            );
+            assert_eq!(decoded.params[2].value.as_deref(), Some("7"));
+
+            let erc721 = transfer_log(&[from.into(), to.into(), H256::from_low_u64_be(7)], vec![]);
+            let decoded = signatures.decode_log(&erc721).unwrap();
+            assert_eq!(decoded.params[2].name, "tokenId");
+            assert_eq!(decoded.params[2].kind, "uint256 indexed");
+            assert_eq!(decoded.params[2].value.as_deref(), Some("7"));
+        }
+
+        #[test]
+        fn rejects_logs_that_fit_no_signature() {
+            let signatures = Signatures::load(&["./data/events.json"]);
+            // Too few topics for either Transfer.
+            let log = transfer_log(&[H256::zero()], vec![Token::Uint(7.into())]);
+            assert_eq!(signatures.decode_log(&log), None);
+            // Leftover data.
+            let log = transfer_log(
+                &[H256::zero(), H256::zero()],
+                vec![Token::Uint(7.into()), Token::Uint(8.into())],
+            );
+            assert_eq!(signatures.decode_log(&log), None);
+            assert_eq!(signatures.decode_log(&Log::default()), None);
+        }
+
+        #[test]
+        fn skips_invalid_signatures_and_missing_files() {
+            let path = std::env::temp_dir().join(format!(
+                "lazy-etherscan-{}-signatures.json",
+                std::process::id()
+            ));
+            fs::write(
+                &path,
+                r#"["transfer(address,uint256)", "transfer(address to, uint256 amount)", "nope("]"#,
+            )
+            .unwrap();
+
+            let signatures = Signatures::load(&[path.to_owned(), path.with_extension("missing")]);
+            let _ = fs::remove_file(path);
+            // Names don't change the selector, so the second signature is the same candidate.
+            let functions = signatures.find_functions(&id("transfer(address,uint256)"));
+            assert_eq!(functions.len(), 1);
+            assert_eq!(signatures.functions.len(), 1);
+        }
+    }
+} /* signatures */
+
+pub mod bytecode {
+    use ethers::core::{types::Address, utils::hex};
+    use std::fmt;
+
+    pub const JUMPDEST: u8 = 0x5b;
+    const PUSH1: u8 = 0x60;
+    const PUSH4: u8 = 0x63;
+    const PUSH32: u8 = 0x7f;
+    const EQ: u8 = 0x14;
+
+    /// An instruction of EVM bytecode.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Instruction<'a> {
+        /// Where the instruction starts in the code, which is what jumps refer to.
+        pub offset: usize,
+        pub opcode: u8,
+        /// The immediate of `PUSH1` to `PUSH32`, shorter than its size if the code ends early.
+        pub push_data: &'a [u8],
+    }
+
+    impl Instruction<'_> {
+        pub fn is_jumpdest(&self) -> bool {
+            self.opcode == JUMPDEST
+        }
+    }
+
+    impl fmt::Display for Instruction<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match opcode_name(self.opcode) {
+                Some(name) if self.push_data.is_empty() => write!(f, "{name}"),
+                Some(name) => write!(f, "{name} 0x{}", hex::encode(self.push_data)),
+                None => write!(f, "UNKNOWN 0x{:02x}", self.opcode),
+            }
+        }
+    }
+
+    /// The metadata solc and Vyper append to the code, as a CBOR map followed by its length.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct Metadata {
+        /// e.g. `solc 0.8.19`.
+        pub compiler: Option<String>,
+        /// The IPFS CIDv0 of the metadata JSON, e.g. `QmQ8Lh...`.
+        pub ipfs: Option<String>,
+        /// The Swarm hash of the metadata JSON, from `bzzr0` or `bzzr1`.
+        pub bzz: Option<String>,
+        /// Set when the contract was compiled with experimental features.
+        pub experimental: bool,
+    }
+
+    /// Splits `code` into the part to disassemble and the metadata at its end, if there is any.
+    pub fn split_metadata(code: &[u8]) -> (&[u8], Option<Metadata>) {
+        let Some((rest, length)) = code.len().checked_sub(2).map(|end| {
+            (
+                &code[..end],
+                usize::from(code[end]) << 8 | usize::from(code[end + 1]),
+            )
+        }) else {
+            return (code, None);
+        };
+        let Some(start) = rest.len().checked_sub(length) else {
+            return (code, None);
+        };
+        match decode_metadata(&rest[start..]) {
+            Some(metadata) => (&rest[..start], Some(metadata)),
+            None => (code, None),
+        }
+    }
+
+    /// Decodes `code` one instruction at a time. Data embedded in the code is decoded as if it
+    /// were instructions, as there's no telling them apart without running it.
+    pub fn disassemble(code: &[u8]) -> Vec<Instruction<'_>> {
+        let mut instructions = vec![];
+        let mut offset = 0;
+        while let Some(&opcode) = code.get(offset) {
+            let size = match opcode {
+                PUSH1..=PUSH32 => usize::from(opcode - PUSH1 + 1),
+                _ => 0,
+            };
+            let end = (offset + 1 + size).min(code.len());
+            instructions.push(Instruction {
+                offset,
+                opcode,
+                push_data: &code[offset + 1..end],
+            });
+            offset = end;
+        }
+        instructions
+    }
+
+    /// The implementation of an EIP-1167 minimal proxy, which is all its code consists of.
+    pub fn minimal_proxy_implementation(code: &[u8]) -> Option<Address> {
+        const PREFIX: &[u8] = &[0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+        const SUFFIX: &[u8] = &[
+            0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b,
+            0xf3,
+        ];
+        let implementation = code.strip_prefix(PREFIX)?.strip_suffix(SUFFIX)?;
+        (implementation.len() == 20).then(|| Address::from_slice(implementation))
+    }
+
+    /// Function selectors the code compares the calldata with, in the order they're checked.
+    /// Solidity dispatches with `PUSH4 <selector>` followed by an `EQ` within two instructions.
+    pub fn selectors(instructions: &[Instruction]) -> Vec<[u8; 4]> {
+        let mut selectors = vec![];
+        for (i, instruction) in instructions.iter().enumerate() {
+            if instruction.opcode != PUSH4
+                || !instructions[i + 1..]
+                    .iter()
+                    .take(2)
+                    .any(|instruction| instruction.opcode == EQ)
+            {
+                continue;
+            }
+            if let Ok(selector) = <[u8; 4]>::try_from(instruction.push_data) {
+                if !selectors.contains(&selector) {
+                    selectors.push(selector);
+                }
+            }
+        }
+        selectors
+    }
+
+    pub fn opcode_name(opcode: u8) -> Option<&'static str> {
+        const PUSH: [&str; 32] = [
+            "PUSH1", "PUSH2", "PUSH3", "PUSH4", "PUSH5", "PUSH6", "PUSH7", "PUSH8", "PUSH9",
+            "PUSH10", "PUSH11", "PUSH12", "PUSH13", "PUSH14", "PUSH15", "PUSH16", "PUSH17",
+            "PUSH18", "PUSH19", "PUSH20", "PUSH21", "PUSH22", "PUSH23", "PUSH24", "PUSH25",
+            "PUSH26", "PUSH27", "PUSH28", "PUSH29", "PUSH30", "PUSH31", "PUSH32",
+        ];
+        const DUP: [&str; 16] = [
+            "DUP1", "DUP2", "DUP3", "DUP4", "DUP5", "DUP6", "DUP7", "DUP8", "DUP9", "DUP10",
+            "DUP11", "DUP12", "DUP13", "DUP14", "DUP15", "DUP16",
+        ];
+        const SWAP: [&str; 16] = [
+            "SWAP1", "SWAP2", "SWAP3", "SWAP4", "SWAP5", "SWAP6", "SWAP7", "SWAP8", "SWAP9",
+            "SWAP10", "SWAP11", "SWAP12", "SWAP13", "SWAP14", "SWAP15", "SWAP16",
+        ];
+        const LOG: [&str; 5] = ["LOG0", "LOG1", "LOG2", "LOG3", "LOG4"];
+
+        let name = match opcode {
+            0x00 => "STOP",
+            0x01 => "ADD",
+            0x02 => "MUL",
+            0x03 => "SUB",
+            0x04 => "DIV",
+            0x05 => "SDIV",
+            0x06 => "MOD",
+            0x07 => "SMOD",
+            0x08 => "ADDMOD",
+            0x09 => "MULMOD",
+            0x0a => "EXP",
+            0x0b => "SIGNEXTEND",
+            0x10 => "LT",
+            0x11 => "GT",
+            0x12 => "SLT",
+            0x13 => "SGT",
+            0x14 => "EQ",
+            0x15 => "ISZERO",
+            0x16 => "AND",
+            0x17 => "OR",
+            0x18 => "XOR",
+            0x19 => "NOT",
+            0x1a => "BYTE",
+            0x1b => "SHL",
+            0x1c => "SHR",
+            0x1d => "SAR",
+            0x20 => "KECCAK256",
+            0x30 => "ADDRESS",
+            0x31 => "BALANCE",
+            0x32 => "ORIGIN",
+            0x33 => "CALLER",
+            0x34 => "CALLVALUE",
+            0x35 => "CALLDATALOAD",
+            0x36 => "CALLDATASIZE",
+            0x37 => "CALLDATACOPY",
+            0x38 => "CODESIZE",
+            0x39 => "CODECOPY",
+            0x3a => "GASPRICE",
+            0x3b => "EXTCODESIZE",
+            0x3c => "EXTCODECOPY",
+            0x3d => "RETURNDATASIZE",
+            0x3e => "RETURNDATACOPY",
+            0x3f => "EXTCODEHASH",
+            0x40 => "BLOCKHASH",
+            0x41 => "COINBASE",
+            0x42 => "TIMESTAMP",
+            0x43 => "NUMBER",
+            0x44 => "PREVRANDAO",
+            0x45 => "GASLIMIT",
+            0x46 => "CHAINID",
+            0x47 => "SELFBALANCE",
+            0x48 => "BASEFEE",
+            0x49 => "BLOBHASH",
+            0x4a => "BLOBBASEFEE",
+            0x50 => "POP",
+            0x51 => "MLOAD",
+            0x52 => "MSTORE",
+            0x53 => "MSTORE8",
+            0x54 => "SLOAD",
+            0x55 => "SSTORE",
+            0x56 => "JUMP",
+            0x57 => "JUMPI",
+            0x58 => "PC",
+            0x59 => "MSIZE",
+            0x5a => "GAS",
+            0x5b => "JUMPDEST",
+            0x5c => "TLOAD",
+            0x5d => "TSTORE",
+            0x5e => "MCOPY",
+            0x5f => "PUSH0",
+            0x60..=0x7f => PUSH[usize::from(opcode - 0x60)],
+            0x80..=0x8f => DUP[usize::from(opcode - 0x80)],
+            0x90..=0x9f => SWAP[usize::from(opcode - 0x90)],
+            0xa0..=0xa4 => LOG[usize::from(opcode - 0xa0)],
+            0xf0 => "CREATE",
+            0xf1 => "CALL",
+            0xf2 => "CALLCODE",
+            0xf3 => "RETURN",
+            0xf4 => "DELEGATECALL",
+            0xf5 => "CREATE2",
+            0xfa => "STATICCALL",
+            0xfd => "REVERT",
+            0xfe => "INVALID",
+            0xff => "SELFDESTRUCT",
+            _ => return None,
+        };
+        Some(name)
+    }
+
+    fn decode_metadata(cbor: &[u8]) -> Option<Metadata> {
+        let mut decoder = Cbor { data: cbor };
+        let (5, entries) = decoder.head()? else {
+            return None;
+        };
+        let mut metadata = Metadata::default();
+        for _ in 0..entries {
+            let Value::Text(key) = decoder.value()? else {
+                return None;
+            };
+            match (key, decoder.value()?) {
+                ("solc", Value::Bytes(&[major, minor, patch])) => {
+                    metadata.compiler = Some(format!("solc {major}.{minor}.{patch}"));
+                }
+                // Prerelease builds record their full version string.
+                ("solc", Value::Text(version)) => {
+                    metadata.compiler = Some(format!("solc {version}"));
+                }
+                ("vyper", Value::Array(version)) => {
+                    let version = version
+                        .iter()
+                        .map(|part| match part {
+                            Value::Uint(part) => Some(part.to_string()),
+                            _ => None,
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+                    metadata.compiler = Some(format!("vyper {}", version.join(".")));
+                }
+                ("ipfs", Value::Bytes(hash)) => metadata.ipfs = Some(base58(hash)),
+                ("bzzr0" | "bzzr1", Value::Bytes(hash)) => {
+                    metadata.bzz = Some(format!("0x{}", hex::encode(hash)));
+                }
+                ("experimental", Value::Bool(experimental)) => {
+                    metadata.experimental = experimental;
+                }
+                _ => {}
+            }
+        }
+        // Anything left over means the length didn't point at metadata after all.
+        decoder.data.is_empty().then_some(metadata)
+    }
+
+    /// How deeply arrays may be nested in metadata.
+    const MAX_CBOR_DEPTH: usize = 4;
+
+    /// The subset of CBOR compilers write metadata with.
+    enum Value<'a> {
+        Uint(u64),
+        Bytes(&'a [u8]),
+        Text(&'a str),
+        Array(Vec<Value<'a>>),
+        Bool(bool),
+    }
+
+    struct Cbor<'a> {
+        data: &'a [u8],
+    }
+
+    impl<'a> Cbor<'a> {
+        fn take(&mut self, length: u64) -> Option<&'a [u8]> {
+            let length = usize::try_from(length).ok()?;
+            if length > self.data.len() {
+                return None;
+            }
+            let (taken, rest) = self.data.split_at(length);
+            self.data = rest;
+            Some(taken)
+        }
+
+        /// The major type and argument of the next item.
+        fn head(&mut self) -> Option<(u8, u64)> {
+            let initial = *self.take(1)?.first()?;
+            let argument = match initial & 0x1f {
+                info @ 0..=23 => u64::from(info),
+                info @ 24..=27 => self
+                    .take(1 << (info - 24))?
+                    .iter()
+                    .fold(0, |argument, byte| argument << 8 | u64::from(*byte)),
+                _ => return None,
+            };
+            Some((initial >> 5, argument))
+        }
+
+        fn value(&mut self) -> Option<Value<'a>> {
+            self.nested_value(0)
+        }
+
+        /// The next item, inside `depth` arrays. Metadata is never nested deeper than
+        /// `MAX_CBOR_DEPTH`, so anything deeper isn't metadata.
+        fn nested_value(&mut self, depth: usize) -> Option<Value<'a>> {
+            match self.head()? {
+                (0, value) => Some(Value::Uint(value)),
+                (2, length) => self.take(length).map(Value::Bytes),
+                (3, length) => std::str::from_utf8(self.take(length)?)
+                    .ok()
+                    .map(Value::Text),
+                (4, _) if depth >= MAX_CBOR_DEPTH => None,
+                (4, length) => (0..length)
+                    .map(|_| self.nested_value(depth + 1))
+                    .collect::<Option<Vec<_>>>()
+                    .map(Value::Array),
+                (7, 20) => Some(Value::Bool(false)),
+                (7, 21) => Some(Value::Bool(true)),
+                _ => None,
+            }
+        }
+    }
+
+    fn base58(bytes: &[u8]) -> String {
+        const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+        // Base-58 digits, least significant first.
+        let mut digits: Vec<u8> = vec![];
+        for byte in bytes {
+            let mut carry = u32::from(*byte);
+            for digit in digits.iter_mut() {
+                carry += u32::from(*digit) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                digits.push((carry % 58) as u8);
+                carry /= 58;
+            }
+        }
+        let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+        "1".repeat(zeros)
+            + &digits
+                .iter()
+                .rev()
+                .map(|digit| char::from(ALPHABET[usize::from(*digit)]))
+                .collect::<String>()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Metadata with the given CBOR map, followed by its length.
+        fn with_metadata(code: &[u8], cbor: &[u8]) -> Vec<u8> {
+            [code, cbor, &(cbor.len() as u16).to_be_bytes()].concat()
+        }
+
+        #[test]
+        fn disassembles_pushes_and_opcodes() {
+            let code = [0x60, 0x80, 0x60, 0x40, 0x52, 0x5b, 0x0c, 0x61, 0x01];
+            let instructions = disassemble(&code);
+
+            assert_eq!(
+                instructions
+                    .iter()
+                    .map(|instruction| (instruction.offset, instruction.to_string()))
+                    .collect::<Vec<_>>(),
+                [
+                    (0, "PUSH1 0x80".to_owned()),
+                    (2, "PUSH1 0x40".to_owned()),
+                    (4, "MSTORE".to_owned()),
+                    (5, "JUMPDEST".to_owned()),
+                    (6, "UNKNOWN 0x0c".to_owned()),
+                    // The code ends before the rest of the immediate.
+                    (7, "PUSH2 0x01".to_owned()),
+                ]
+            );
+            assert!(instructions[3].is_jumpdest());
+        }
+
+        #[test]
+        fn finds_dispatched_selectors() {
+            // DUP1 PUSH4 0xa9059cbb EQ, and a PUSH4 that isn't compared.
+            let code = [
+                0x80, 0x63, 0xa9, 0x05, 0x9c, 0xbb, 0x14, 0x63, 0x12, 0x34, 0x56, 0x78, 0x50, 0x50,
+                0x50,
+            ];
+            assert_eq!(selectors(&disassemble(&code)), [[0xa9, 0x05, 0x9c, 0xbb]]);
+        }
+
+        #[test]
+        fn splits_solc_metadata() {
+            // {"ipfs": <34-byte multihash>, "solc": 0.8.19}
+            let mut cbor = vec![0xa2, 0x64, b'i', b'p', b'f', b's', 0x58, 0x22, 0x12, 0x20];
+            cbor.extend([0xab; 32]);
+            cbor.extend([0x64, b's', b'o', b'l', b'c', 0x43, 0x00, 0x08, 0x13]);
+            let code = with_metadata(&[0x60, 0x80, 0x00], &cbor);
+
+            let (executable, metadata) = split_metadata(&code);
+            let metadata = metadata.unwrap();
+            assert_eq!(executable, [0x60, 0x80, 0x00]);
+            assert_eq!(metadata.compiler.as_deref(), Some("solc 0.8.19"));
+            assert!(metadata.ipfs.unwrap().starts_with("Qm"));
+            assert_eq!(metadata.bzz, None);
+            assert!(!metadata.experimental);
+        }
+
+        #[test]
+        fn splits_vyper_metadata() {
+            // {"vyper": [0, 3, 10]}
+            let cbor = [
+                0xa1, 0x65, b'v', b'y', b'p', b'e', b'r', 0x83, 0x00, 0x03, 0x0a,
+            ];
+            let code = with_metadata(&[0x00], &cbor);
+            assert_eq!(
+                split_metadata(&code).1.unwrap().compiler.as_deref(),
+                Some("vyper 0.3.10")
+            );
+        }
+
+        #[test]
+        fn leaves_code_without_metadata_whole() {
+            for code in [
+                &[][..],
+                &[0x00],
+                &[0x60, 0x80, 0x60, 0x40],
+                &[0x00, 0x00, 0x01],
+            ] {
+                assert_eq!(split_metadata(code), (code, None));
+            }
+        }
+
+        #[test]
+        fn rejects_deeply_nested_metadata() {
+            // {"vyper": [[[[[0]]]]]}
+            let cbor = [
+                0xa1, 0x65, b'v', b'y', b'p', b'e', b'r', 0x81, 0x81, 0x81, 0x81, 0x81, 0x00,
+            ];
+            let code = with_metadata(&[0x00], &cbor);
+            assert_eq!(split_metadata(&code), (&code[..], None));
+        }
+
+        #[test]
+        fn finds_the_implementation_of_a_minimal_proxy() {
+            let implementation = Address::repeat_byte(0xbe);
+            let code = [
+                &[0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73][..],
+                implementation.as_bytes(),
+                &[
+                    0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd,
+                    0x5b, 0xf3,
+                ],
+            ]
+            .concat();
+
+            assert_eq!(minimal_proxy_implementation(&code), Some(implementation));
+            assert_eq!(minimal_proxy_implementation(&code[1..]), None);
+            assert_eq!(
+                minimal_proxy_implementation(&[&code[..], &[0x00]].concat()),
+                None
+            );
+        }
+    }
+} /* bytecode */
+
+pub mod storage {
+    use anyhow::{bail, Context, Result};
+    use ethers::{
+        core::types::{Address, H256, U256},
+        utils::keccak256,
+    };
+    use serde::{Deserialize, Deserializer};
+    use std::{collections::HashMap, fs, path::Path};
+
+    /// The `storageLayout` output of solc, e.g. from `solc --storage-layout` or
+    /// `forge inspect <contract> storageLayout`.
+    #[derive(Clone, Debug, Default, Deserialize)]
+    pub struct StorageLayout {
+        pub storage: Vec<StorageVariable>,
+        pub types: Option<HashMap<String, StorageType>>,
+    }
+
+    #[derive(Clone, Debug, Deserialize)]
+    pub struct StorageVariable {
+        pub label: String,
+        /// Where the variable starts in its slot, counted in bytes from the right.
+        pub offset: usize,
+        #[serde(deserialize_with = "deserialize_u256_from_decimal")]
+        pub slot: U256,
+        /// A key of `types`, e.g. `t_mapping(t_address,t_uint256)`.
+        #[serde(rename = "type")]
+        pub kind: String,
+    }
+
+    #[derive(Clone, Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct StorageType {
+        /// The Solidity type, e.g. `mapping(address => uint256)`.
+        pub label: String,
+        pub number_of_bytes: String,
+    }
+
+    fn deserialize_u256_from_decimal<'de, D>(deserializer: D) -> Result<U256, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+
+        U256::from_dec_str(&s).map_err(serde::de::Error::custom)
+    }
+
+    impl StorageLayout {
+        pub fn load(path: &Path) -> Result<Self> {
+            let json = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse {}", path.display()))
+        }
+
+        pub fn variable(&self, label: &str) -> Option<&StorageVariable> {
+            self.storage.iter().find(|variable| variable.label == label)
+        }
+
+        /// The variables stored in `slot`, several if they're packed together.
+        pub fn variables_at(&self, slot: H256) -> Vec<&StorageVariable> {
+            let slot = U256::from_big_endian(slot.as_bytes());
+            self.storage
+                .iter()
+                .filter(|variable| variable.slot == slot)
+                .collect()
+        }
+
+        pub fn type_of(&self, variable: &StorageVariable) -> Option<&StorageType> {
+            self.types.as_ref()?.get(&variable.kind)
+        }
+    }
+
+    /// Where EIP-1967 proxies keep their implementation.
+    pub fn eip1967_implementation_slot() -> H256 {
+        eip1967_slot("eip1967.proxy.implementation")
+    }
+
+    pub fn eip1967_admin_slot() -> H256 {
+        eip1967_slot("eip1967.proxy.admin")
+    }
+
+    pub fn eip1967_beacon_slot() -> H256 {
+        eip1967_slot("eip1967.proxy.beacon")
+    }
+
+    /// Where EIP-1822 (UUPS) proxies keep their implementation.
+    pub fn eip1822_slot() -> H256 {
+        H256(keccak256("PROXIABLE"))
+    }
+
+    /// Where OpenZeppelin proxies kept their implementation before EIP-1967, e.g. USDC's.
+    pub fn zeppelinos_implementation_slot() -> H256 {
+        H256(keccak256("org.zeppelinos.proxy.implementation"))
+    }
+
+    pub fn zeppelinos_admin_slot() -> H256 {
+        H256(keccak256("org.zeppelinos.proxy.admin"))
+    }
+
+    /// `keccak256(id) - 1`, which has no known preimage and so can't collide with a mapping.
+    fn eip1967_slot(id: &str) -> H256 {
+        H256(to_bytes(U256::from_big_endian(&keccak256(id)) - 1))
+    }
+
+    /// The bytes of a variable of `size` bytes at `offset` in `value`.
+    pub fn packed(value: &H256, offset: usize, size: usize) -> &[u8] {
+        let end = 32usize.saturating_sub(offset);
+        &value.as_bytes()[end.saturating_sub(size)..end]
+    }
+
+    pub fn as_uint(value: &H256) -> U256 {
+        U256::from_big_endian(value.as_bytes())
+    }
+
+    /// The lowest 20 bytes, where an address is stored on its own.
+    pub fn as_address(value: &H256) -> Address {
+        Address::from_slice(&value.as_bytes()[12..])
+    }
+
+    pub fn as_bool(value: &H256) -> bool {
+        !value.is_zero()
+    }
+
+    /// Computes the slot a query refers to. A query starts with a slot number, in decimal or
+    /// `0x` hex, or with the name of a variable in `layout`, followed by any of:
+    ///
+    /// - `[key]`: the entry of a mapping, at `keccak256(key . slot)`. Keys are numbers,
+    ///   addresses, `true`/`false` or `"strings"`.
+    /// - `#i`: element `i` of a dynamic array, at `keccak256(slot) + i`.
+    /// - `+i`: the slot `i` after, for struct members and fixed-size arrays.
+    ///
+    /// e.g. `_balances[0xd8da6bf26964af9d7eed9e03e53415d37aa96045]` or `3#2+1`.
+    pub fn parse_slot(query: &str, layout: Option<&StorageLayout>) -> Result<H256> {
+        let operators = ['[', '#', '+'];
+        let query = query.trim();
+        let (base, mut rest) = query.split_at(query.find(operators).unwrap_or(query.len()));
+        let base = base.trim();
+        let mut slot = if let Some(slot) = parse_uint(base) {
+            slot
+        } else if let Some(variable) = layout.and_then(|layout| layout.variable(base)) {
+            variable.slot
+        } else if layout.is_some() {
+            bail!("Unknown slot or variable `{base}`");
+        } else {
+            bail!("Unknown slot `{base}`; variable names need a storage layout");
+        };
+
+        while let Some(operator) = rest.chars().next() {
+            rest = &rest[operator.len_utf8()..];
+            match operator {
+                '[' => {
+                    let end = if let Some(string) = rest.strip_prefix('"') {
+                        string.find('"').map(|quote| quote + 2)
+                    } else {
+                        rest.find(']')
+                    }
+                    .with_context(|| format!("Unclosed `[` in `{query}`"))?;
+                    let key = encode_key(rest[..end].trim())?;
+                    rest = rest[end..]
+                        .trim_start()
+                        .strip_prefix(']')
+                        .with_context(|| format!("Unclosed `[` in `{query}`"))?;
+                    slot =
+                        U256::from_big_endian(&keccak256([key, to_bytes(slot).to_vec()].concat()));
+                }
+                '#' | '+' => {
+                    let end = rest.find(operators).unwrap_or(rest.len());
+                    let i = parse_uint(rest[..end].trim())
+                        .with_context(|| format!("Expected a number after `{operator}`"))?;
+                    rest = &rest[end..];
+                    if operator == '#' {
+                        slot = U256::from_big_endian(&keccak256(to_bytes(slot)));
+                    }
+                    slot = slot.overflowing_add(i).0;
+                }
+                _ => bail!("Unexpected `{operator}` in `{query}`"),
+            }
+        }
+        Ok(H256(to_bytes(slot)))
+    }
+
+    fn parse_uint(s: &str) -> Option<U256> {
+        // Both parsers take an empty string for 0.
+        match s.strip_prefix("0x") {
+            Some(hex) if !hex.is_empty() => U256::from_str_radix(hex, 16).ok(),
+            None if !s.is_empty() => U256::from_dec_str(s).ok(),
+            _ => None,
+        }
+    }
+
+    fn to_bytes(value: U256) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        value.to_big_endian(&mut bytes);
+        bytes
+    }
+
+    /// Encodes a mapping key the way Solidity hashes it: padded to 32 bytes, except for strings.
+    fn encode_key(key: &str) -> Result<Vec<u8>> {
+        if let Some(string) = key.strip_prefix('"').and_then(|key| key.strip_suffix('"')) {
+            return Ok(string.as_bytes().to_vec());
+        }
+        let value = match key {
+            "true" => U256::one(),
+            "false" => U256::zero(),
+            // Addresses are numbers as far as padding goes.
+            _ => parse_uint(key).with_context(|| format!("Invalid mapping key `{key}`"))?,
+        };
+        Ok(to_bytes(value).to_vec())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn slot(hex: &str) -> H256 {
+            hex.parse().unwrap()
+        }
+
+        fn layout() -> StorageLayout {
+            serde_json::from_str(
+                r#"{
+                    "storage": [
+                        { "label": "_balances", "offset": 0, "slot": "0", "type": "t_mapping" },
+                        { "label": "owner", "offset": 0, "slot": "5", "type": "t_address" }
+                    ],
+                    "types": null
+                }"#,
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn parses_slot_numbers() {
+            assert_eq!(parse_slot("0", None).unwrap(), H256::zero());
+            assert_eq!(
+                parse_slot(" 0x2a ", None).unwrap(),
+                H256::from_low_u64_be(42)
+            );
+            assert_eq!(parse_slot("42", None).unwrap(), H256::from_low_u64_be(42));
+        }
+
+        #[test]
+        fn parses_mapping_entries() {
+            // keccak256(uint256(0) . uint256(0))
+            let entry = slot("0xad3228b676f7d3cd4284a5443f17f1962b36e491b30a40b2405849e597ba5fb5");
+            assert_eq!(parse_slot("0[0]", None).unwrap(), entry);
+            assert_eq!(parse_slot("0[false]", None).unwrap(), entry);
+            assert_eq!(parse_slot("0[ 0x0 ]", None).unwrap(), entry);
+            assert_ne!(parse_slot("0[1]", None).unwrap(), entry);
+            // Strings are hashed unpadded, so `"a]b"` is a single key.
+            assert!(parse_slot(r#"0["a]b"]"#, None).is_ok());
+        }
+
+        #[test]
+        fn parses_array_elements_and_offsets() {
+            // keccak256(uint256(3))
+            let element = "0xc2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f8";
+            assert_eq!(
+                parse_slot("3#0", None).unwrap(),
+                slot(&format!("{element}5b"))
+            );
+            assert_eq!(
+                parse_slot("3#2+1", None).unwrap(),
+                slot(&format!("{element}5e"))
+            );
+            assert_eq!(parse_slot("1+2", None).unwrap(), H256::from_low_u64_be(3));
+        }
+
+        #[test]
+        fn parses_variable_names_with_a_layout() {
+            let layout = layout();
+            assert_eq!(
+                parse_slot("owner", Some(&layout)).unwrap(),
+                H256::from_low_u64_be(5)
+            );
+            assert_eq!(
+                parse_slot("_balances[0]", Some(&layout)).unwrap(),
+                parse_slot("0[0]", None).unwrap()
+            );
+            assert!(parse_slot("owner", None).is_err());
+            assert!(parse_slot("totalSupply", Some(&layout)).is_err());
+        }
+
+        #[test]
+        fn rejects_malformed_queries() {
+            for query in ["", "0x", "0[1", "0[nope]", "0#", "0+x", "0]"] {
+                assert!(parse_slot(query, None).is_err(), "{query}");
+            }
+        }
+
+        #[test]
+        fn computes_proxy_slots() {
+            assert_eq!(
+                eip1967_implementation_slot(),
+                slot("0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc")
+            );
+            assert_eq!(
+                eip1967_admin_slot(),
+                slot("0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103")
+            );
+        }
+
+        #[test]
+        fn reads_packed_variables() {
+            let value = slot("0x00000000000000000000000000000000000000000000000000000000aabbccdd");
+            assert_eq!(packed(&value, 0, 1), [0xdd]);
+            assert_eq!(packed(&value, 1, 2), [0xbb, 0xcc]);
+            assert_eq!(as_uint(&value), U256::from(0xaabbccddu64));
+            assert!(as_bool(&value));
+        }
+    }
+} /* storage */
@@ -1,32 +1,132 @@
 mod app;
+mod config;
 mod ethers;
 mod network;
 mod route;
 mod ui;
 mod widget;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use app::{event_handling::event_handling, App};
 use chrono::Utc;
 use clap::Parser;
-use crossterm::{event, execute, terminal};
-use log::LevelFilter;
-use network::{IoEvent, Network};
+use crossterm::{
+    event::{self, EventStream},
+    execute, terminal,
+};
+use futures::StreamExt;
+use log::{warn, LevelFilter};
+use network::{
+    backend::RpcBackend, cache::Cache, chain::ChainContext, failover::FailoverBackend,
+    scheduler::is_positive, IoEvent, Message, Network, RequestId, MAX_CONCURRENT_IO_EVENTS,
+};
 use ratatui::prelude::*;
 use simplelog::{ColorChoice, CombinedLogger, Config, TermLogger, TerminalMode, WriteLogger};
-use std::{io, sync::Arc, time::Duration};
-use tokio::sync::Mutex;
+use std::{collections::HashMap, io, path::PathBuf, sync::Arc, time::Duration};
+use tokio::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex, Semaphore,
+    },
+    task::AbortHandle,
+    time::MissedTickBehavior,
+};
+
+/// How many requests from the UI can wait for the network task before new ones are turned down.
+const IO_EVENT_QUEUE_SIZE: usize = 256;
+/// How many results can wait for the UI before the network task waits for it to catch up.
+const MESSAGE_QUEUE_SIZE: usize = 256;
+/// How long to wait before asking for the chain ID again when the endpoints can't be reached.
+const CHAIN_DETECTION_RETRY_DELAY: Duration = Duration::from_secs(5);
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Json-RPC URL
-    #[arg(short, long, default_value = "https://eth.llamarpc.com")]
-    endpoint: String,
+    /// Config file to read instead of the default one
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Json-RPC URL (http(s)://, ws(s)://, ipc:// or a path to an IPC socket). Repeat it, or
+    /// separate URLs with commas, to fail over to the next endpoint when one is unhealthy
+    /// [default: https://eth.llamarpc.com]
+    #[arg(short, long, value_delimiter = ',')]
+    endpoint: Vec<String>,
+    /// Directory to persist finalized blocks, transactions and receipts in
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+    /// Directory to write logs to [default: logs]
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+    /// JSON file with extra function or event signatures, e.g. ["transfer(address,uint256)",
+    /// "event Transfer(address indexed, address indexed, uint256)"]. Can be repeated
+    #[arg(long = "signatures", value_name = "FILE")]
+    signature_files: Vec<PathBuf>,
+    /// Etherscan-compatible explorer API URL, e.g. a Blockscout instance. Defaults to the
+    /// chain's Etherscan
+    #[arg(long)]
+    explorer_api_url: Option<String>,
+    /// Explorer API key. Defaults to the chain's environment variable, e.g. ETHERSCAN_API_KEY
+    #[arg(long)]
+    explorer_api_key: Option<String>,
+    /// Maximum number of JSON-RPC requests per second [default: 20]
+    #[arg(long)]
+    rate_limit: Option<f64>,
+    /// Maximum number of JSON-RPC requests in flight [default: 16]
+    #[arg(long)]
+    max_concurrency: Option<usize>,
+    /// How many times a rate-limited or failed request is retried [default: 5]
+    #[arg(long)]
+    max_retries: Option<u32>,
+}
+
+impl Args {
+    /// Overrides `config` with the flags that were given.
+    fn apply(self, mut config: config::Config) -> Result<config::Config> {
+        if !self.endpoint.is_empty() {
+            config.endpoints = self.endpoint;
+        }
+        if let Some(cache_dir) = self.cache_dir {
+            config.cache_dir = Some(cache_dir);
+        }
+        if let Some(log_dir) = self.log_dir {
+            config.log_dir = log_dir;
+        }
+        config.signatures.extend(self.signature_files);
+        // Chain-specific explorer settings would otherwise take precedence over the flags.
+        for explorer in std::iter::once(&mut config.explorer)
+            .chain(config.chains.values_mut().map(|chain| &mut chain.explorer))
+        {
+            if let Some(api_url) = self.explorer_api_url.as_ref() {
+                explorer.api_url = Some(api_url.to_owned());
+            }
+            if let Some(api_key) = self.explorer_api_key.as_ref() {
+                explorer.api_key = Some(api_key.to_owned());
+            }
+        }
+        if let Some(rate_limit) = self.rate_limit {
+            if !is_positive(rate_limit) {
+                bail!("--rate-limit must be a positive number");
+            }
+            config.scheduler.requests_per_second = rate_limit;
+        }
+        if let Some(max_concurrency) = self.max_concurrency {
+            config.scheduler.max_concurrency = max_concurrency;
+        }
+        if let Some(max_retries) = self.max_retries {
+            config.scheduler.max_retries = max_retries;
+        }
+        Ok(config)
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let _ = std::fs::create_dir("logs");
+    let args = Args::parse();
+    let config = config::Config::load(args.config.to_owned())?;
+    let config = args.apply(config)?;
+    if config.endpoints.is_empty() {
+        bail!("No endpoints are configured");
+    }
+
+    let _ = std::fs::create_dir_all(&config.log_dir);
     CombinedLogger::init(vec![
         TermLogger::new(
             LevelFilter::Error,
@@ -37,7 +137,11 @@ async fn main() -> Result<()> {
         WriteLogger::new(
             LevelFilter::Debug,
             Config::default(),
-            std::fs::File::create(format!("logs/{}.log", Utc::now().format("%Y%m%d%H%M")))?,
+            std::fs::File::create(
+                config
+                    .log_dir
+                    .join(format!("{}.log", Utc::now().format("%Y%m%d%H%M"))),
+            )?,
         ),
     ])?;
 
@@ -52,20 +156,17 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let (sync_io_tx, sync_io_rx) = std::sync::mpsc::channel::<IoEvent>();
-
-    let args = Args::parse();
+    let (io_tx, io_rx) = mpsc::channel::<(RequestId, IoEvent)>(IO_EVENT_QUEUE_SIZE);
+    let (message_tx, mut message_rx) = mpsc::channel::<Message>(MESSAGE_QUEUE_SIZE);
 
     // create app and run it
-    let app = Arc::new(Mutex::new(App::new(sync_io_tx, &args.endpoint)));
-    let cloned_app = Arc::clone(&app);
+    let mut app = App::new(io_tx, &config);
+    let signatures = Arc::clone(&app.signatures);
+    let ui_poll_interval = config.refresh.ui_poll_interval();
 
-    std::thread::spawn(move || {
-        let mut network = Network::new(&app, &args.endpoint);
-        start_tokio(sync_io_rx, &mut network);
-    });
+    tokio::spawn(start_network(io_rx, message_tx, signatures, config));
 
-    let res = start_ui(&mut terminal, &cloned_app).await;
+    let res = start_ui(&mut terminal, &mut app, &mut message_rx, ui_poll_interval).await;
 
     // restore terminal
     terminal::disable_raw_mode()?;
@@ -84,34 +185,200 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn start_ui<B: Backend>(terminal: &mut Terminal<B>, app: &Arc<Mutex<App>>) -> Result<()> {
-    let mut is_first_render = true;
+/// Owns `App`: terminal events and messages from the network task are applied to it in turn, and
+/// it's redrawn only when either of them, or the passing of time, changed what's on screen.
+async fn start_ui<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    messages: &mut Receiver<Message>,
+    tick_rate: Duration,
+) -> Result<()> {
+    let mut events = EventStream::new();
+    let mut tick = tokio::time::interval(tick_rate);
+    tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    let height = terminal.size()?.height as usize;
+    app.dispatch(IoEvent::InitialSetup {
+        n: (height - 3 * 4) / 2 - 4,
+    });
+    app.dispatch(IoEvent::SubscribeNewHeads {
+        n: (height - 3 * 4) / 2 - 4,
+    });
 
+    let mut needs_redraw = true;
+    // Spinners move on every tick, while block ages and notifications only change every second.
+    let mut is_spinning = false;
+    let mut shows_elapsed_time = false;
+    let mut last_drawn_at = Utc::now().timestamp();
     loop {
-        let mut app = app.lock().await;
-        terminal.draw(|f| ui::ui_home(f, &mut app))?;
+        if needs_redraw {
+            terminal.draw(|f| ui::ui_home(f, app))?;
+            is_spinning = app.is_spinning();
+            shows_elapsed_time = app.shows_elapsed_time();
+            last_drawn_at = Utc::now().timestamp();
+        }
 
-        if event::poll(Duration::from_millis(250))? {
-            let is_q = event_handling(event::read()?, &mut app, terminal);
-            if is_q {
-                return Ok(());
+        tokio::select! {
+            event = events.next() => match event {
+                Some(Ok(event)) => {
+                    if event_handling(event, app, terminal) {
+                        return Ok(());
+                    }
+                    needs_redraw = true;
+                }
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(()),
+            },
+            Some(message) = messages.recv() => {
+                app.handle_message(message);
+                // Apply whatever else has arrived before redrawing once for all of it.
+                while let Ok(message) = messages.try_recv() {
+                    app.handle_message(message);
+                }
+                needs_redraw = true;
+            }
+            _ = tick.tick() => {
+                needs_redraw = is_spinning
+                    || (shows_elapsed_time && Utc::now().timestamp() != last_drawn_at);
+            }
+        }
+    }
+}
+
+async fn start_network(
+    mut io_rx: Receiver<(RequestId, IoEvent)>,
+    messages: Sender<Message>,
+    signatures: Arc<ethers::signatures::Signatures>,
+    config: config::Config,
+) {
+    let backend = Arc::new(FailoverBackend::new(&config.endpoints, config.scheduler));
+
+    // Cached data, the explorer and the token list all depend on the chain, so requests wait
+    // until it's known.
+    let chain = loop {
+        match ChainContext::detect(backend.as_ref()).await {
+            Ok(chain) => break chain,
+            Err(e) => {
+                warn!("Failed to detect the chain: {e:#}");
+                let failed = Message::Failed {
+                    request_id: None,
+                    io_event: Box::new(IoEvent::DetectChain),
+                    endpoint: backend.endpoint(),
+                    message: format!("{e:#}"),
+                };
+                if messages.send(failed).await.is_err() {
+                    return;
+                }
+                tokio::time::sleep(CHAIN_DETECTION_RETRY_DELAY).await;
             }
         }
+    };
+    let chain = chain.to_owned().with_config(config.chain(chain.id));
+    if messages
+        .send(Message::Chain(Box::new(chain.to_owned())))
+        .await
+        .is_err()
+    {
+        return;
+    }
+    // Block numbers and addresses only identify data within a chain.
+    let cache = Arc::new(Mutex::new(Cache::new(
+        config
+            .cache_dir
+            .map(|cache_dir| cache_dir.join(chain.id.to_string())),
+    )));
+
+    let network = Network::new(
+        messages,
+        backend,
+        cache,
+        chain,
+        signatures,
+        config.refresh.new_heads_poll_interval(),
+    );
 
-        if is_first_render {
-            let height = terminal.size()?.height as usize;
-            app.dispatch(IoEvent::InitialSetup {
-                n: (height - 3 * 4) / 2 - 4,
-            });
+    // Requests run concurrently, so a slow one can be cancelled without holding up the rest.
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_IO_EVENTS));
+    let mut in_flight_requests = HashMap::<RequestId, AbortHandle>::new();
+    while let Some((request_id, io_event)) = io_rx.recv().await {
+        in_flight_requests.retain(|_, request| !request.is_finished());
 
-            is_first_render = false;
+        if let IoEvent::Cancel { request_id } = io_event {
+            if let Some(request) = in_flight_requests.remove(&request_id) {
+                request.abort();
+            }
+            continue;
         }
+
+        let mut network = network.clone();
+        let permits = Arc::clone(&permits);
+        let request = tokio::spawn(async move {
+            // The subscription runs until the app quits, so it doesn't take up a permit.
+            let _permit = match io_event {
+                IoEvent::SubscribeNewHeads { .. } => None,
+                _ => match permits.acquire_owned().await {
+                    Ok(permit) => Some(permit),
+                    Err(_) => return,
+                },
+            };
+            network.handle_network_event(request_id, io_event).await;
+            network.update_app_with_backend_status().await;
+        });
+        in_flight_requests.insert(request_id, request.abort_handle());
     }
 }
 
-#[tokio::main]
-async fn start_tokio(io_rx: std::sync::mpsc::Receiver<IoEvent>, network: &mut Network) {
-    while let Ok(io_event) = io_rx.recv() {
-        let _ = network.handle_network_event(io_event).await;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(flags: &[&str], toml: &str) -> Result<config::Config> {
+        let args = Args::parse_from(std::iter::once("lazy-etherscan").chain(flags.iter().copied()));
+        args.apply(::toml::from_str(toml).unwrap())
+    }
+
+    #[test]
+    fn flags_override_the_config_file() {
+        let config = apply(
+            &[
+                "--endpoint=http://a,http://b",
+                "--rate-limit=3",
+                "--explorer-api-key=flag",
+            ],
+            r#"
+            endpoints = ["http://file"]
+
+            [scheduler]
+            requests_per_second = 5
+            max_retries = 2
+
+            [explorer]
+            api_key = "file"
+
+            [chains.5.explorer]
+            api_key = "goerli"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.endpoints, ["http://a", "http://b"]);
+        assert_eq!(config.scheduler.requests_per_second, 3.0);
+        assert_eq!(config.scheduler.max_retries, 2);
+        // The flag wins over chain-specific settings too.
+        assert_eq!(config.chain(1).explorer.api_key.as_deref(), Some("flag"));
+        assert_eq!(config.chain(5).explorer.api_key.as_deref(), Some("flag"));
+    }
+
+    #[test]
+    fn the_config_file_is_kept_without_flags() {
+        let config = apply(&[], "endpoints = [\"http://file\"]").unwrap();
+        assert_eq!(config.endpoints, ["http://file"]);
+        assert_eq!(config.scheduler.requests_per_second, 20.0);
+    }
+
+    #[test]
+    fn rejects_non_positive_rate_limits() {
+        assert!(apply(&["--rate-limit=0"], "").is_err());
+        assert!(apply(&["--rate-limit=-1"], "").is_err());
     }
 }
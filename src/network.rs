@@ -1,35 +1,85 @@
+pub mod backend;
+pub mod cache;
+pub mod chain;
+pub mod failover;
+#[cfg(test)]
+pub mod fake;
+pub mod scheduler;
 use crate::{
-    app::{statistics::Statistics, App},
-    ethers::types::{AddressInfo, BlockWithTransactionReceipts, TransactionWithReceipt},
-    route::{ActiveBlock, Route, RouteId},
-    widget::StatefulList,
+    app::statistics::Statistics,
+    ethers::{
+        abi::{self, ContractAbi},
+        bytecode,
+        signatures::Signatures,
+        storage,
+        types::{
+            AddressInfo, AddressTransactions, BlockWithTransactionReceipts, DecodedCall,
+            DecodedEvent, ERC20Token, Proxy, ProxyKind, TokenBalance, TransactionHistorySource,
+            TransactionWithReceipt,
+        },
+    },
+    route::RouteId,
 };
 use anyhow::Result;
+use backend::{is_ens_not_found, is_unsupported, RpcBackend};
+use cache::Cache;
+use chain::{ChainContext, ExplorerStatus};
 use ethers::{
-    core::types::{
-        Address, BlockId, BlockNumber, Chain, NameOrAddress, Transaction, TransactionReceipt,
-        TxHash, H256, U64,
+    core::{
+        abi::{decode, encode, ParamType, Token},
+        types::{
+            Address, BigEndianHash, Block, BlockId, BlockNumber, Bytes, NameOrAddress, Transaction,
+            TransactionReceipt, TxHash, H256, U256, U64,
+        },
+        utils::id,
+    },
+    etherscan::{
+        account::{NormalTransaction, Sort, TxListParams},
+        contract::ContractMetadata,
+        errors::EtherscanError,
+        Client,
     },
-    etherscan::Client,
-    providers::{Http, Middleware, Provider},
 };
-use futures::future::{join_all, try_join, try_join3};
+use futures::{
+    future::{join, join3, join_all, try_join},
+    stream, try_join, Future, StreamExt,
+};
+use log::warn;
+use scheduler::RateLimiter;
 use std::{
-    fs::File,
-    io::Write,
-    process::Command,
-    {error::Error, sync::Arc},
+    collections::HashSet,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use tempfile::tempdir;
-use tokio::sync::Mutex;
-
-const RATE_LIMIT: usize = 60;
-
+use tokio::sync::{mpsc::Sender, Mutex};
+
+/// How long a newly arrived block stays highlighted in the Latest Blocks pane.
+pub const NEW_BLOCK_HIGHLIGHT_SECS: i64 = 3;
+/// How many `IoEvent`s are handled at the same time. Others wait for one of them to finish.
+pub const MAX_CONCURRENT_IO_EVENTS: usize = 8;
+
+/// How many transactions of an address the explorer lists per page.
+pub const ADDRESS_TRANSACTIONS_PAGE_SIZE: usize = 25;
+/// How many blocks are scanned per page for the transactions of an address when there's no
+/// explorer.
+pub const ADDRESS_SCAN_BLOCKS: u64 = 100;
+/// How many `eth_call`s `Network::call_all` has in flight when the endpoint can't batch them.
+const MAX_CONCURRENT_CALLS: usize = 4;
+/// Etherscan's free tier allows 5 requests per second.
+const EXPLORER_REQUESTS_PER_SECOND: f64 = 5.0;
+/// How long block numbers are polled after a `newHeads` subscription fails before subscribing
+/// again.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(30);
+
+/// Identifies a dispatched `IoEvent`.
+pub type RequestId = u64;
+
+#[derive(Clone, PartialEq)]
 pub enum IoEvent {
     GetStatistics,
     GetNameOrAddressInfo {
         name_or_address: NameOrAddress,
-        is_searching: bool,
     },
     GetBlock {
         number: U64,
@@ -46,6 +96,9 @@ pub enum IoEvent {
     GetDecodedInputData {
         transaction: Transaction,
     },
+    GetDecodedLogs {
+        transaction_receipt: TransactionReceipt,
+    },
     GetLatestBlocks {
         n: usize,
     },
@@ -55,205 +108,331 @@ pub enum IoEvent {
     LookupAddresses {
         addresses: Vec<Address>,
     },
+    /// Pages are counted back from block `until`, or from the latest block if it's `None`.
+    GetAddressTransactions {
+        address: Address,
+        page: usize,
+        until: Option<U64>,
+    },
+    GetTokenBalances {
+        address: Address,
+    },
+    GetStorageAt {
+        address: Address,
+        slot: H256,
+    },
     InitialSetup {
         n: usize,
     },
+    SubscribeNewHeads {
+        n: usize,
+    },
+    /// Only reported by the network task, which retries it on its own until the chain is known.
+    DetectChain,
+    /// Aborts the request if it's still in flight.
+    Cancel {
+        request_id: RequestId,
+    },
+}
+
+impl IoEvent {
+    /// Whether the request opens a page in the main pane.
+    pub fn opens_route(&self) -> bool {
+        matches!(
+            self,
+            IoEvent::GetNameOrAddressInfo { .. }
+                | IoEvent::GetBlock { .. }
+                | IoEvent::GetBlockByHash { .. }
+                | IoEvent::GetTransactionWithReceipt { .. }
+        )
+    }
+}
+
+impl fmt::Display for IoEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IoEvent::GetStatistics => write!(f, "statistics"),
+            IoEvent::GetNameOrAddressInfo {
+                name_or_address: NameOrAddress::Name(name),
+            } => write!(f, "{name}"),
+            IoEvent::GetNameOrAddressInfo {
+                name_or_address: NameOrAddress::Address(address),
+            } => write!(f, "address {address:#x}"),
+            IoEvent::GetBlock { number } => write!(f, "block #{number}"),
+            IoEvent::GetBlockByHash { hash } => write!(f, "block {hash:#x}"),
+            IoEvent::GetTransactionWithReceipt { transaction_hash } => {
+                write!(f, "transaction {transaction_hash:#x}")
+            }
+            IoEvent::GetTransactionReceipts { transactions } => {
+                write!(f, "{} transaction receipts", transactions.len())
+            }
+            IoEvent::GetDecodedInputData { transaction } => {
+                write!(f, "input data of {:#x}", transaction.hash)
+            }
+            IoEvent::GetDecodedLogs {
+                transaction_receipt,
+            } => write!(f, "logs of {:#x}", transaction_receipt.transaction_hash),
+            IoEvent::GetLatestBlocks { .. } => write!(f, "latest blocks"),
+            IoEvent::GetLatestTransactions { .. } => write!(f, "latest transactions"),
+            IoEvent::LookupAddresses { addresses } => {
+                write!(f, "ENS names of {} addresses", addresses.len())
+            }
+            IoEvent::GetAddressTransactions { address, page, .. } => {
+                write!(f, "page {} of the transactions of {address:#x}", page + 1)
+            }
+            IoEvent::GetTokenBalances { address } => {
+                write!(f, "token balances of {address:#x}")
+            }
+            IoEvent::GetStorageAt { address, slot } => {
+                write!(f, "storage slot {slot:#x} of {address:#x}")
+            }
+            IoEvent::InitialSetup { .. } => write!(f, "initial setup"),
+            IoEvent::SubscribeNewHeads { .. } => write!(f, "new heads"),
+            IoEvent::DetectChain => write!(f, "chain ID"),
+            IoEvent::Cancel { request_id } => write!(f, "cancellation of request {request_id}"),
+        }
+    }
+}
+
+/// What the network task found out, sent to the UI task to be applied to `App`.
+pub enum Message {
+    /// The chain the endpoint serves, detected at startup.
+    Chain(Box<ChainContext>),
+    Statistics(Box<Statistics>),
+    /// Opens a page in response to a request, unless the user has left the screen that sent
+    /// it.
+    Route {
+        request_id: RequestId,
+        route_id: Box<RouteId>,
+    },
+    DecodedInputData {
+        transaction_hash: TxHash,
+        decoded_input_data: Option<DecodedCall>,
+    },
+    DecodedLogs {
+        transaction_hash: TxHash,
+        decoded_logs: Vec<Option<DecodedEvent>>,
+    },
+    TransactionReceipts(Vec<TransactionReceipt>),
+    /// Receipts of `transactions` were requested, and those of `failed` couldn't be loaded.
+    FailedReceipts {
+        transactions: Vec<TxHash>,
+        failed: Vec<TxHash>,
+    },
+    /// The latest blocks, newest first. `failed` of them couldn't be loaded.
+    LatestBlocks {
+        blocks: Vec<BlockWithTransactionReceipts<Transaction>>,
+        failed: usize,
+    },
+    LatestTransactions(Vec<TransactionWithReceipt>),
+    /// Blocks that arrived since the latest known one, newest first, along with the
+    /// transactions of the newest. At most `n` of each are kept.
+    NewHeads {
+        blocks: Vec<Block<Transaction>>,
+        transactions: Vec<TransactionWithReceipt>,
+        n: usize,
+    },
+    EnsIds(Vec<(Address, Option<String>)>),
+    AddressTransactions(AddressTransactions),
+    TokenBalances {
+        address: Address,
+        token_balances: Vec<TokenBalance>,
+    },
+    StorageAt {
+        address: Address,
+        slot: H256,
+        value: H256,
+    },
+    ExplorerStatus(ExplorerStatus),
+    BackendStatus {
+        failed_requests: usize,
+        endpoint: Option<String>,
+    },
+    /// A request failed. `request_id` is `None` for requests the user didn't make, such as
+    /// ENS lookups.
+    Failed {
+        request_id: Option<RequestId>,
+        io_event: Box<IoEvent>,
+        endpoint: Option<String>,
+        message: String,
+    },
+    Finished {
+        request_id: RequestId,
+    },
 }
 
 #[derive(Clone)]
-pub struct Network<'a> {
-    pub app: &'a Arc<Mutex<App>>,
-    endpoint: &'a str,
+pub struct Network {
+    messages: Sender<Message>,
+    backend: Arc<dyn RpcBackend>,
+    cache: Arc<Mutex<Cache>>,
+    chain: ChainContext,
+    signatures: Arc<Signatures>,
+    /// The token list of the chain, along with the configured tokens.
+    erc20_tokens: Arc<Vec<ERC20Token>>,
+    /// The newest block sent to the UI, or `None` until the latest blocks have been loaded.
+    latest_block_number: Arc<Mutex<Option<U64>>>,
+    /// How often the latest block number is polled when the endpoint can't push new heads.
+    new_heads_poll_interval: Duration,
+    /// Each `explorer` call makes one request, and they all share the explorer's rate limit.
+    explorer_rate_limiter: Arc<RateLimiter>,
+    /// When the last explorer request failed, unless one has succeeded since.
+    last_explorer_failure: Arc<Mutex<Option<Instant>>>,
 }
 
-impl<'a> Network<'a> {
-    pub fn new(app: &'a Arc<Mutex<App>>, endpoint: &'a str) -> Self {
-        Self { app, endpoint }
+impl Network {
+    pub fn new(
+        messages: Sender<Message>,
+        backend: Arc<dyn RpcBackend>,
+        cache: Arc<Mutex<Cache>>,
+        chain: ChainContext,
+        signatures: Arc<Signatures>,
+        new_heads_poll_interval: Duration,
+    ) -> Self {
+        Self {
+            messages,
+            backend,
+            cache,
+            erc20_tokens: Arc::new(chain.erc20_tokens()),
+            chain,
+            signatures,
+            latest_block_number: Arc::new(Mutex::new(None)),
+            new_heads_poll_interval,
+            explorer_rate_limiter: Arc::new(RateLimiter::new(EXPLORER_REQUESTS_PER_SECOND)),
+            last_explorer_failure: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Sends `message` to the UI. It's dropped if the UI has already quit.
+    async fn send(&self, message: Message) {
+        let _ = self.messages.send(message).await;
+    }
+
+    pub async fn handle_network_event(&mut self, request_id: RequestId, io_event: IoEvent) {
+        if let IoEvent::InitialSetup { n } = io_event {
+            // Each part is reported and retried on its own, so one failing doesn't hold up the
+            // others.
+            let (mut statistics, mut blocks, mut transactions) =
+                (self.clone(), self.clone(), self.clone());
+            join3(
+                statistics.handle_and_report(request_id, IoEvent::GetStatistics),
+                blocks.handle_and_report(request_id, IoEvent::GetLatestBlocks { n }),
+                transactions.handle_and_report(request_id, IoEvent::GetLatestTransactions { n }),
+            )
+            .await;
+        } else {
+            self.handle_and_report(request_id, io_event).await;
+        }
+
+        self.send(Message::Finished { request_id }).await;
+    }
+
+    async fn handle_and_report(&mut self, request_id: RequestId, io_event: IoEvent) {
+        if let Err(e) = self.handle(request_id, io_event.to_owned()).await {
+            self.notify(Some(request_id), io_event, &e).await;
+        }
     }
 
-    pub async fn handle_network_event(&mut self, io_event: IoEvent) -> Result<()> {
+    /// Reports a failed request to the UI.
+    async fn notify(&self, request_id: Option<RequestId>, io_event: IoEvent, e: &anyhow::Error) {
+        warn!("Failed to load {io_event}: {e:#}");
+        self.send(Message::Failed {
+            request_id,
+            io_event: Box::new(io_event),
+            endpoint: self.backend.endpoint(),
+            message: format!("{e:#}"),
+        })
+        .await;
+    }
+
+    async fn handle(&mut self, request_id: RequestId, io_event: IoEvent) -> Result<()> {
         match io_event {
             IoEvent::GetStatistics => {
-                let res = Self::get_statistics(self.endpoint).await;
-                let mut app = self.app.lock().await;
-                if let Ok(statistics) = res {
-                    app.statistics = statistics;
-                }
-                app.is_loading = false;
+                let statistics = self.get_statistics().await?;
+                self.send(Message::Statistics(Box::new(statistics))).await;
                 Ok(())
             }
-            IoEvent::GetNameOrAddressInfo {
-                name_or_address,
-                is_searching,
-            } => {
-                let res = match name_or_address {
-                    NameOrAddress::Name(name) => Self::get_name_info(self.endpoint, &name).await,
-                    NameOrAddress::Address(address) => {
-                        Self::get_address_info(self.endpoint, address).await
-                    }
+            IoEvent::GetNameOrAddressInfo { name_or_address } => {
+                let address_info = match name_or_address {
+                    NameOrAddress::Name(name) => self.get_name_info(&name).await?,
+                    NameOrAddress::Address(address) => self.get_address_info(address).await?,
                 };
-                let mut app = self.app.lock().await;
-                if is_searching {
-                    app.pop_current_route();
-                }
-                app.set_route(Route::new(
-                    RouteId::AddressInfo(if let Ok(some) = res { some } else { None }),
-                    ActiveBlock::Main,
-                ));
-
-                app.is_loading = false;
+                // Transactions and token balances load once their tabs are opened.
+                self.send(Message::Route {
+                    request_id,
+                    route_id: Box::new(RouteId::AddressInfo(address_info)),
+                })
+                .await;
                 Ok(())
             }
             IoEvent::GetBlock { number } => {
-                let res = Self::get_block(self.endpoint, number).await;
-                if let Ok(block) = res {
-                    {
-                        let mut app = self.app.lock().await;
-                        app.pop_current_route();
-                        app.set_route(Route::new(
-                            RouteId::Block(block.to_owned()),
-                            ActiveBlock::Main,
-                        ));
-                    }
-
-                    if let Some(block) = block {
-                        let mut addresses = vec![];
-                        for transaction in block.block.transactions {
-                            addresses.push(transaction.from);
-                            if let Some(to) = transaction.to {
-                                addresses.push(to);
-                            }
-                        }
-
-                        let _ = self.update_app_with_ens_ids(&addresses).await;
-                    }
-                }
-                let mut app = self.app.lock().await;
-                app.is_loading = false;
+                let block = self.get_block(number).await?;
+                self.update_app_with_block(request_id, block).await;
                 Ok(())
             }
             IoEvent::GetBlockByHash { hash } => {
-                let res = Self::get_block(self.endpoint, hash).await;
-                if let Ok(block) = res {
-                    {
-                        let mut app = self.app.lock().await;
-                        app.pop_current_route();
-                        app.set_route(Route::new(
-                            RouteId::Block(block.to_owned()),
-                            ActiveBlock::Main,
-                        ));
-                    }
-
-                    if let Some(block) = block {
-                        let mut addresses = vec![];
-                        for transaction in block.block.transactions {
-                            addresses.push(transaction.from);
-                            if let Some(to) = transaction.to {
-                                addresses.push(to);
-                            }
-                        }
-
-                        let _ = self.update_app_with_ens_ids(&addresses).await;
-                    }
-                }
-                let mut app = self.app.lock().await;
-                app.is_loading = false;
+                let block = self.get_block(hash).await?;
+                self.update_app_with_block(request_id, block).await;
                 Ok(())
             }
             IoEvent::GetDecodedInputData { transaction } => {
-                let res = Self::get_decoded_input_data(transaction).await;
-
-                let mut app = self.app.lock().await;
-                if let Ok(decoded_input_data) = res {
-                    let current_route = app.get_current_route();
-                    match current_route.get_id() {
-                        RouteId::Transaction(transaction)
-                        | RouteId::InputDataOfTransaction(transaction) => {
-                            app.pop_current_route();
-                            let new_transaction =
-                                transaction.map(|transaction| TransactionWithReceipt {
-                                    transaction: transaction.transaction,
-                                    transaction_receipt: transaction.transaction_receipt,
-                                    decoded_input_data,
-                                });
-                            let new_route_id = match current_route.get_id() {
-                                RouteId::Transaction(_) => RouteId::Transaction(new_transaction),
-                                RouteId::InputDataOfTransaction(_) => {
-                                    RouteId::InputDataOfTransaction(new_transaction)
-                                }
-                                _ => unreachable!(),
-                            };
-                            app.set_route(Route::new(
-                                new_route_id,
-                                current_route.get_active_block(),
-                            ));
-                        }
-                        _ => {}
-                    }
-                }
+                let decoded_input_data = self.get_decoded_input_data(&transaction).await;
+                self.send(Message::DecodedInputData {
+                    transaction_hash: transaction.hash,
+                    decoded_input_data,
+                })
+                .await;
                 Ok(())
             }
-            IoEvent::GetTransactionWithReceipt { transaction_hash } => {
-                let res = Self::get_transaction_with_receipt(self.endpoint, transaction_hash).await;
-                let mut app = self.app.lock().await;
-                if let Ok(some) = res {
-                    app.set_route(Route::new(RouteId::Transaction(some), ActiveBlock::Main));
-                }
-                app.is_loading = false;
+            IoEvent::GetDecodedLogs {
+                transaction_receipt,
+            } => {
+                let decoded_logs = self.get_decoded_logs(&transaction_receipt).await;
+                self.send(Message::DecodedLogs {
+                    transaction_hash: transaction_receipt.transaction_hash,
+                    decoded_logs,
+                })
+                .await;
+
+                self.update_app_with_ens_ids(&emitters(&transaction_receipt))
+                    .await;
                 Ok(())
             }
-            IoEvent::GetTransactionReceipts { transactions } => {
-                let splitted_transactions = transactions.chunks(RATE_LIMIT).collect::<Vec<_>>();
-
-                for transactions in splitted_transactions {
-                    let res = Self::get_transaction_receipts(self.endpoint, transactions).await;
-                    let mut app = self.app.lock().await;
-                    if let Ok(receipts) = res {
-                        app.update_block_with_transaction_receipts(receipts);
-                    }
-                }
-                let mut app = self.app.lock().await;
-                app.is_loading = false;
+            IoEvent::GetTransactionWithReceipt { transaction_hash } => {
+                let transaction = self.get_transaction_with_receipt(transaction_hash).await?;
+                let addresses = transaction
+                    .as_ref()
+                    .map(|transaction| emitters(&transaction.transaction_receipt))
+                    .unwrap_or_default();
+
+                self.send(Message::Route {
+                    request_id,
+                    route_id: Box::new(RouteId::Transaction(transaction)),
+                })
+                .await;
+
+                self.update_app_with_ens_ids(&addresses).await;
                 Ok(())
             }
-            IoEvent::InitialSetup { n } => {
-                let (statistics, blocks, transactions) = try_join3(
-                    Self::get_statistics(self.endpoint),
-                    Self::get_latest_blocks(self.endpoint, n),
-                    Self::get_latest_transactions(self.endpoint, n),
-                )
-                .await?;
-                let mut addresses = vec![];
-                for transaction in &transactions {
-                    addresses.push(transaction.transaction.from);
-                    if let Some(to) = transaction.transaction.to {
-                        addresses.push(to);
-                    }
-                }
-
-                {
-                    let mut app = self.app.lock().await;
-                    app.statistics = statistics;
-
-                    app.latest_blocks = Some(StatefulList::with_items(blocks));
-                    app.latest_transactions = Some(StatefulList::with_items(transactions));
-                }
-
-                let _ = self.update_app_with_ens_ids(&addresses).await;
-
-                let mut app = self.app.lock().await;
-                app.is_loading = false;
+            IoEvent::GetTransactionReceipts { transactions } => {
+                let receipts = self.get_transaction_receipts(&transactions).await?;
+                self.send(Message::TransactionReceipts(receipts)).await;
                 Ok(())
             }
             IoEvent::GetLatestBlocks { n } => {
-                let blocks = Self::get_latest_blocks(self.endpoint, n).await?;
-                let mut app = self.app.lock().await;
-                app.latest_blocks = Some(StatefulList::with_items(blocks));
-                app.is_loading = false;
+                let (blocks, failed) = self.get_latest_blocks(n).await?;
+                *self.latest_block_number.lock().await = Some(
+                    blocks
+                        .iter()
+                        .filter_map(|block| block.block.number)
+                        .max()
+                        .unwrap_or_default(),
+                );
+                self.send(Message::LatestBlocks { blocks, failed }).await;
                 Ok(())
             }
             IoEvent::GetLatestTransactions { n } => {
-                let transactions = Self::get_latest_transactions(self.endpoint, n).await?;
+                let transactions = self.get_latest_transactions(n).await?;
 
                 let mut addresses = vec![];
                 for transaction in &transactions {
@@ -263,192 +442,725 @@ impl<'a> Network<'a> {
                     }
                 }
 
-                {
-                    let mut app = self.app.lock().await;
-                    app.latest_transactions = Some(StatefulList::with_items(transactions));
-                }
-
-                let _ = self.update_app_with_ens_ids(&addresses).await;
+                self.send(Message::LatestTransactions(transactions)).await;
 
-                let mut app = self.app.lock().await;
-                app.is_loading = false;
+                self.update_app_with_ens_ids(&addresses).await;
+                Ok(())
+            }
+            IoEvent::SubscribeNewHeads { n } => {
+                let mut network = self.clone();
+                tokio::spawn(async move {
+                    network.watch_new_heads(n).await;
+                });
                 Ok(())
             }
             IoEvent::LookupAddresses { addresses } => {
-                let _ = self.update_app_with_ens_ids(&addresses).await;
-                let mut app = self.app.lock().await;
-                app.is_loading = false;
+                self.update_app_with_ens_ids(&addresses).await;
                 Ok(())
             }
+            IoEvent::GetAddressTransactions {
+                address,
+                page,
+                until,
+            } => {
+                self.update_app_with_address_transactions(address, page, until)
+                    .await
+            }
+            IoEvent::GetTokenBalances { address } => {
+                self.update_app_with_token_balances(address).await
+            }
+            IoEvent::GetStorageAt { address, slot } => {
+                let value = self.backend.get_storage_at(address, slot, None).await?;
+                self.send(Message::StorageAt {
+                    address,
+                    slot,
+                    value,
+                })
+                .await;
+                Ok(())
+            }
+            IoEvent::InitialSetup { .. } | IoEvent::DetectChain | IoEvent::Cancel { .. } => {
+                unreachable!("handled by handle_network_event and start_network")
+            }
+        }
+    }
+
+    async fn update_app_with_block(
+        &mut self,
+        request_id: RequestId,
+        block: Option<BlockWithTransactionReceipts<Transaction>>,
+    ) {
+        // The block is cached, so it loads instantly if the user comes back to it.
+        self.send(Message::Route {
+            request_id,
+            route_id: Box::new(RouteId::Block(block.to_owned())),
+        })
+        .await;
+
+        if let Some(block) = block {
+            let mut addresses = vec![];
+            for transaction in block.block.transactions {
+                addresses.push(transaction.from);
+                if let Some(to) = transaction.to {
+                    addresses.push(to);
+                }
+            }
+
+            self.update_app_with_ens_ids(&addresses).await;
         }
     }
 
     async fn get_block<T: Into<BlockId> + Send + Sync>(
-        endpoint: &'a str,
+        &self,
         block_hash_or_number: T,
-    ) -> Result<Option<BlockWithTransactionReceipts<Transaction>>, Box<dyn Error>> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
-        let block = provider.get_block_with_txs(block_hash_or_number).await?;
-        let query = if let Some(block) = block.as_ref() {
-            block
-                .transactions
-                .iter()
-                .map(|tx| provider.get_transaction_receipt(tx.hash))
-                .collect()
-        } else {
-            vec![]
-        };
-        let transaction_receipts = join_all(query).await;
-        let transaction_receipts = transaction_receipts
-            .iter()
-            .filter_map(|receipt| receipt.as_ref().ok().and_then(|receipt| receipt.clone()))
-            .collect::<Vec<_>>();
+    ) -> Result<Option<BlockWithTransactionReceipts<Transaction>>> {
+        let block_id = block_hash_or_number.into();
+        if let Some(block) = self.cache.lock().await.get_block(block_id) {
+            return Ok(Some(block));
+        }
+
+        let block = self.backend.get_block_with_txs(block_id).await?;
 
         if let Some(block) = block {
-            Ok(Some(BlockWithTransactionReceipts {
+            let transaction_receipts = self.get_transaction_receipts(&block.transactions).await?;
+            let block = BlockWithTransactionReceipts {
                 block,
                 transaction_receipts: Some(transaction_receipts),
-            }))
+            };
+            self.cache.lock().await.put_block(&block);
+            Ok(Some(block))
         } else {
             Ok(None)
         }
     }
 
-    async fn get_name_info(
-        endpoint: &'a str,
-        ens_id: &str,
-    ) -> Result<Option<AddressInfo>, Box<dyn Error>> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
-        let address = provider.resolve_name(ens_id).await?;
+    async fn get_name_info(&self, ens_id: &str) -> Result<Option<AddressInfo>> {
+        let address = self.backend.resolve_name(ens_id).await?;
 
-        let avatar_url = provider.resolve_avatar(ens_id).await.ok();
-        let balance = provider.get_balance(address, None).await?;
+        let avatar_url = self.backend.resolve_avatar(ens_id).await.ok();
+        let (balance, code) = try_join(
+            self.backend.get_balance(address, None),
+            self.backend.get_code(address, None),
+        )
+        .await?;
+        let proxy = self.update_proxy(address, &code).await;
 
         Ok(Some(AddressInfo {
             address,
             balance,
+            code,
+            proxy,
             avatar_url,
             contract_abi: None,
             contract_source_code: None,
             ens_id: Some(ens_id.to_owned()),
+            transactions: None,
+            token_balances: None,
+            storage_slots: vec![],
         }))
     }
 
-    async fn get_address_info(
-        endpoint: &'a str,
-        address: Address,
-    ) -> Result<Option<AddressInfo>, Box<dyn Error>> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
-        let ens_id = provider.lookup_address(address).await.ok();
+    async fn get_address_info(&self, address: Address) -> Result<Option<AddressInfo>> {
+        let ens_id = self.backend.lookup_address(address).await.ok();
 
         let avatar_url = if let Some(ens_id) = ens_id.as_ref() {
-            provider.resolve_avatar(ens_id).await.ok()
+            self.backend.resolve_avatar(ens_id).await.ok()
         } else {
             None
         };
 
-        let (contract_source_code, contract_abi) =
-            if let Ok(client) = Client::new_from_env(Chain::Mainnet) {
-                try_join(
-                    client.contract_source_code(address),
-                    client.contract_abi(address),
-                )
-                .await
-                .map_or((None, None), |res| (Some(res.0), Some(res.1)))
-            } else {
-                (None, None)
-            };
+        let (balance, code) = try_join(
+            self.backend.get_balance(address, None),
+            self.backend.get_code(address, None),
+        )
+        .await?;
+        let proxy = self.update_proxy(address, &code).await;
 
-        let balance = provider.get_balance(address, None).await?;
+        // A proxy's own ABI only has its upgrade functions, so its implementation's is shown if
+        // it's verified.
+        let mut verified_contract = (None, None);
+        if let Some(proxy) = proxy.as_ref() {
+            verified_contract = self.get_verified_contract(proxy.implementation).await;
+        }
+        if verified_contract.1.is_none() {
+            verified_contract = self.get_verified_contract(address).await;
+        }
+        let (contract_source_code, contract_abi) = verified_contract;
 
         Ok(Some(AddressInfo {
             address,
             balance,
+            code,
+            proxy,
             avatar_url,
             contract_abi,
             contract_source_code,
             ens_id,
+            transactions: None,
+            token_balances: None,
+            storage_slots: vec![],
         }))
     }
 
-    async fn get_decoded_input_data(transaction: Transaction) -> Result<Option<String>> {
-        let decoded_input_data = if let Ok(client) = Client::new_from_env(Chain::Mainnet) {
-            if let Some(to) = transaction.to {
-                let abi = client.contract_abi(to).await?;
+    /// The source code and ABI of `address`, if it's verified. Explorer failures show up in the
+    /// explorer status rather than failing the whole page.
+    async fn get_verified_contract(
+        &self,
+        address: Address,
+    ) -> (Option<ContractMetadata>, Option<ContractAbi>) {
+        let contract_source_code = self.get_contract_source_code(address).await.ok().flatten();
+        let contract_abi = self.cache.lock().await.get_abi(address).flatten();
+        (contract_source_code, contract_abi)
+    }
 
-                let s = serde_json::to_string(&abi)?;
+    /// Detects whether the contract at `address` with `code` is a proxy, caching the answer for
+    /// decoding. Failures are only logged, as the page is still useful without knowing.
+    async fn update_proxy(&self, address: Address, code: &Bytes) -> Option<Proxy> {
+        match self.detect_proxy(address, code).await {
+            Ok(proxy) => {
+                self.cache.lock().await.put_proxy(address, proxy.to_owned());
+                proxy
+            }
+            Err(e) => {
+                warn!("Failed to detect whether {address:#x} is a proxy: {e:#}");
+                None
+            }
+        }
+    }
 
-                let dir = tempdir()?;
-                let file_path = dir.path().join("lazy-etherscan.tmp.abi.json");
-                let mut file = File::create(&file_path)?;
-                writeln!(file, "{}", s)?;
+    async fn get_proxy(&self, address: Address) -> Result<Option<Proxy>> {
+        if let Some(proxy) = self.cache.lock().await.get_proxy(address) {
+            return Ok(proxy);
+        }
 
-                let output = Command::new("ethereum-input-data-decoder")
-                    .args([
-                        "--abi",
-                        file_path.to_str().unwrap(),
-                        &transaction.input.to_string(),
-                    ])
-                    .output()
-                    .map_or(None, |output| String::from_utf8(output.stdout).ok());
+        let code = self.backend.get_code(address, None).await?;
+        let proxy = self.detect_proxy(address, &code).await?;
+        self.cache.lock().await.put_proxy(address, proxy.to_owned());
+        Ok(proxy)
+    }
 
-                drop(file);
-                dir.close()?;
+    /// Finds the implementation of a proxy, from its bytecode if it's a minimal proxy and from
+    /// the storage slots where the common proxy standards keep it otherwise.
+    async fn detect_proxy(&self, address: Address, code: &Bytes) -> Result<Option<Proxy>> {
+        if code.is_empty() {
+            return Ok(None);
+        }
+        if let Some(implementation) = bytecode::minimal_proxy_implementation(code) {
+            return Ok(Some(Proxy {
+                kind: ProxyKind::Eip1167,
+                implementation,
+                admin: None,
+                beacon: None,
+            }));
+        }
 
-                output
-            } else {
+        let (implementation, admin, beacon, proxiable, old_implementation, old_admin) = try_join!(
+            self.get_address_at(address, storage::eip1967_implementation_slot()),
+            self.get_address_at(address, storage::eip1967_admin_slot()),
+            self.get_address_at(address, storage::eip1967_beacon_slot()),
+            self.get_address_at(address, storage::eip1822_slot()),
+            self.get_address_at(address, storage::zeppelinos_implementation_slot()),
+            self.get_address_at(address, storage::zeppelinos_admin_slot()),
+        )?;
+
+        let proxy = if let Some(beacon) = beacon {
+            let output = self
+                .backend
+                .call(beacon, id("implementation()").to_vec().into())
+                .await?;
+            let implementation = decode(&[ParamType::Address], &output)
+                .ok()
+                .and_then(|mut tokens| tokens.pop()?.into_address());
+            implementation.map(|implementation| Proxy {
+                kind: ProxyKind::Beacon,
+                implementation,
+                admin,
+                beacon: Some(beacon),
+            })
+        } else if let Some(implementation) = implementation {
+            Some(Proxy {
+                kind: if admin.is_some() {
+                    ProxyKind::Transparent
+                } else {
+                    ProxyKind::Eip1967
+                },
+                implementation,
+                admin,
+                beacon: None,
+            })
+        } else if let Some(implementation) = old_implementation {
+            Some(Proxy {
+                kind: ProxyKind::Transparent,
+                implementation,
+                admin: old_admin,
+                beacon: None,
+            })
+        } else {
+            proxiable.map(|implementation| Proxy {
+                kind: ProxyKind::Eip1822,
+                implementation,
+                admin: None,
+                beacon: None,
+            })
+        };
+        Ok(proxy)
+    }
+
+    /// The address stored on its own in `slot` of `address`, or `None` if the slot is empty.
+    async fn get_address_at(&self, address: Address, slot: H256) -> Result<Option<Address>> {
+        let value = self.backend.get_storage_at(address, slot, None).await?;
+        Ok((!value.is_zero()).then(|| storage::as_address(&value)))
+    }
+
+    /// The ABIs to decode the calls to and logs of `address` with: its own if `decodes` it,
+    /// and otherwise the implementation's first if it's a proxy, and then its own. ABIs that
+    /// fail to load are left out, so that decoding falls back to the signature database.
+    async fn get_decoding_abis<F>(&self, address: Address, decodes: F) -> Vec<ContractAbi>
+    where
+        F: Fn(&ContractAbi) -> bool,
+    {
+        let abi = match self.get_contract_abi(address).await {
+            Ok(abi) => abi,
+            Err(e) => {
+                warn!("Failed to load the ABI of {address:#x}: {e:#}");
                 None
             }
+        };
+        // Detecting a proxy takes several requests, which aren't needed if its own ABI will do.
+        if let Some(abi) = abi.as_ref().filter(|abi| decodes(abi)) {
+            return vec![abi.to_owned()];
+        }
+
+        // Not knowing whether it's a proxy only makes for less decoding.
+        let implementation = match self.get_proxy(address).await {
+            Ok(proxy) => proxy.map(|proxy| proxy.implementation),
+            Err(e) => {
+                warn!("Failed to detect whether {address:#x} is a proxy: {e:#}");
+                None
+            }
+        };
+        let mut abis = vec![];
+        if let Some(implementation) = implementation {
+            match self.get_contract_abi(implementation).await {
+                Ok(Some(abi)) => abis.push(abi),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to load the ABI of {implementation:#x}: {e:#}"),
+            }
+        }
+        abis.extend(abi);
+        abis
+    }
+
+    async fn update_app_with_address_transactions(
+        &self,
+        address: Address,
+        page: usize,
+        until: Option<U64>,
+    ) -> Result<()> {
+        let transactions = self.get_address_transactions(address, page, until).await?;
+
+        let mut addresses = vec![];
+        for transaction in &transactions.transactions {
+            addresses.push(transaction.transaction.from);
+            if let Some(to) = transaction.transaction.to {
+                addresses.push(to);
+            }
+        }
+
+        self.send(Message::AddressTransactions(transactions)).await;
+        self.update_app_with_ens_ids(&addresses).await;
+        Ok(())
+    }
+
+    /// A page of the transactions sent from or to `address`, listed by the explorer if there is
+    /// one, and otherwise found by scanning blocks.
+    async fn get_address_transactions(
+        &self,
+        address: Address,
+        page: usize,
+        until: Option<U64>,
+    ) -> Result<AddressTransactions> {
+        let until = match until {
+            Some(until) => until,
+            None => self.backend.get_block_number().await?,
+        };
+
+        let listed = self
+            .explorer(|client| async move {
+                let params = TxListParams::new(
+                    0,
+                    until.as_u64(),
+                    page as u64 + 1,
+                    ADDRESS_TRANSACTIONS_PAGE_SIZE as u64,
+                    Sort::Desc,
+                );
+                match client.get_transactions(&address, Some(params)).await {
+                    Ok(transactions) => Ok(transactions
+                        .into_iter()
+                        .filter_map(from_normal_transaction)
+                        .collect::<Vec<_>>()),
+                    // Etherscan reports an empty page as an error.
+                    Err(EtherscanError::ErrorResponse { message, .. })
+                        if message.starts_with("No transactions found") =>
+                    {
+                        Ok(vec![])
+                    }
+                    Err(e) => Err(e.into()),
+                }
+            })
+            .await?;
+
+        let (source, transactions) = if let Some(transactions) = listed {
+            (
+                TransactionHistorySource::Explorer {
+                    page_size: ADDRESS_TRANSACTIONS_PAGE_SIZE,
+                },
+                transactions,
+            )
         } else {
-            None
+            let to = until.saturating_sub(U64::from(page as u64 * ADDRESS_SCAN_BLOCKS));
+            let from = to.saturating_sub(U64::from(ADDRESS_SCAN_BLOCKS - 1));
+            let transactions = self
+                .scan_blocks(from, to, |transaction| {
+                    transaction.from == address || transaction.to == Some(address)
+                })
+                .await?;
+            (
+                TransactionHistorySource::BlockScan { from, to },
+                self.get_transactions_with_receipts(transactions).await?,
+            )
+        };
+
+        Ok(AddressTransactions {
+            address,
+            page,
+            until,
+            source,
+            transactions,
+        })
+    }
+
+    /// The transactions in blocks `from..=to` that match `filter`, newest first. Blocks that
+    /// fail to load are skipped, unless they all do.
+    async fn scan_blocks<F>(&self, from: U64, to: U64, filter: F) -> Result<Vec<Transaction>>
+    where
+        F: Fn(&Transaction) -> bool,
+    {
+        let results = join_all(
+            (from.as_u64()..=to.as_u64())
+                .rev()
+                .map(|number| self.backend.get_block_with_txs(U64::from(number).into())),
+        )
+        .await;
+
+        let mut transactions = vec![];
+        let mut last_error = None;
+        let mut failed = 0;
+        for (number, res) in (from.as_u64()..=to.as_u64()).rev().zip(results) {
+            match res {
+                Ok(block) => transactions.extend(
+                    block
+                        .into_iter()
+                        .flat_map(|block| block.transactions)
+                        .filter(|transaction| filter(transaction)),
+                ),
+                Err(e) => {
+                    warn!("Failed to scan block #{number}: {e:#}");
+                    failed += 1;
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) if failed as u64 > to.as_u64() - from.as_u64() => Err(e),
+            _ => Ok(transactions),
+        }
+    }
+
+    async fn update_app_with_token_balances(&self, address: Address) -> Result<()> {
+        let token_balances = self.get_token_balances(address).await?;
+        self.send(Message::TokenBalances {
+            address,
+            token_balances,
+        })
+        .await;
+        Ok(())
+    }
+
+    /// Balances of `address` in each known token, by the number of tokens held, most first.
+    /// Tokens it doesn't hold are left out.
+    async fn get_token_balances(&self, address: Address) -> Result<Vec<TokenBalance>> {
+        let calls = self
+            .erc20_tokens
+            .iter()
+            .map(|token| (token.contract_address, balance_of(address)))
+            .collect::<Vec<_>>();
+        let held = self
+            .erc20_tokens
+            .iter()
+            .zip(self.call_all(&calls).await?)
+            .filter_map(|(token, output)| {
+                let balance = decode_uint(&output?)?;
+                (!balance.is_zero()).then(|| (token.to_owned(), balance))
+            })
+            .collect::<Vec<_>>();
+
+        let unknown_decimals = {
+            let cache = self.cache.lock().await;
+            held.iter()
+                .map(|(token, _)| token.contract_address)
+                .filter(|address| cache.get_decimals(*address).is_none())
+                .collect::<Vec<_>>()
+        };
+        let calls = unknown_decimals
+            .iter()
+            .map(|address| (*address, Bytes::from(id("decimals()").to_vec())))
+            .collect::<Vec<_>>();
+        let outputs = self.call_all(&calls).await?;
+
+        let mut cache = self.cache.lock().await;
+        for (address, output) in unknown_decimals.into_iter().zip(outputs) {
+            if let Some(decimals) = output
+                .and_then(|output| decode_uint(&output))
+                .filter(|decimals| *decimals <= U256::from(u8::MAX))
+            {
+                cache.put_decimals(address, decimals.as_u32() as u8);
+            }
+        }
+        let mut token_balances = held
+            .into_iter()
+            .map(|(token, balance)| TokenBalance {
+                decimals: cache.get_decimals(token.contract_address),
+                token,
+                balance,
+            })
+            .collect::<Vec<_>>();
+        token_balances.sort_by(|a, b| {
+            let amount = |token_balance: &TokenBalance| {
+                token_balance.amount().parse::<f64>().unwrap_or_default()
+            };
+            amount(b).total_cmp(&amount(a))
+        });
+        Ok(token_balances)
+    }
+
+    /// Makes `calls` in batches if the endpoint supports them, and otherwise one at a time,
+    /// `MAX_CONCURRENT_CALLS` at once so that other requests aren't queued behind them. Calls
+    /// that fail come back as `None`.
+    async fn call_all(&self, calls: &[(Address, Bytes)]) -> Result<Vec<Option<Bytes>>> {
+        if calls.is_empty() {
+            return Ok(vec![]);
+        }
+        match self.backend.call_batch(calls).await {
+            Err(e) if is_unsupported(&e) => {
+                let requests = calls
+                    .iter()
+                    .map(|(to, data)| self.backend.call(*to, data.to_owned()))
+                    .collect::<Vec<_>>();
+                Ok(stream::iter(requests)
+                    .buffered(MAX_CONCURRENT_CALLS)
+                    .map(Result::ok)
+                    .collect()
+                    .await)
+            }
+            res => res,
+        }
+    }
+
+    /// Sends `request` to the explorer once the rate limit allows, and keeps
+    /// `App::explorer_status` up to date. Returns `None` if explorer features are disabled.
+    async fn explorer<T, F, Fut>(&self, request: F) -> Result<Option<T>>
+    where
+        F: FnOnce(Client) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let started_at = Instant::now();
+        let res = match self.chain.etherscan_client() {
+            Ok(Some(client)) => {
+                self.explorer_rate_limiter.wait().await;
+                request(client).await.map(Some)
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        };
+
+        let status = {
+            let mut last_explorer_failure = self.last_explorer_failure.lock().await;
+            match res.as_ref() {
+                // A request that was already in flight when another one failed doesn't show that
+                // the explorer has recovered.
+                Ok(Some(_))
+                    if last_explorer_failure.is_some_and(|failed_at| failed_at > started_at) =>
+                {
+                    None
+                }
+                Ok(Some(_)) => {
+                    *last_explorer_failure = None;
+                    Some(ExplorerStatus::Enabled)
+                }
+                Ok(None) => Some(ExplorerStatus::Disabled),
+                Err(e) => {
+                    *last_explorer_failure = Some(Instant::now());
+                    Some(ExplorerStatus::Failing(format!("{e:#}")))
+                }
+            }
         };
-        Ok(decoded_input_data)
+        if let Some(status) = status {
+            self.send(Message::ExplorerStatus(status)).await;
+        }
+
+        res
+    }
+
+    /// The verified ABI of the contract at `address`, or `None` if it isn't verified or explorer
+    /// features are disabled.
+    async fn get_contract_abi(&self, address: Address) -> Result<Option<ContractAbi>> {
+        if let Some(abi) = self.cache.lock().await.get_abi(address) {
+            return Ok(abi);
+        }
+
+        self.get_contract_source_code(address).await?;
+        Ok(self.cache.lock().await.get_abi(address).flatten())
+    }
+
+    /// The verified source code of the contract at `address`, or `None` if it isn't verified or
+    /// explorer features are disabled. Its ABI is cached for decoding. It's taken from here
+    /// rather than from `getabi`, which ethers only returns parsed, without the names of tuple
+    /// components.
+    async fn get_contract_source_code(&self, address: Address) -> Result<Option<ContractMetadata>> {
+        let Some(contract_source_code) = self
+            .explorer(|client| async move {
+                match client.contract_source_code(address).await {
+                    Ok(contract_source_code) => Ok(Some(contract_source_code)),
+                    Err(EtherscanError::ContractCodeNotVerified(_)) => Ok(None),
+                    Err(e) => Err(e.into()),
+                }
+            })
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let abi = contract_source_code
+            .as_ref()
+            .and_then(|contract_source_code| {
+                let raw_abi = contract_source_code.items.first()?.raw_abi().ok()?;
+                ContractAbi::try_from(raw_abi).ok()
+            });
+        let mut cache = self.cache.lock().await;
+        // An ABI placed in the cache directory is kept for contracts that aren't verified.
+        if abi.is_some() || cache.get_abi(address).is_none() {
+            cache.put_abi(address, abi);
+        }
+        Ok(contract_source_code)
+    }
+
+    async fn get_decoded_input_data(&self, transaction: &Transaction) -> Option<DecodedCall> {
+        let to = transaction.to?;
+        let abis = self
+            .get_decoding_abis(to, |abi| {
+                abi::decode_input(abi, &transaction.input).is_some()
+            })
+            .await;
+        if let Some(decoded) = abis
+            .iter()
+            .find_map(|abi| abi::decode_input(abi, &transaction.input))
+        {
+            return Some(decoded);
+        }
+
+        self.signatures.decode_input(&transaction.input)
+    }
+
+    /// Decodes each log of `transaction_receipt` with the ABI of the contract that emitted it,
+    /// or with the event signatures if it isn't verified or its ABI fails to load.
+    async fn get_decoded_logs(
+        &self,
+        transaction_receipt: &TransactionReceipt,
+    ) -> Vec<Option<DecodedEvent>> {
+        let emitters = emitters(transaction_receipt);
+        let abis = join_all(emitters.iter().map(|address| {
+            self.get_decoding_abis(*address, |abi| {
+                transaction_receipt
+                    .logs
+                    .iter()
+                    .filter(|log| log.address == *address)
+                    .all(|log| abi::decode_log(abi, log).is_some())
+            })
+        }))
+        .await;
+
+        transaction_receipt
+            .logs
+            .iter()
+            .map(|log| {
+                let i = emitters
+                    .iter()
+                    .position(|address| *address == log.address)?;
+                abis[i]
+                    .iter()
+                    .find_map(|abi| abi::decode_log(abi, log))
+                    .or_else(|| self.signatures.decode_log(log))
+            })
+            .collect()
     }
 
     async fn get_transaction_with_receipt(
-        endpoint: &'a str,
+        &self,
         transaction_hash: TxHash,
     ) -> Result<Option<TransactionWithReceipt>> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
-        let transaction = provider.get_transaction(transaction_hash).await?;
-        let transaction_receipt = provider.get_transaction_receipt(transaction_hash).await?;
+        let (cached_transaction, cached_transaction_receipt) = {
+            let mut cache = self.cache.lock().await;
+            (
+                // Transactions that aren't finalized may since have been reorged into another
+                // block.
+                cache
+                    .get_transaction(transaction_hash)
+                    .filter(|transaction| cache.is_finalized(transaction.block_number)),
+                cache.get_receipt(transaction_hash),
+            )
+        };
+        let transaction = match cached_transaction {
+            Some(transaction) => Some(transaction),
+            None => self.backend.get_transaction(transaction_hash).await?,
+        };
+        // A cached receipt from another block means the transaction was reorged.
+        let cached_transaction_receipt = cached_transaction_receipt.filter(|receipt| {
+            transaction
+                .as_ref()
+                .is_some_and(|transaction| receipt.block_hash == transaction.block_hash)
+        });
+        let transaction_receipt = match cached_transaction_receipt {
+            Some(transaction_receipt) => Some(transaction_receipt),
+            None => {
+                self.backend
+                    .get_transaction_receipt(transaction_hash)
+                    .await?
+            }
+        };
+        {
+            let mut cache = self.cache.lock().await;
+            if let Some(transaction) = transaction.as_ref() {
+                cache.put_transaction(transaction);
+            }
+            if let Some(transaction_receipt) = transaction_receipt.as_ref() {
+                cache.put_receipt(transaction_receipt);
+            }
+        }
         if let Some(transaction) = transaction {
             if let Some(transaction_receipt) = transaction_receipt {
-                let decoded_input_data = if let Ok(client) = Client::new_from_env(Chain::Mainnet) {
-                    if let Some(to) = transaction.to {
-                        let abi = client.contract_abi(to).await?;
-
-                        let s = serde_json::to_string(&abi)?;
-
-                        let dir = tempdir()?;
-                        let file_path = dir.path().join("lazy-etherscan.tmp.abi.json");
-                        let mut file = File::create(&file_path)?;
-                        writeln!(file, "{}", s)?;
-
-                        let output = Command::new("ethereum-input-data-decoder")
-                            .args([
-                                "--abi",
-                                file_path.to_str().unwrap(),
-                                &transaction.input.to_string(),
-                            ])
-                            .output()
-                            .map_or(None, |output| String::from_utf8(output.stdout).ok());
-
-                        drop(file);
-                        dir.close()?;
-
-                        output
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
+                // Decoding is best-effort, so the page opens even if the explorer fails.
+                let (decoded_input_data, decoded_logs) = join(
+                    self.get_decoded_input_data(&transaction),
+                    self.get_decoded_logs(&transaction_receipt),
+                )
+                .await;
 
                 Ok(Some(TransactionWithReceipt {
                     transaction,
                     transaction_receipt,
                     decoded_input_data,
+                    decoded_logs,
                 }))
             } else {
                 Ok(None)
@@ -458,175 +1170,957 @@ impl<'a> Network<'a> {
         }
     }
 
+    /// The latest `n` blocks, or all of them on a shorter chain, along with how many of them
+    /// couldn't be loaded.
     async fn get_latest_blocks(
-        endpoint: &'a str,
+        &self,
         n: usize,
-    ) -> Result<Vec<BlockWithTransactionReceipts<Transaction>>> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
-        let block_number = provider.get_block_number().await?;
-
-        let mut blocks = vec![];
-        for i in 0..n {
-            let block = provider.get_block_with_txs(block_number - i);
-            blocks.push(block);
-        }
+    ) -> Result<(Vec<BlockWithTransactionReceipts<Transaction>>, usize)> {
+        let block_number = self.backend.get_block_number().await?;
 
-        let blocks = join_all(blocks).await;
+        let query = (0..=block_number.as_u64())
+            .rev()
+            .take(n)
+            .map(|number| self.backend.get_block_with_txs(U64::from(number).into()))
+            .collect::<Vec<_>>();
 
         let mut latest_blocks = vec![];
-        for block in blocks.into_iter().flatten().flatten() {
-            latest_blocks.push(BlockWithTransactionReceipts {
-                block,
-                transaction_receipts: None,
-            });
+        let mut failed = 0;
+        for block in join_all(query).await {
+            match block {
+                Ok(Some(block)) => latest_blocks.push(BlockWithTransactionReceipts {
+                    block,
+                    transaction_receipts: None,
+                }),
+                // Load-balanced endpoints may not have caught up with the block number yet.
+                Ok(None) | Err(_) => failed += 1,
+            }
         }
-        Ok(latest_blocks)
+        Ok((latest_blocks, failed))
     }
 
     async fn get_transaction_receipts(
-        endpoint: &'a str,
+        &self,
         transactions: &[Transaction],
     ) -> Result<Vec<TransactionReceipt>> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
-        let query = transactions
-            .iter()
-            .map(|tx| provider.get_transaction_receipt(tx.hash))
-            .collect::<Vec<_>>();
-        let res = join_all(query).await;
         let mut transaction_receips = vec![];
+        let mut missing_transactions = vec![];
+        {
+            let mut cache = self.cache.lock().await;
+            for tx in transactions {
+                // A cached receipt from another block means the transaction was reorged.
+                match cache
+                    .get_receipt(tx.hash)
+                    .filter(|receipt| receipt.block_hash == tx.block_hash)
+                {
+                    Some(receipt) => transaction_receips.push(receipt),
+                    None => missing_transactions.push(tx),
+                }
+            }
+        }
 
-        for receipt in res.into_iter().flatten().flatten() {
-            transaction_receips.push(receipt);
+        let res = self.fetch_transaction_receipts(&missing_transactions).await;
+
+        let mut failed_transactions = vec![];
+        {
+            let mut cache = self.cache.lock().await;
+            for (tx, receipt) in missing_transactions.iter().zip(res) {
+                match receipt {
+                    Ok(Some(receipt)) => {
+                        cache.put_receipt(&receipt);
+                        transaction_receips.push(receipt);
+                    }
+                    Ok(None) => {}
+                    Err(_) => failed_transactions.push(tx.hash),
+                }
+            }
         }
 
+        self.send(Message::FailedReceipts {
+            transactions: transactions.iter().map(|tx| tx.hash).collect(),
+            failed: failed_transactions,
+        })
+        .await;
+
         Ok(transaction_receips)
     }
 
-    async fn get_latest_transactions(
-        endpoint: &'a str,
-        n: usize,
-    ) -> Result<Vec<TransactionWithReceipt>> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
+    /// Fetches receipts with the cheapest method the endpoint supports: `eth_getBlockReceipts`
+    /// if all transactions are in one block, then JSON-RPC batches, then one request per
+    /// transaction. The backend remembers which methods each endpoint supports, so unsupported
+    /// ones fail right away. The result is in the order of `transactions`.
+    async fn fetch_transaction_receipts(
+        &self,
+        transactions: &[&Transaction],
+    ) -> Vec<Result<Option<TransactionReceipt>>> {
+        if transactions.is_empty() {
+            return vec![];
+        }
 
-        let block = provider.get_block(BlockNumber::Latest).await?;
+        let block_hash = transactions[0].block_hash;
+        let is_same_block = transactions.iter().all(|tx| tx.block_hash == block_hash);
+        if let (Some(block_hash), true) = (block_hash, is_same_block) {
+            if let Ok(receipts) = self.backend.get_block_receipts(block_hash.into()).await {
+                return transactions
+                    .iter()
+                    .map(|tx| {
+                        Ok(receipts
+                            .iter()
+                            .find(|receipt| receipt.transaction_hash == tx.hash)
+                            .cloned())
+                    })
+                    .collect();
+            }
+        }
 
-        let transaction_futures = if let Some(block) = block {
-            block
-                .transactions
-                .iter()
-                .take(n)
-                .map(|&tx| provider.get_transaction(tx))
-                .collect::<Vec<_>>()
-        } else {
-            vec![]
-        };
+        let transaction_hashes = transactions.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+        if let Ok(receipts) = self
+            .backend
+            .get_transaction_receipts_batch(&transaction_hashes)
+            .await
+        {
+            return receipts.into_iter().map(Ok).collect();
+        }
 
-        let transactions = join_all(transaction_futures).await;
-        let transactions = transactions
+        let query = transactions
             .iter()
-            .filter_map(|tx| tx.as_ref().ok().and_then(|tx| tx.clone()))
+            .map(|tx| self.backend.get_transaction_receipt(tx.hash))
             .collect::<Vec<_>>();
+        join_all(query).await
+    }
 
-        let receipt_futures = transactions
-            .iter()
-            .map(|tx| provider.get_transaction_receipt(tx.hash))
-            .collect::<Vec<_>>();
-        let receipts = join_all(receipt_futures).await;
-        let receipts = receipts
-            .iter()
-            .filter_map(|tx| tx.as_ref().ok().and_then(|receipt| receipt.clone()))
-            .collect::<Vec<_>>();
+    async fn get_latest_transactions(&self, n: usize) -> Result<Vec<TransactionWithReceipt>> {
+        let block = self
+            .backend
+            .get_block_with_txs(BlockNumber::Latest.into())
+            .await?;
 
-        let mut result = vec![];
-        for i in 0..receipts.len() {
-            result.push(TransactionWithReceipt {
-                transaction: transactions[i].to_owned(),
-                transaction_receipt: receipts[i].to_owned(),
-                decoded_input_data: None,
-            });
-        }
+        let transactions = block.map_or(vec![], |block| {
+            block.transactions.into_iter().take(n).collect::<Vec<_>>()
+        });
 
-        Ok(result)
+        self.get_transactions_with_receipts(transactions).await
     }
 
-    async fn get_statistics(endpoint: &'a str) -> Result<Statistics> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
-
-        let mut ethusd = None;
-        let mut node_count = None;
-        let mut suggested_base_fee = None;
-        let mut med_gas_price = None;
-        if let Ok(client) = Client::new_from_env(Chain::Mainnet) {
-            let (eth_price, total_node_count, gas_oracle) =
-                try_join3(client.eth_price(), client.node_count(), client.gas_oracle()).await?;
+    async fn get_transactions_with_receipts(
+        &self,
+        transactions: Vec<Transaction>,
+    ) -> Result<Vec<TransactionWithReceipt>> {
+        let receipts = self.get_transaction_receipts(&transactions).await?;
+
+        Ok(transactions
+            .into_iter()
+            .filter_map(|transaction| {
+                receipts
+                    .iter()
+                    .find(|receipt| receipt.transaction_hash == transaction.hash)
+                    .map(|receipt| TransactionWithReceipt {
+                        transaction,
+                        transaction_receipt: receipt.to_owned(),
+                        decoded_input_data: None,
+                        decoded_logs: vec![],
+                    })
+            })
+            .collect())
+    }
 
-            ethusd = Some(eth_price.ethusd);
-            node_count = Some(total_node_count.total_node_count);
-            suggested_base_fee = Some(gas_oracle.suggested_base_fee);
-            med_gas_price = Some(gas_oracle.propose_gas_price);
-        }
+    async fn get_statistics(&self) -> Result<Statistics> {
+        // Explorer failures show up in the explorer status rather than failing the statistics.
+        let (eth_price, node_count, gas_oracle) = join3(
+            self.explorer(|client| async move { Ok(client.eth_price().await?) }),
+            self.explorer(|client| async move { Ok(client.node_count().await?) }),
+            self.explorer(|client| async move { Ok(client.gas_oracle().await?) }),
+        )
+        .await;
+        let gas_oracle = gas_oracle.ok().flatten();
 
         let (last_safe_block, last_finalized_block) = try_join(
-            provider.get_block_with_txs(BlockNumber::Safe),
-            provider.get_block_with_txs(BlockNumber::Finalized),
+            self.backend.get_block_with_txs(BlockNumber::Safe.into()),
+            self.backend
+                .get_block_with_txs(BlockNumber::Finalized.into()),
         )
         .await?;
 
+        if let Some(number) = last_finalized_block.as_ref().and_then(|block| block.number) {
+            self.cache.lock().await.set_last_finalized_block(number);
+        }
+
         Ok(Statistics {
-            ethusd,
-            node_count,
-            suggested_base_fee,
-            med_gas_price,
+            ethusd: eth_price.ok().flatten().map(|eth_price| eth_price.ethusd),
+            node_count: node_count
+                .ok()
+                .flatten()
+                .map(|node_count| node_count.total_node_count),
+            suggested_base_fee: gas_oracle
+                .as_ref()
+                .map(|gas_oracle| gas_oracle.suggested_base_fee),
+            med_gas_price: gas_oracle.map(|gas_oracle| gas_oracle.propose_gas_price),
             last_safe_block,
             last_finalized_block,
         })
     }
 
-    async fn update_app_with_ens_ids(
-        &mut self,
-        addresses: &[Address],
-    ) -> Result<(), Box<dyn Error>> {
-        let chunked_addresses = addresses.chunks(RATE_LIMIT).collect::<Vec<_>>();
-        for addresses in chunked_addresses {
-            let results = Self::lookup_addresses(self.endpoint, addresses).await?;
-            let mut app = self.app.lock().await;
-
-            for (address, ens_id) in results {
-                if ens_id.is_some() {
-                    app.address2ens_id.insert(address, ens_id);
-                } else {
-                    app.address2ens_id.entry(address).or_insert(ens_id);
+    /// Keeps Latest Blocks and Latest Transactions up to date, using a `newHeads` subscription
+    /// when the transport supports it and block number polling otherwise. A subscription that
+    /// ends or can't be made is made again, on another endpoint if the active one is unhealthy.
+    async fn watch_new_heads(&mut self, n: usize) {
+        let backend = Arc::clone(&self.backend);
+        loop {
+            match backend.subscribe_new_heads().await {
+                Ok(Some(mut heads)) => {
+                    let endpoint = backend.endpoint();
+                    let mut interval = tokio::time::interval(self.new_heads_poll_interval);
+                    loop {
+                        tokio::select! {
+                            head = heads.next() => {
+                                let Some(head) = head else {
+                                    warn!("The newHeads subscription ended, subscribing again");
+                                    break;
+                                };
+                                if let Some(number) = head.number {
+                                    let _ = self.update_app_with_new_head(number, n).await;
+                                    self.update_app_with_backend_status().await;
+                                }
+                            }
+                            // Requests failed over to another endpoint, which is likely to
+                            // serve heads more reliably too.
+                            _ = interval.tick() => if backend.endpoint() != endpoint {
+                                break;
+                            },
+                        }
+                    }
+                }
+                // The transport can only be polled.
+                Ok(None) => self.poll_new_heads(n, None).await,
+                Err(e) => {
+                    warn!("Failed to subscribe to newHeads: {e:#}");
+                    self.poll_new_heads(n, Some(RESUBSCRIBE_DELAY)).await;
                 }
             }
         }
+    }
+
+    /// Polls the latest block number for `duration`, or for good if it's `None`. Stops early
+    /// when requests fail over to another endpoint, which may be able to push new heads.
+    async fn poll_new_heads(&mut self, n: usize, duration: Option<Duration>) {
+        let endpoint = self.backend.endpoint();
+        let until = duration.map(|duration| tokio::time::Instant::now() + duration);
+        let mut interval = tokio::time::interval(self.new_heads_poll_interval);
+        while until.is_none_or(|until| tokio::time::Instant::now() < until)
+            && self.backend.endpoint() == endpoint
+        {
+            interval.tick().await;
+            if let Ok(number) = self.backend.get_block_number().await {
+                let _ = self.update_app_with_new_head(number, n).await;
+            }
+            self.update_app_with_backend_status().await;
+        }
+    }
+
+    async fn update_app_with_new_head(&mut self, number: U64, n: usize) -> Result<()> {
+        // Initial setup or a manual refresh is still in flight.
+        let Some(latest_known_number) = *self.latest_block_number.lock().await else {
+            return Ok(());
+        };
+        if latest_known_number >= number {
+            return Ok(());
+        }
+        let from = std::cmp::max(
+            latest_known_number + 1,
+            number.saturating_sub(U64::from(n.saturating_sub(1))),
+        );
+
+        let query = (from.as_u64()..=number.as_u64())
+            .map(|number| self.backend.get_block_with_txs(U64::from(number).into()))
+            .collect::<Vec<_>>();
+        // Blocks are only added on top of the latest known one, so those after one that failed
+        // are fetched again with the next head.
+        let mut new_blocks = join_all(query)
+            .await
+            .into_iter()
+            .map_while(|block| block.ok().flatten())
+            .collect::<Vec<_>>();
+        new_blocks.reverse();
+
+        let Some(head) = new_blocks.first() else {
+            return Ok(());
+        };
+        let head_number = from + new_blocks.len() as u64 - 1;
+
+        let transactions = self
+            .get_transactions_with_receipts(head.transactions.iter().take(n).cloned().collect())
+            .await?;
+
+        let mut addresses = HashSet::new();
+        for transaction in &transactions {
+            addresses.insert(transaction.transaction.from);
+            if let Some(to) = transaction.transaction.to {
+                addresses.insert(to);
+            }
+        }
+
+        *self.latest_block_number.lock().await = Some(head_number);
+        self.send(Message::NewHeads {
+            blocks: new_blocks,
+            transactions,
+            n,
+        })
+        .await;
+
+        self.update_app_with_ens_ids(&addresses.into_iter().collect::<Vec<_>>())
+            .await;
         Ok(())
     }
 
+    pub async fn update_app_with_backend_status(&self) {
+        self.send(Message::BackendStatus {
+            failed_requests: self.backend.failed_requests(),
+            endpoint: self.backend.endpoint(),
+        })
+        .await;
+    }
+
+    /// Looks up ENS names of `addresses`. Lookups that fail for reasons other than a missing
+    /// name are reported as one `LookupAddresses` request, so they can be retried together.
+    async fn update_app_with_ens_ids(&self, addresses: &[Address]) {
+        let (results, failure) = self.lookup_addresses(addresses).await;
+        if let Some((failed_addresses, e)) = failure {
+            self.notify(
+                None,
+                IoEvent::LookupAddresses {
+                    addresses: failed_addresses,
+                },
+                &e,
+            )
+            .await;
+        }
+
+        self.send(Message::EnsIds(results)).await;
+    }
+
+    /// Returns the ENS name of each address, and the addresses whose lookup failed along with
+    /// the last error.
     async fn lookup_addresses(
-        endpoint: &'a str,
+        &self,
         addresses: &[Address],
-    ) -> Result<Vec<(Address, Option<String>)>> {
-        let provider = Provider::<Http>::try_from(endpoint)?;
-        let query = addresses
+    ) -> (
+        Vec<(Address, Option<String>)>,
+        Option<(Vec<Address>, anyhow::Error)>,
+    ) {
+        let mut res = vec![];
+        let mut missing_addresses = vec![];
+        {
+            let mut cache = self.cache.lock().await;
+            for &address in addresses {
+                match cache.get_ens_id(address) {
+                    Some(ens_id) => res.push((address, ens_id)),
+                    None => missing_addresses.push(address),
+                }
+            }
+        }
+
+        let query = missing_addresses
             .iter()
-            .map(|&address| provider.lookup_address(address))
+            .map(|&address| self.backend.lookup_address(address))
             .collect::<Vec<_>>();
 
         let results = join_all(query).await;
 
-        let res = addresses
+        let mut failed_addresses = vec![];
+        let mut last_error = None;
+        let mut cache = self.cache.lock().await;
+        for (address, ens_id) in missing_addresses.into_iter().zip(results) {
+            match ens_id {
+                Ok(ens_id) => {
+                    cache.put_ens_id(address, Some(ens_id.to_owned()));
+                    res.push((address, Some(ens_id)));
+                }
+                Err(e) => {
+                    // Only a missing reverse record is worth remembering; transport errors are not.
+                    if is_ens_not_found(&e) {
+                        cache.put_ens_id(address, None);
+                    } else {
+                        failed_addresses.push(address);
+                        last_error = Some(e);
+                    }
+                    res.push((address, None));
+                }
+            }
+        }
+
+        (res, last_error.map(|e| (failed_addresses, e)))
+    }
+}
+
+/// A transaction and its receipt as listed by the explorer's `txlist`, so that a page of
+/// them doesn't have to be fetched again over JSON-RPC. The listing has no logs, and doesn't
+/// say the type of the transaction, which both load once it's opened.
+fn from_normal_transaction(listed: NormalTransaction) -> Option<TransactionWithReceipt> {
+    let hash = *listed.hash.value()?;
+    let from = listed.from.value().copied().unwrap_or_default();
+    let block_hash = listed.block_hash.map(|hash| H256::from_uint(&hash));
+    let block_number = listed.block_number.as_number();
+    let transaction_index = listed.transaction_index.map(U64::from);
+    // `txreceipt_status` is empty before Byzantium, when only `isError` is known.
+    let status = match listed.tx_receipt_status.as_str() {
+        "" => (listed.is_error == "0") as u64,
+        status => (status == "1") as u64,
+    };
+    Some(TransactionWithReceipt {
+        transaction: Transaction {
+            hash,
+            nonce: listed.nonce.unwrap_or_default(),
+            block_hash,
+            block_number,
+            transaction_index,
+            from,
+            to: listed.to,
+            value: listed.value,
+            gas_price: listed.gas_price,
+            gas: listed.gas,
+            input: listed.input,
+            ..Default::default()
+        },
+        transaction_receipt: TransactionReceipt {
+            transaction_hash: hash,
+            transaction_index: transaction_index.unwrap_or_default(),
+            block_hash,
+            block_number,
+            from,
+            to: listed.to,
+            cumulative_gas_used: listed.cumulative_gas_used,
+            gas_used: Some(listed.gas_used),
+            contract_address: listed.contract_address,
+            status: Some(U64::from(status)),
+            effective_gas_price: listed.gas_price,
+            ..Default::default()
+        },
+        decoded_input_data: None,
+        decoded_logs: vec![],
+    })
+}
+
+/// Calldata for ERC-20 `balanceOf(owner)`.
+fn balance_of(owner: Address) -> Bytes {
+    [
+        &id("balanceOf(address)")[..],
+        &encode(&[Token::Address(owner)]),
+    ]
+    .concat()
+    .into()
+}
+
+/// Decodes the output of a function returning a `uint`.
+fn decode_uint(output: &[u8]) -> Option<U256> {
+    match decode(&[ParamType::Uint(256)], output).ok()?.pop()? {
+        Token::Uint(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// Addresses of the contracts that emitted the logs of `transaction_receipt`, without duplicates.
+fn emitters(transaction_receipt: &TransactionReceipt) -> Vec<Address> {
+    let mut emitters = vec![];
+    for log in &transaction_receipt.logs {
+        if !emitters.contains(&log.address) {
+            emitters.push(log.address);
+        }
+    }
+    emitters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use ethers::core::types::Log;
+    use fake::{transaction, FakeBackend, CHAIN_ID};
+    use tokio::sync::mpsc::{self, Receiver};
+
+    /// A `Network` backed by `backend`, and the receiving end of its messages.
+    fn network(backend: &Arc<FakeBackend>) -> (Network, Receiver<Message>) {
+        let (messages, receiver) = mpsc::channel(256);
+        let network = Network::new(
+            messages,
+            backend.to_owned(),
+            Arc::new(Mutex::new(Cache::new(None))),
+            ChainContext::new(CHAIN_ID),
+            Arc::new(Signatures::default()),
+            Duration::from_secs(1),
+        );
+        (network, receiver)
+    }
+
+    /// A chain of a genesis block and a block with two transactions.
+    fn chain() -> (Arc<FakeBackend>, Block<Transaction>) {
+        let backend = Arc::new(FakeBackend::new());
+        backend.mine(vec![]);
+        let sender = Address::from_low_u64_be(1);
+        let block = backend.mine(vec![
+            transaction(sender, 0, Some(Address::from_low_u64_be(2)), Bytes::new()),
+            transaction(sender, 1, None, Bytes::new()),
+        ]);
+        (backend, block)
+    }
+
+    fn hashes(block: &Block<Transaction>) -> Vec<TxHash> {
+        block.transactions.iter().map(|tx| tx.hash).collect()
+    }
+
+    #[tokio::test]
+    async fn loads_a_block_with_its_receipts() {
+        let (backend, block) = chain();
+        let (network, _receiver) = network(&backend);
+
+        let loaded = network.get_block(U64::from(1)).await.unwrap().unwrap();
+        assert_eq!(loaded.block.hash, block.hash);
+        let receipts = loaded.transaction_receipts.unwrap();
+        assert_eq!(
+            receipts
+                .iter()
+                .map(|receipt| receipt.transaction_hash)
+                .collect::<Vec<_>>(),
+            hashes(&block)
+        );
+        assert!(receipts
             .iter()
-            .zip(results.iter())
-            .map(|(address, ens_id)| {
-                (
-                    address.to_owned(),
-                    ens_id
-                        .as_ref()
-                        .map_or(None, |ens_id| Some(ens_id.to_owned())),
-                )
-            })
+            .all(|receipt| receipt.block_hash == block.hash));
+    }
+
+    #[tokio::test]
+    async fn loads_receipts_one_by_one_without_bulk_methods() {
+        let (backend, block) = chain();
+        backend.unsupport("eth_getBlockReceipts");
+        backend.unsupport("batch");
+        let (network, _receiver) = network(&backend);
+
+        let loaded = network
+            .get_block(block.hash.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.transaction_receipts.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn serves_blocks_from_the_cache() {
+        let (backend, block) = chain();
+        let (network, _receiver) = network(&backend);
+
+        network.get_block(block.hash.unwrap()).await.unwrap();
+        let requests = backend.requests();
+        network.get_block(block.hash.unwrap()).await.unwrap();
+        assert_eq!(backend.requests(), requests);
+    }
+
+    #[tokio::test]
+    async fn loads_a_transaction_with_its_receipt() {
+        let (backend, block) = chain();
+        let (network, _receiver) = network(&backend);
+
+        let transaction_hash = block.transactions[0].hash;
+        let loaded = network
+            .get_transaction_with_receipt(transaction_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.transaction.hash, transaction_hash);
+        assert_eq!(
+            loaded.transaction_receipt.transaction_hash,
+            transaction_hash
+        );
+        assert_eq!(loaded.transaction_receipt.block_hash, block.hash);
+
+        let missing = network
+            .get_transaction_with_receipt(TxHash::zero())
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn loads_receipts_of_reorged_transactions_again() {
+        let (backend, block) = chain();
+        let (network, _receiver) = network(&backend);
+        let transaction_hash = block.transactions[0].hash;
+        network
+            .get_transaction_with_receipt(transaction_hash)
+            .await
+            .unwrap();
+
+        backend.reorg(1);
+        let reorged = backend.mine(block.transactions.to_owned());
+        assert_ne!(reorged.hash, block.hash);
+
+        let loaded = network
+            .get_transaction_with_receipt(transaction_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.transaction.block_hash, reorged.hash);
+        assert_eq!(loaded.transaction_receipt.block_hash, reorged.hash);
+
+        let loaded = network
+            .get_block(reorged.hash.unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(loaded
+            .transaction_receipts
+            .unwrap()
+            .iter()
+            .all(|receipt| receipt.block_hash == reorged.hash));
+    }
+
+    #[tokio::test]
+    async fn serves_finalized_transactions_from_the_cache() {
+        let (backend, block) = chain();
+        let (network, _receiver) = network(&backend);
+        network
+            .cache
+            .lock()
+            .await
+            .set_last_finalized_block(1.into());
+        let transaction_hash = block.transactions[0].hash;
+
+        network
+            .get_transaction_with_receipt(transaction_hash)
+            .await
+            .unwrap();
+        let requests = backend.requests();
+        network
+            .get_transaction_with_receipt(transaction_hash)
+            .await
+            .unwrap();
+        assert_eq!(backend.requests(), requests);
+    }
+
+    #[tokio::test]
+    async fn loads_the_latest_blocks_down_to_genesis() {
+        let (backend, _) = chain();
+        let (network, _receiver) = network(&backend);
+
+        let (blocks, failed) = network.get_latest_blocks(5).await.unwrap();
+        let blocks = blocks
+            .into_iter()
+            .map(|block| block.block)
             .collect::<Vec<_>>();
+        assert_eq!(numbers(&blocks), [1, 0]);
+        assert_eq!(failed, 0);
+    }
+
+    #[tokio::test]
+    async fn counts_latest_blocks_that_failed() {
+        let (backend, _) = chain();
+        backend.mine(vec![]);
+        backend.fail_block(1);
+        let (mut network, mut receiver) = network(&backend);
+
+        network
+            .handle_network_event(0, IoEvent::GetLatestBlocks { n: 3 })
+            .await;
+        let Some(Message::LatestBlocks { blocks, failed }) = receiver.recv().await else {
+            panic!("expected the latest blocks");
+        };
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(failed, 1);
+        assert_eq!(*network.latest_block_number.lock().await, Some(2.into()));
+    }
+
+    #[tokio::test]
+    async fn decodes_input_data_with_the_contract_abi() {
+        let backend = Arc::new(FakeBackend::new());
+        let contract = Address::from_low_u64_be(0xc0de);
+        let block = backend.mine(vec![transaction(
+            Address::from_low_u64_be(1),
+            0,
+            Some(contract),
+            abi::tests::swap_input(Address::zero()).into(),
+        )]);
+        let (network, _receiver) = network(&backend);
+        network
+            .cache
+            .lock()
+            .await
+            .put_abi(contract, serde_json::from_str(abi::tests::ABI).unwrap());
+
+        let loaded = network
+            .get_transaction_with_receipt(block.transactions[0].hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.decoded_input_data.unwrap().name, "swap");
+    }
+
+    #[tokio::test]
+    async fn decodes_logs_with_the_abi_of_their_emitter() {
+        let backend = Arc::new(FakeBackend::new());
+        let (contract, other) = (
+            Address::from_low_u64_be(0xc0de),
+            Address::from_low_u64_be(2),
+        );
+        let block = backend.mine(vec![transaction(other, 0, Some(contract), Bytes::new())]);
+        let transaction_hash = block.transactions[0].hash;
+        let filled = abi::tests::filled_log(other, other, 1);
+        backend.set_logs(
+            transaction_hash,
+            vec![
+                Log {
+                    address: contract,
+                    ..filled.to_owned()
+                },
+                // Emitted by a contract without a known ABI.
+                Log {
+                    address: other,
+                    ..filled
+                },
+            ],
+        );
+        let (network, _receiver) = network(&backend);
+        network
+            .cache
+            .lock()
+            .await
+            .put_abi(contract, serde_json::from_str(abi::tests::ABI).unwrap());
+
+        let loaded = network
+            .get_transaction_with_receipt(transaction_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        let names = loaded
+            .decoded_logs
+            .iter()
+            .map(|decoded| decoded.as_ref().map(|decoded| decoded.name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(names, [Some("Filled"), None]);
+    }
+
+    /// Waits for the `NewHeads` message, skipping the others.
+    async fn new_heads(receiver: &mut Receiver<Message>) -> Vec<Block<Transaction>> {
+        loop {
+            if let Message::NewHeads { blocks, .. } = receiver.recv().await.unwrap() {
+                return blocks;
+            }
+        }
+    }
+
+    fn numbers(blocks: &[Block<Transaction>]) -> Vec<u64> {
+        blocks
+            .iter()
+            .map(|block| block.number.unwrap().as_u64())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn sends_the_blocks_since_the_latest_known_one() {
+        let (backend, _) = chain();
+        let (mut network, mut receiver) = network(&backend);
+        *network.latest_block_number.lock().await = Some(1.into());
+        for _ in 0..3 {
+            backend.mine(vec![]);
+        }
+
+        network
+            .update_app_with_new_head(4.into(), 10)
+            .await
+            .unwrap();
+        assert_eq!(numbers(&new_heads(&mut receiver).await), [4, 3, 2]);
+        assert_eq!(*network.latest_block_number.lock().await, Some(4.into()));
+    }
+
+    #[tokio::test]
+    async fn ignores_heads_until_the_latest_blocks_are_loaded() {
+        let (backend, _) = chain();
+        let (mut network, _receiver) = network(&backend);
+
+        network
+            .update_app_with_new_head(1.into(), 10)
+            .await
+            .unwrap();
+        assert_eq!(backend.requests(), 0);
+        assert_eq!(*network.latest_block_number.lock().await, None);
+    }
+
+    #[tokio::test]
+    async fn fetches_blocks_after_a_failed_one_again_with_the_next_head() {
+        let (backend, _) = chain();
+        let (mut network, mut receiver) = network(&backend);
+        *network.latest_block_number.lock().await = Some(1.into());
+        for _ in 0..3 {
+            backend.mine(vec![]);
+        }
+        backend.fail_block(3);
+
+        network
+            .update_app_with_new_head(4.into(), 10)
+            .await
+            .unwrap();
+        assert_eq!(numbers(&new_heads(&mut receiver).await), [2]);
+        assert_eq!(*network.latest_block_number.lock().await, Some(2.into()));
+
+        backend.mine(vec![]);
+        network
+            .update_app_with_new_head(5.into(), 10)
+            .await
+            .unwrap();
+        assert_eq!(numbers(&new_heads(&mut receiver).await), [5, 4, 3]);
+        assert_eq!(*network.latest_block_number.lock().await, Some(5.into()));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polls_new_heads_until_subscribing_again() {
+        let (backend, _) = chain();
+        let (mut network, mut receiver) = network(&backend);
+        *network.latest_block_number.lock().await = Some(1.into());
+        backend.mine(vec![]);
+
+        network.poll_new_heads(10, Some(RESUBSCRIBE_DELAY)).await;
+        assert_eq!(numbers(&new_heads(&mut receiver).await), [2]);
+        assert_eq!(*network.latest_block_number.lock().await, Some(2.into()));
+    }
+
+    #[tokio::test]
+    async fn loads_the_code_of_an_address() {
+        let backend = Arc::new(FakeBackend::new());
+        let address = Address::from_low_u64_be(1);
+        let code = Bytes::from(vec![0x60, 0x00, 0xf3]);
+        backend.set_code(address, code.to_owned());
+        let (network, _receiver) = network(&backend);
+
+        let info = network.get_address_info(address).await.unwrap().unwrap();
+        assert_eq!(info.code, code);
+        assert!(info.ens_id.is_none());
+        assert!(info.proxy.is_none());
+    }
+
+    #[tokio::test]
+    async fn reads_storage_slots() {
+        let backend = Arc::new(FakeBackend::new());
+        let address = Address::from_low_u64_be(1);
+        let slot = H256::from_low_u64_be(2);
+        backend.set_storage(address, slot, H256::from_low_u64_be(3));
+        let (mut network, mut receiver) = network(&backend);
+
+        network
+            .handle_network_event(0, IoEvent::GetStorageAt { address, slot })
+            .await;
+        let Some(Message::StorageAt { value, .. }) = receiver.recv().await else {
+            panic!("expected the slot's value");
+        };
+        assert_eq!(value, H256::from_low_u64_be(3));
+        assert!(matches!(
+            receiver.recv().await,
+            Some(Message::Finished { request_id: 0 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn reports_failed_requests() {
+        let backend = Arc::new(FakeBackend::new());
+        backend.fail_next(anyhow!("connection refused"));
+        let (mut network, mut receiver) = network(&backend);
+
+        network
+            .handle_network_event(
+                7,
+                IoEvent::GetStorageAt {
+                    address: Address::zero(),
+                    slot: H256::zero(),
+                },
+            )
+            .await;
+        let Some(Message::Failed {
+            request_id,
+            io_event,
+            message,
+            ..
+        }) = receiver.recv().await
+        else {
+            panic!("expected the failure to be reported");
+        };
+        assert_eq!(request_id, Some(7));
+        assert!(matches!(*io_event, IoEvent::GetStorageAt { .. }));
+        assert_eq!(message, "connection refused");
+        assert!(matches!(
+            receiver.recv().await,
+            Some(Message::Finished { request_id: 7 })
+        ));
+    }
+
+    /// Makes `proxy` an EIP-1967 proxy of `implementation`, and mines a call of `swap` to it.
+    fn proxy_call(backend: &FakeBackend, proxy: Address, implementation: Address) -> TxHash {
+        backend.set_code(proxy, vec![0x36, 0x3d, 0x3d, 0x37].into());
+        backend.set_storage(
+            proxy,
+            storage::eip1967_implementation_slot(),
+            implementation.into(),
+        );
+        let block = backend.mine(vec![transaction(
+            Address::from_low_u64_be(1),
+            0,
+            Some(proxy),
+            abi::tests::swap_input(Address::zero()).into(),
+        )]);
+        block.transactions[0].hash
+    }
+
+    #[tokio::test]
+    async fn decodes_calls_to_a_proxy_with_its_implementations_abi() {
+        let backend = Arc::new(FakeBackend::new());
+        let (proxy, implementation) = (
+            Address::from_low_u64_be(0xc0de),
+            Address::from_low_u64_be(0x1111),
+        );
+        let transaction_hash = proxy_call(&backend, proxy, implementation);
+        let (network, _receiver) = network(&backend);
+        {
+            let mut cache = network.cache.lock().await;
+            cache.put_abi(proxy, None);
+            cache.put_abi(
+                implementation,
+                serde_json::from_str(abi::tests::ABI).unwrap(),
+            );
+        }
+
+        let loaded = network
+            .get_transaction_with_receipt(transaction_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.decoded_input_data.unwrap().name, "swap");
+        assert_eq!(
+            network.cache.lock().await.get_proxy(proxy).flatten(),
+            Some(Proxy {
+                kind: ProxyKind::Eip1967,
+                implementation,
+                admin: None,
+                beacon: None,
+            })
+        );
+    }
 
-        Ok(res)
+    #[tokio::test]
+    async fn skips_proxy_detection_when_the_contracts_own_abi_decodes() {
+        let backend = Arc::new(FakeBackend::new());
+        let (proxy, implementation) = (
+            Address::from_low_u64_be(0xc0de),
+            Address::from_low_u64_be(0x1111),
+        );
+        let transaction_hash = proxy_call(&backend, proxy, implementation);
+        let (network, _receiver) = network(&backend);
+        network
+            .cache
+            .lock()
+            .await
+            .put_abi(proxy, serde_json::from_str(abi::tests::ABI).unwrap());
+
+        let loaded = network
+            .get_transaction_with_receipt(transaction_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.decoded_input_data.unwrap().name, "swap");
+        assert_eq!(network.cache.lock().await.get_proxy(proxy), None);
     }
 }
@@ -0,0 +1,490 @@
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use ethers::{
+    core::types::{
+        Address, Block, BlockId, Bytes, Transaction, TransactionReceipt, TransactionRequest,
+        TxHash, H256, U256, U64,
+    },
+    providers::{
+        Http, HttpClientError, Ipc, JsonRpcClient, JsonRpcError, Middleware, Provider,
+        ProviderError, Ws,
+    },
+};
+use futures::{
+    future::try_join_all,
+    stream::{BoxStream, StreamExt},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{fmt::Debug, sync::Arc};
+use url::Url;
+
+/// JSON-RPC operations `Network` needs from a node.
+///
+/// Implemented for every ethers `Provider`, so HTTP, WebSocket and IPC transports can be used
+/// interchangeably and a fake backend can be plugged in where no node is available.
+#[async_trait]
+pub trait RpcBackend: NewHeads + BatchRequests + Send + Sync {
+    async fn get_chain_id(&self) -> Result<u64>;
+
+    async fn get_block_number(&self) -> Result<U64>;
+
+    async fn get_block_with_txs(&self, block_id: BlockId) -> Result<Option<Block<Transaction>>>;
+
+    async fn get_transaction(&self, transaction_hash: TxHash) -> Result<Option<Transaction>>;
+
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>>;
+
+    /// Receipts of every transaction in a block, with `eth_getBlockReceipts`.
+    async fn get_block_receipts(&self, block_id: BlockId) -> Result<Vec<TransactionReceipt>>;
+
+    async fn get_balance(&self, address: Address, block_id: Option<BlockId>) -> Result<U256>;
+
+    /// The code deployed at `address`, empty for externally owned accounts.
+    async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> Result<Bytes>;
+
+    async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: H256,
+        block_id: Option<BlockId>,
+    ) -> Result<H256>;
+
+    /// Makes an `eth_call` to `to` at the latest block.
+    async fn call(&self, to: Address, data: Bytes) -> Result<Bytes>;
+
+    async fn lookup_address(&self, address: Address) -> Result<String>;
+
+    async fn resolve_name(&self, ens_id: &str) -> Result<Address>;
+
+    async fn resolve_avatar(&self, ens_id: &str) -> Result<Url>;
+
+    /// Number of requests that failed even after retrying.
+    fn failed_requests(&self) -> usize {
+        0
+    }
+
+    /// The endpoint requests are currently sent to, if there is a choice of several.
+    fn endpoint(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Push notifications of new block heads.
+#[async_trait]
+pub trait NewHeads {
+    /// Subscribes to `newHeads`, or returns `None` if the transport can only be polled.
+    async fn subscribe_new_heads(&self) -> Result<Option<BoxStream<'_, Block<TxHash>>>> {
+        Ok(None)
+    }
+}
+
+impl NewHeads for Provider<HttpTransport> {}
+
+#[async_trait]
+impl NewHeads for Provider<Ws> {
+    async fn subscribe_new_heads(&self) -> Result<Option<BoxStream<'_, Block<TxHash>>>> {
+        Ok(Some(self.subscribe_blocks().await?.boxed()))
+    }
+}
+
+#[async_trait]
+impl NewHeads for Provider<Ipc> {
+    async fn subscribe_new_heads(&self) -> Result<Option<BoxStream<'_, Block<TxHash>>>> {
+        Ok(Some(self.subscribe_blocks().await?.boxed()))
+    }
+}
+
+/// The HTTP transport, along with the client it sends requests with, so that batch requests are
+/// sent the same way as single ones.
+#[derive(Debug)]
+pub struct HttpTransport {
+    http: Http,
+    client: reqwest::Client,
+}
+
+impl HttpTransport {
+    pub fn new(url: Url) -> Self {
+        let client = reqwest::Client::new();
+        Self {
+            http: Http::new_with_client(url, client.to_owned()),
+            client,
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for HttpTransport {
+    type Error = HttpClientError;
+
+    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, HttpClientError>
+    where
+        T: Debug + Serialize + Send + Sync,
+        R: DeserializeOwned + Send,
+    {
+        self.http.request(method, params).await
+    }
+}
+
+/// The largest batch most public endpoints accept.
+const MAX_BATCH_SIZE: usize = 100;
+
+/// JSON-RPC batch requests.
+#[async_trait]
+pub trait BatchRequests {
+    /// Fetches receipts with `eth_getTransactionReceipt` batch requests, or fails with
+    /// `ProviderError::UnsupportedRPC` if the transport can't batch.
+    async fn get_transaction_receipts_batch(
+        &self,
+        _transaction_hashes: &[TxHash],
+    ) -> Result<Vec<Option<TransactionReceipt>>> {
+        Err(ProviderError::UnsupportedRPC.into())
+    }
+
+    /// Makes `eth_call`s at the latest block with batch requests, or fails with
+    /// `ProviderError::UnsupportedRPC` if the transport can't batch. Calls that revert come back
+    /// as `None`.
+    async fn call_batch(&self, _calls: &[(Address, Bytes)]) -> Result<Vec<Option<Bytes>>> {
+        Err(ProviderError::UnsupportedRPC.into())
+    }
+}
+
+#[async_trait]
+impl BatchRequests for Provider<HttpTransport> {
+    async fn get_transaction_receipts_batch(
+        &self,
+        transaction_hashes: &[TxHash],
+    ) -> Result<Vec<Option<TransactionReceipt>>> {
+        let params = transaction_hashes
+            .iter()
+            .map(|transaction_hash| json!([transaction_hash]))
+            .collect();
+        send_batch(self.as_ref(), "eth_getTransactionReceipt", params)
+            .await?
+            .into_iter()
+            .map(|response| match response {
+                Some(response) => Ok(serde_json::from_value(into_result(response)?)?),
+                None => Ok(None),
+            })
+            .collect()
+    }
+
+    async fn call_batch(&self, calls: &[(Address, Bytes)]) -> Result<Vec<Option<Bytes>>> {
+        let params = calls
+            .iter()
+            .map(|(to, data)| json!([{ "to": to, "data": data }, "latest"]))
+            .collect();
+        Ok(send_batch(self.as_ref(), "eth_call", params)
+            .await?
+            .into_iter()
+            .map(|response| serde_json::from_value(into_result(response?).ok()?).ok())
+            .collect())
+    }
+}
+
+/// Sends a `method` request for each of `params` in as few batches as possible, and returns
+/// the response objects in the same order, `None` where the endpoint left one out.
+async fn send_batch(
+    transport: &HttpTransport,
+    method: &str,
+    params: Vec<Value>,
+) -> Result<Vec<Option<Value>>> {
+    let url = transport.http.url();
+
+    let query = params.chunks(MAX_BATCH_SIZE).map(|params| {
+        let requests = params
+            .iter()
+            .enumerate()
+            .map(|(id, params)| {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "method": method,
+                    "params": params,
+                })
+            })
+            .collect::<Vec<_>>();
+        let request = transport.client.post(url.to_owned()).json(&requests);
+
+        async move {
+            let text = request.send().await?.error_for_status()?.text().await?;
+            let response = serde_json::from_str(&text).map_err(|err| {
+                ProviderError::from(HttpClientError::SerdeJson {
+                    err,
+                    text: text.to_owned(),
+                })
+            })?;
+            let Value::Array(responses) = response else {
+                // A single error object answers the batch as a whole: "method not found" from
+                // endpoints without batch support, or e.g. a rate limit.
+                into_result(response)?;
+                return Err(ProviderError::from(HttpClientError::SerdeJson {
+                    err: serde::de::Error::custom("expected an array of responses"),
+                    text,
+                })
+                .into());
+            };
+
+            let mut ordered = vec![None; params.len()];
+            for response in responses {
+                if let Some(slot) = response
+                    .get("id")
+                    .and_then(Value::as_u64)
+                    .and_then(|id| ordered.get_mut(id as usize))
+                {
+                    *slot = Some(response);
+                }
+            }
+            Ok::<_, anyhow::Error>(ordered)
+        }
+    });
+
+    Ok(try_join_all(query).await?.into_iter().flatten().collect())
+}
+
+/// The result of a response object, or its error as the provider would have returned it.
+fn into_result(mut response: Value) -> Result<Value> {
+    if let Some(error) = response.get_mut("error") {
+        let error = serde_json::from_value::<JsonRpcError>(error.take())?;
+        return Err(ProviderError::from(HttpClientError::JsonRpcError(error)).into());
+    }
+    Ok(response["result"].take())
+}
+
+impl BatchRequests for Provider<Ws> {}
+
+impl BatchRequests for Provider<Ipc> {}
+
+#[async_trait]
+impl<P> RpcBackend for Provider<P>
+where
+    P: JsonRpcClient + 'static,
+    Provider<P>: NewHeads + BatchRequests,
+{
+    async fn get_chain_id(&self) -> Result<u64> {
+        Ok(Middleware::get_chainid(self).await?.as_u64())
+    }
+
+    async fn get_block_number(&self) -> Result<U64> {
+        Ok(Middleware::get_block_number(self).await?)
+    }
+
+    async fn get_block_with_txs(&self, block_id: BlockId) -> Result<Option<Block<Transaction>>> {
+        Ok(Middleware::get_block_with_txs(self, block_id).await?)
+    }
+
+    async fn get_transaction(&self, transaction_hash: TxHash) -> Result<Option<Transaction>> {
+        Ok(Middleware::get_transaction(self, transaction_hash).await?)
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>> {
+        Ok(Middleware::get_transaction_receipt(self, transaction_hash).await?)
+    }
+
+    async fn get_block_receipts(&self, block_id: BlockId) -> Result<Vec<TransactionReceipt>> {
+        Ok(self.request("eth_getBlockReceipts", [block_id]).await?)
+    }
+
+    async fn get_balance(&self, address: Address, block_id: Option<BlockId>) -> Result<U256> {
+        Ok(Middleware::get_balance(self, address, block_id).await?)
+    }
+
+    async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> Result<Bytes> {
+        Ok(Middleware::get_code(self, address, block_id).await?)
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: H256,
+        block_id: Option<BlockId>,
+    ) -> Result<H256> {
+        Ok(Middleware::get_storage_at(self, address, slot, block_id).await?)
+    }
+
+    async fn call(&self, to: Address, data: Bytes) -> Result<Bytes> {
+        let transaction = TransactionRequest::new().to(to).data(data);
+        Ok(Middleware::call(self, &transaction.into(), None).await?)
+    }
+
+    async fn lookup_address(&self, address: Address) -> Result<String> {
+        Ok(Middleware::lookup_address(self, address).await?)
+    }
+
+    async fn resolve_name(&self, ens_id: &str) -> Result<Address> {
+        Ok(Middleware::resolve_name(self, ens_id).await?)
+    }
+
+    async fn resolve_avatar(&self, ens_id: &str) -> Result<Url> {
+        Ok(Middleware::resolve_avatar(self, ens_id).await?)
+    }
+}
+
+/// Whether `e` only means that an address has no ENS name (or a name no address).
+pub fn is_ens_not_found(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<ProviderError>(),
+        Some(ProviderError::EnsError(_) | ProviderError::EnsNotOwned(_))
+    )
+}
+
+/// Whether the node answered with an error, e.g. because a call reverted, rather than failing to
+/// answer.
+pub fn is_error_response(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<ProviderError>(),
+        Some(ProviderError::JsonRpcClientError(e)) if e.is_error_response()
+    )
+}
+
+/// The JSON-RPC error code for a method the node doesn't have.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Whether the node answered that it doesn't support the request. Other error responses, such as
+/// rate limits, may well go away.
+pub fn is_unsupported(e: &anyhow::Error) -> bool {
+    match e.downcast_ref::<ProviderError>() {
+        Some(ProviderError::UnsupportedRPC) => true,
+        Some(ProviderError::JsonRpcClientError(e)) => e
+            .as_error_response()
+            .is_some_and(|response| response.code == METHOD_NOT_FOUND),
+        _ => false,
+    }
+}
+
+/// Connects to `endpoint`, choosing the transport by its URL scheme.
+///
+/// `http(s)://` uses HTTP, `ws(s)://` uses WebSocket, and `ipc://<path>` or a bare file path
+/// (e.g. `/home/user/.ethereum/geth.ipc`) uses IPC.
+pub async fn connect(endpoint: &str) -> Result<Arc<dyn RpcBackend>> {
+    match Url::parse(endpoint) {
+        Ok(url) => match url.scheme() {
+            "http" | "https" => Ok(Arc::new(Provider::new(HttpTransport::new(url)))),
+            "ws" | "wss" => Ok(Arc::new(Provider::<Ws>::connect(endpoint).await?)),
+            "ipc" | "file" => Ok(Arc::new(Provider::connect_ipc(url.path()).await?)),
+            scheme => bail!("Unsupported endpoint scheme: {scheme}"),
+        },
+        Err(_) => Ok(Arc::new(Provider::<Ipc>::connect_ipc(endpoint).await?)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::scheduler::is_retryable;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// A provider whose endpoint answers its first request with `body`.
+    async fn answering(body: &'static str) -> Provider<HttpTransport> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            // Reads the whole request before answering.
+            let mut request = vec![];
+            let mut buf = [0; 4096];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_lowercase();
+                if let Some((head, content)) = text.split_once("\r\n\r\n") {
+                    let content_length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .map_or(0, |length| length.trim().parse().unwrap());
+                    if content.len() >= content_length {
+                        break;
+                    }
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\
+                 connection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+        Provider::new(HttpTransport::new(url))
+    }
+
+    fn calls(n: u64) -> Vec<(Address, Bytes)> {
+        (0..n)
+            .map(|i| (Address::from_low_u64_be(i), Bytes::new()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn orders_batch_responses_by_id() {
+        let provider = answering(
+            r#"[
+                {"jsonrpc": "2.0", "id": 2, "result": "0x02"},
+                {"jsonrpc": "2.0", "id": 0, "result": "0x00"},
+                {"jsonrpc": "2.0", "id": 1, "error": {"code": 3, "message": "reverted"}}
+            ]"#,
+        )
+        .await;
+
+        let outputs = provider.call_batch(&calls(4)).await.unwrap();
+        assert_eq!(
+            outputs,
+            [
+                Some(Bytes::from(vec![0])),
+                None,
+                Some(Bytes::from(vec![2])),
+                None
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn only_method_not_found_means_batches_are_unsupported() {
+        let provider = answering(
+            r#"{"jsonrpc": "2.0", "id": null, "error": {"code": -32601, "message": "no batches"}}"#,
+        )
+        .await;
+        let e = provider.call_batch(&calls(2)).await.unwrap_err();
+        assert!(is_unsupported(&e));
+
+        let provider = answering(
+            r#"{"jsonrpc": "2.0", "id": null, "error": {"code": 429, "message": "slow down"}}"#,
+        )
+        .await;
+        let e = provider.call_batch(&calls(2)).await.unwrap_err();
+        assert!(!is_unsupported(&e));
+        assert!(is_retryable(&e));
+    }
+
+    #[tokio::test]
+    async fn fails_on_responses_that_are_not_batches() {
+        let provider = answering(r#"{"jsonrpc": "2.0", "id": 0, "result": "0x"}"#).await;
+        let e = provider.call_batch(&calls(2)).await.unwrap_err();
+        assert!(!is_unsupported(&e));
+    }
+
+    #[tokio::test]
+    async fn returns_receipt_errors_as_provider_errors() {
+        let provider = answering(
+            r#"[
+                {"jsonrpc": "2.0", "id": 0, "result": null},
+                {"jsonrpc": "2.0", "id": 1, "error": {"code": -32005, "message": "limit"}}
+            ]"#,
+        )
+        .await;
+
+        let e = provider
+            .get_transaction_receipts_batch(&[TxHash::zero(), TxHash::zero()])
+            .await
+            .unwrap_err();
+        assert!(e.downcast_ref::<ProviderError>().is_some());
+        assert!(is_retryable(&e));
+    }
+}
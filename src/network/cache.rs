@@ -0,0 +1,303 @@
+use crate::ethers::{
+    abi::ContractAbi,
+    types::{BlockWithTransactionReceipts, Proxy},
+};
+use ethers::core::types::{
+    Address, BlockId, BlockNumber, Transaction, TransactionReceipt, TxHash, H256, U64,
+};
+use log::debug;
+use lru::LruCache;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::HashMap, fs, num::NonZeroUsize, path::PathBuf};
+
+const BLOCK_CACHE_SIZE: usize = 64;
+const TRANSACTION_CACHE_SIZE: usize = 8192;
+const ENS_ID_CACHE_SIZE: usize = 8192;
+const ABI_CACHE_SIZE: usize = 256;
+const PROXY_CACHE_SIZE: usize = 1024;
+
+/// Responses shared by every network task.
+///
+/// Blocks are keyed by hash, so they never go stale in memory. Transactions and receipts are
+/// only cached once mined, and a reorg can move them to another block, so readers check them
+/// against their block unless it's finalized.
+/// Anything at or below the last finalized block is additionally written to `dir`, if given,
+/// and survives restarts, as do verified contract ABIs. ABIs placed in `dir/abis` as
+/// `<address>.json` are used as if they had been fetched.
+pub struct Cache {
+    blocks: LruCache<H256, BlockWithTransactionReceipts<Transaction>>,
+    finalized_block_hashes: HashMap<U64, H256>,
+    transactions: LruCache<TxHash, Transaction>,
+    receipts: LruCache<TxHash, TransactionReceipt>,
+    ens_ids: LruCache<Address, Option<String>>,
+    /// `None` for contracts that aren't verified.
+    abis: LruCache<Address, Option<ContractAbi>>,
+    /// `None` for contracts that aren't proxies. Kept in memory only, as proxies get upgraded.
+    proxies: LruCache<Address, Option<Proxy>>,
+    /// `decimals()` of ERC-20 tokens, which never change.
+    decimals: HashMap<Address, u8>,
+    last_finalized_block: Option<U64>,
+    dir: Option<PathBuf>,
+}
+
+impl Cache {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        if let Some(dir) = dir.as_ref() {
+            for sub_dir in ["blocks", "transactions", "receipts", "abis"] {
+                let _ = fs::create_dir_all(dir.join(sub_dir));
+            }
+        }
+
+        Self {
+            blocks: LruCache::new(NonZeroUsize::new(BLOCK_CACHE_SIZE).unwrap()),
+            finalized_block_hashes: HashMap::new(),
+            transactions: LruCache::new(NonZeroUsize::new(TRANSACTION_CACHE_SIZE).unwrap()),
+            receipts: LruCache::new(NonZeroUsize::new(TRANSACTION_CACHE_SIZE).unwrap()),
+            ens_ids: LruCache::new(NonZeroUsize::new(ENS_ID_CACHE_SIZE).unwrap()),
+            abis: LruCache::new(NonZeroUsize::new(ABI_CACHE_SIZE).unwrap()),
+            proxies: LruCache::new(NonZeroUsize::new(PROXY_CACHE_SIZE).unwrap()),
+            decimals: HashMap::new(),
+            last_finalized_block: None,
+            dir,
+        }
+    }
+
+    pub fn set_last_finalized_block(&mut self, number: U64) {
+        self.last_finalized_block = Some(number);
+    }
+
+    /// Whether block `number` is at or below the last finalized block, so it can't be reorged.
+    pub fn is_finalized(&self, number: Option<U64>) -> bool {
+        match (number, self.last_finalized_block) {
+            (Some(number), Some(last_finalized_block)) => number <= last_finalized_block,
+            _ => false,
+        }
+    }
+
+    pub fn get_block(
+        &mut self,
+        block_id: BlockId,
+    ) -> Option<BlockWithTransactionReceipts<Transaction>> {
+        let hash = match block_id {
+            BlockId::Hash(hash) => hash,
+            BlockId::Number(BlockNumber::Number(number)) => {
+                if let Some(hash) = self.finalized_block_hashes.get(&number) {
+                    hash.to_owned()
+                } else {
+                    let hash = self
+                        .read::<H256>("blocks", &format!("{number}"))
+                        .filter(|_| self.is_finalized(Some(number)))?;
+                    self.finalized_block_hashes.insert(number, hash);
+                    hash
+                }
+            }
+            BlockId::Number(_) => return None,
+        };
+
+        if let Some(block) = self.blocks.get(&hash) {
+            return Some(block.to_owned());
+        }
+
+        let block = self
+            .read::<BlockWithTransactionReceipts<Transaction>>("blocks", &format!("{hash:#x}"))?;
+        self.blocks.put(hash, block.to_owned());
+        Some(block)
+    }
+
+    /// Caches `block` if all of its receipts have been fetched.
+    pub fn put_block(&mut self, block: &BlockWithTransactionReceipts<Transaction>) {
+        let Some(hash) = block.block.hash else {
+            return;
+        };
+        let is_complete = block
+            .transaction_receipts
+            .as_ref()
+            .is_some_and(|receipts| receipts.len() == block.block.transactions.len());
+        if !is_complete {
+            return;
+        }
+
+        for transaction in &block.block.transactions {
+            self.put_transaction(transaction);
+        }
+        for receipt in block.transaction_receipts.iter().flatten() {
+            self.put_receipt(receipt);
+        }
+
+        if self.is_finalized(block.block.number) {
+            if let Some(number) = block.block.number {
+                self.finalized_block_hashes.insert(number, hash);
+                self.write("blocks", &format!("{number}"), &hash);
+            }
+            self.write("blocks", &format!("{hash:#x}"), block);
+        }
+        self.blocks.put(hash, block.to_owned());
+    }
+
+    pub fn get_transaction(&mut self, transaction_hash: TxHash) -> Option<Transaction> {
+        if let Some(transaction) = self.transactions.get(&transaction_hash) {
+            return Some(transaction.to_owned());
+        }
+
+        let transaction =
+            self.read::<Transaction>("transactions", &format!("{transaction_hash:#x}"))?;
+        self.transactions
+            .put(transaction_hash, transaction.to_owned());
+        Some(transaction)
+    }
+
+    /// Caches `transaction` if it has been mined. Pending ones change once they are.
+    pub fn put_transaction(&mut self, transaction: &Transaction) {
+        if transaction.block_hash.is_none() || transaction.block_number.is_none() {
+            return;
+        }
+        if self.is_finalized(transaction.block_number) {
+            self.write(
+                "transactions",
+                &format!("{:#x}", transaction.hash),
+                transaction,
+            );
+        }
+        self.transactions
+            .put(transaction.hash, transaction.to_owned());
+    }
+
+    pub fn get_receipt(&mut self, transaction_hash: TxHash) -> Option<TransactionReceipt> {
+        if let Some(receipt) = self.receipts.get(&transaction_hash) {
+            return Some(receipt.to_owned());
+        }
+
+        let receipt =
+            self.read::<TransactionReceipt>("receipts", &format!("{transaction_hash:#x}"))?;
+        self.receipts.put(transaction_hash, receipt.to_owned());
+        Some(receipt)
+    }
+
+    /// Caches `receipt` if its transaction has been mined.
+    pub fn put_receipt(&mut self, receipt: &TransactionReceipt) {
+        if receipt.block_hash.is_none() || receipt.block_number.is_none() {
+            return;
+        }
+        if self.is_finalized(receipt.block_number) {
+            self.write(
+                "receipts",
+                &format!("{:#x}", receipt.transaction_hash),
+                receipt,
+            );
+        }
+        self.receipts
+            .put(receipt.transaction_hash, receipt.to_owned());
+    }
+
+    pub fn get_ens_id(&mut self, address: Address) -> Option<Option<String>> {
+        self.ens_ids.get(&address).map(|ens_id| ens_id.to_owned())
+    }
+
+    pub fn put_ens_id(&mut self, address: Address, ens_id: Option<String>) {
+        self.ens_ids.put(address, ens_id);
+    }
+
+    pub fn get_abi(&mut self, address: Address) -> Option<Option<ContractAbi>> {
+        if let Some(abi) = self.abis.get(&address) {
+            return Some(abi.to_owned());
+        }
+
+        let abi = self.read::<ContractAbi>("abis", &format!("{address:#x}"))?;
+        self.abis.put(address, Some(abi.to_owned()));
+        Some(Some(abi))
+    }
+
+    pub fn put_abi(&mut self, address: Address, abi: Option<ContractAbi>) {
+        if let Some(abi) = abi.as_ref() {
+            self.write("abis", &format!("{address:#x}"), abi);
+        }
+        self.abis.put(address, abi);
+    }
+
+    pub fn get_proxy(&mut self, address: Address) -> Option<Option<Proxy>> {
+        self.proxies.get(&address).map(|proxy| proxy.to_owned())
+    }
+
+    pub fn put_proxy(&mut self, address: Address, proxy: Option<Proxy>) {
+        self.proxies.put(address, proxy);
+    }
+
+    pub fn get_decimals(&self, token: Address) -> Option<u8> {
+        self.decimals.get(&token).copied()
+    }
+
+    pub fn put_decimals(&mut self, token: Address, decimals: u8) {
+        self.decimals.insert(token, decimals);
+    }
+
+    fn read<T: DeserializeOwned>(&self, kind: &str, key: &str) -> Option<T> {
+        let path = self.dir.as_ref()?.join(kind).join(format!("{key}.json"));
+        let buffer = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&buffer).ok()
+    }
+
+    fn write<T: Serialize>(&self, kind: &str, key: &str, value: &T) {
+        if let Some(dir) = self.dir.as_ref() {
+            let path = dir.join(kind).join(format!("{key}.json"));
+            if let Err(e) = serde_json::to_string(value)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| Ok(fs::write(&path, json)?))
+            {
+                debug!("Failed to write {}: {e}", path.display());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(number: u64) -> Transaction {
+        Transaction {
+            hash: H256::from_low_u64_be(number),
+            block_hash: Some(H256::from_low_u64_be(number)),
+            block_number: Some(number.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn skips_pending_transactions_and_receipts() {
+        let mut cache = Cache::new(None);
+        let pending = Transaction {
+            block_hash: None,
+            block_number: None,
+            ..transaction(1)
+        };
+        cache.put_transaction(&pending);
+        cache.put_receipt(&TransactionReceipt {
+            transaction_hash: pending.hash,
+            ..Default::default()
+        });
+        assert_eq!(cache.get_transaction(pending.hash), None);
+        assert_eq!(cache.get_receipt(pending.hash), None);
+
+        cache.put_transaction(&transaction(1));
+        assert_eq!(cache.get_transaction(pending.hash), Some(transaction(1)));
+    }
+
+    #[test]
+    fn keeps_finalized_transactions_across_restarts() {
+        let dir = std::env::temp_dir().join(format!("lazy-etherscan-{}-cache", std::process::id()));
+        {
+            let mut cache = Cache::new(Some(dir.to_owned()));
+            cache.set_last_finalized_block(1.into());
+            cache.put_transaction(&transaction(1));
+            cache.put_transaction(&transaction(2));
+        }
+
+        let mut cache = Cache::new(Some(dir.to_owned()));
+        assert_eq!(
+            cache.get_transaction(H256::from_low_u64_be(1)),
+            Some(transaction(1))
+        );
+        assert_eq!(cache.get_transaction(H256::from_low_u64_be(2)), None);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
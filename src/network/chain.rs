@@ -0,0 +1,200 @@
+use crate::{
+    config::ChainConfig,
+    ethers::{storage::StorageLayout, types::ERC20Token},
+    network::backend::RpcBackend,
+};
+use anyhow::{Context, Result};
+use ethers::{
+    core::types::{Address, Chain},
+    etherscan::Client,
+};
+use log::warn;
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, fs, path::PathBuf};
+
+/// An Etherscan-compatible explorer API, e.g. Etherscan, Blockscout or Routescan.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExplorerConfig {
+    /// Defaults to the chain's Etherscan API.
+    pub api_url: Option<String>,
+    /// Defaults to the chain's API key environment variable, e.g. `ETHERSCAN_API_KEY`.
+    pub api_key: Option<String>,
+}
+
+/// Whether explorer features (statistics, contract source code and ABIs) work.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExplorerStatus {
+    Enabled,
+    /// No explorer is configured for the chain, or it needs an API key that isn't set.
+    Disabled,
+    Failing(String),
+}
+
+impl fmt::Display for ExplorerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExplorerStatus::Enabled => write!(f, "Enabled"),
+            ExplorerStatus::Disabled => write!(f, "Disabled (no API key)"),
+            ExplorerStatus::Failing(message) => write!(f, "Failing: {message}"),
+        }
+    }
+}
+
+/// The chain the endpoint serves, detected with `eth_chainId`.
+#[derive(Clone, Debug)]
+pub struct ChainContext {
+    pub id: u64,
+    /// `None` for chains ethers doesn't know, which have no explorer API unless one is set.
+    pub chain: Option<Chain>,
+    pub explorer: ExplorerConfig,
+    pub token_list: Option<PathBuf>,
+    pub tokens: Vec<ERC20Token>,
+    pub storage_layouts: HashMap<Address, PathBuf>,
+}
+
+impl Default for ChainContext {
+    fn default() -> Self {
+        Self::new(Chain::Mainnet as u64)
+    }
+}
+
+impl ChainContext {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id,
+            chain: Chain::try_from(id).ok(),
+            explorer: ExplorerConfig::default(),
+            token_list: None,
+            tokens: vec![],
+            storage_layouts: HashMap::new(),
+        }
+    }
+
+    pub fn with_config(self, config: ChainConfig) -> Self {
+        Self {
+            explorer: config.explorer,
+            token_list: config.token_list,
+            tokens: config.tokens,
+            storage_layouts: config.storage_layouts,
+            ..self
+        }
+    }
+
+    /// Asks the endpoint for its chain ID.
+    pub async fn detect(backend: &dyn RpcBackend) -> Result<Self> {
+        Ok(Self::new(backend.get_chain_id().await?))
+    }
+
+    pub fn name(&self) -> String {
+        self.chain
+            .map_or(format!("chain {}", self.id), |chain| chain.to_string())
+    }
+
+    pub fn native_currency_symbol(&self) -> &'static str {
+        match self.chain {
+            Some(Chain::BinanceSmartChain | Chain::BinanceSmartChainTestnet) => "BNB",
+            Some(
+                Chain::Polygon
+                | Chain::PolygonMumbai
+                | Chain::PolygonAmoy
+                | Chain::PolygonZkEvm
+                | Chain::PolygonZkEvmTestnet,
+            ) => "MATIC",
+            Some(Chain::Avalanche | Chain::AvalancheFuji) => "AVAX",
+            Some(Chain::Fantom | Chain::FantomTestnet) => "FTM",
+            Some(Chain::Gnosis | Chain::Chiado) => "xDAI",
+            Some(Chain::Celo | Chain::CeloAlfajores | Chain::CeloBaklava) => "CELO",
+            Some(Chain::Cronos | Chain::CronosTestnet) => "CRO",
+            Some(Chain::Moonbeam) => "GLMR",
+            Some(Chain::Moonriver) => "MOVR",
+            Some(Chain::Mantle | Chain::MantleTestnet) => "MNT",
+            Some(Chain::Metis) => "METIS",
+            _ => "ETH",
+        }
+    }
+
+    /// A client for the configured explorer, or the chain's Etherscan. `None` if there's no
+    /// explorer for the chain or it needs an API key that isn't set.
+    pub fn etherscan_client(&self) -> Result<Option<Client>> {
+        let api_key = self.explorer.api_key.to_owned().or_else(|| {
+            self.chain
+                .and_then(|chain| chain.etherscan_api_key_name())
+                .and_then(|name| std::env::var(name).ok())
+        });
+
+        let builder = match (self.explorer.api_url.as_ref(), self.chain) {
+            (Some(api_url), chain) => {
+                // The web URL is only used for links, so the API URL will do on other chains.
+                let url = chain
+                    .and_then(|chain| chain.etherscan_urls())
+                    .map_or(api_url.as_str(), |(_, url)| url);
+                Client::builder()
+                    .with_api_url(api_url.as_str())
+                    .and_then(|builder| builder.with_url(url))
+                    .with_context(|| format!("Invalid explorer API URL: {api_url}"))?
+            }
+            (None, Some(chain)) if chain.etherscan_urls().is_some() && api_key.is_some() => {
+                Client::builder().chain(chain)?
+            }
+            (None, _) => return Ok(None),
+        };
+
+        let builder = match api_key {
+            Some(api_key) => builder.with_api_key(api_key),
+            None => builder,
+        };
+        Ok(Some(builder.build()?))
+    }
+
+    /// What explorer features can be expected to do before any request is made.
+    pub fn explorer_status(&self) -> ExplorerStatus {
+        match self.etherscan_client() {
+            Ok(Some(_)) => ExplorerStatus::Enabled,
+            Ok(None) => ExplorerStatus::Disabled,
+            Err(e) => ExplorerStatus::Failing(format!("{e:#}")),
+        }
+    }
+
+    /// The configured token list, or `data/tokens.json` on mainnet and
+    /// `data/tokens/<chain ID>.json` elsewhere.
+    pub fn token_list_path(&self) -> PathBuf {
+        if let Some(token_list) = self.token_list.as_ref() {
+            token_list.to_owned()
+        } else if self.chain == Some(Chain::Mainnet) {
+            PathBuf::from("./data/tokens.json")
+        } else {
+            PathBuf::from(format!("./data/tokens/{}.json", self.id))
+        }
+    }
+
+    /// The tokens in the token list, with the configured ones in place of any entries for the
+    /// same contract.
+    pub fn erc20_tokens(&self) -> Vec<ERC20Token> {
+        let mut tokens = fs::read_to_string(self.token_list_path())
+            .ok()
+            .and_then(|json| serde_json::from_str::<Vec<ERC20Token>>(&json).ok())
+            .unwrap_or_default();
+        tokens.retain(|token| {
+            self.tokens
+                .iter()
+                .all(|added| added.contract_address != token.contract_address)
+        });
+        tokens.extend(self.tokens.iter().cloned());
+        tokens
+    }
+
+    /// The configured storage layouts, by contract. Those that can't be read are skipped.
+    pub fn load_storage_layouts(&self) -> HashMap<Address, StorageLayout> {
+        self.storage_layouts
+            .iter()
+            .filter_map(|(address, path)| match StorageLayout::load(path) {
+                Ok(layout) => Some((*address, layout)),
+                Err(e) => {
+                    warn!("{e:#}");
+                    None
+                }
+            })
+            .collect()
+    }
+}
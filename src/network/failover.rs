@@ -0,0 +1,339 @@
+use crate::network::{
+    backend::{self, BatchRequests, NewHeads, RpcBackend},
+    scheduler::{is_final_answer, ScheduledBackend, SchedulerConfig},
+};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ethers::core::types::{
+    Address, Block, BlockId, Bytes, Transaction, TransactionReceipt, TxHash, H256, U256, U64,
+};
+use futures::{stream::BoxStream, Future};
+use log::warn;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex, OnceCell};
+use url::Url;
+
+/// How long a failing endpoint is skipped before it's tried again.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+struct Endpoint {
+    url: String,
+    backend: OnceCell<Arc<dyn RpcBackend>>,
+    unhealthy_until: Mutex<Option<Instant>>,
+}
+
+impl Endpoint {
+    async fn is_healthy(&self) -> bool {
+        self.unhealthy_until
+            .lock()
+            .await
+            .is_none_or(|until| Instant::now() >= until)
+    }
+}
+
+/// Sends every request to the active endpoint and fails over to the next healthy one when it
+/// can't be reached or keeps failing after the scheduler's retries.
+///
+/// Each endpoint is connected lazily and has its own scheduler, since rate limits are per
+/// provider.
+pub struct FailoverBackend {
+    endpoints: Vec<Endpoint>,
+    active: AtomicUsize,
+    scheduler_config: SchedulerConfig,
+    failed_requests: AtomicUsize,
+}
+
+impl FailoverBackend {
+    pub fn new(endpoints: &[String], scheduler_config: SchedulerConfig) -> Self {
+        Self {
+            endpoints: endpoints
+                .iter()
+                .map(|url| Endpoint {
+                    url: url.to_owned(),
+                    backend: OnceCell::new(),
+                    unhealthy_until: Mutex::new(None),
+                })
+                .collect(),
+            active: AtomicUsize::new(0),
+            scheduler_config,
+            failed_requests: AtomicUsize::new(0),
+        }
+    }
+
+    async fn connect(&self, index: usize) -> Result<&Arc<dyn RpcBackend>> {
+        let endpoint = &self.endpoints[index];
+        endpoint
+            .backend
+            .get_or_try_init(|| async {
+                let backend = backend::connect(&endpoint.url).await?;
+                Ok::<_, anyhow::Error>(Arc::new(ScheduledBackend::new(
+                    backend,
+                    self.scheduler_config,
+                )) as Arc<dyn RpcBackend>)
+            })
+            .await
+    }
+
+    /// Endpoint indices in the order they should be tried: healthy ones first, starting from
+    /// the active endpoint.
+    async fn candidates(&self) -> Vec<usize> {
+        let active = self.active.load(Ordering::Relaxed);
+        let mut healthy = vec![];
+        let mut unhealthy = vec![];
+        for i in 0..self.endpoints.len() {
+            let index = (active + i) % self.endpoints.len();
+            if self.endpoints[index].is_healthy().await {
+                healthy.push(index);
+            } else {
+                unhealthy.push(index);
+            }
+        }
+        healthy.append(&mut unhealthy);
+        healthy
+    }
+
+    async fn mark_unhealthy(&self, index: usize, e: &anyhow::Error) {
+        warn!("{} is unhealthy: {e:#}", self.endpoints[index].url);
+        *self.endpoints[index].unhealthy_until.lock().await =
+            Some(Instant::now() + UNHEALTHY_COOLDOWN);
+    }
+
+    async fn request<'a, T, F, Fut>(&'a self, request: F) -> Result<T>
+    where
+        F: Fn(&'a Arc<dyn RpcBackend>) -> Fut + Send,
+        Fut: Future<Output = Result<T>> + Send,
+    {
+        let mut last_error = anyhow!("No endpoints are configured");
+        for index in self.candidates().await {
+            let endpoint = &self.endpoints[index];
+            let backend = match self.connect(index).await {
+                Ok(backend) => backend,
+                Err(e) => {
+                    self.mark_unhealthy(index, &e).await;
+                    last_error = e;
+                    continue;
+                }
+            };
+
+            match request(backend).await {
+                Ok(res) => {
+                    *endpoint.unhealthy_until.lock().await = None;
+                    self.active.store(index, Ordering::Relaxed);
+                    return Ok(res);
+                }
+                // The node answered; another one would most likely answer the same.
+                Err(e) if is_final_answer(&e) => {
+                    return Err(e);
+                }
+                Err(e) => {
+                    self.mark_unhealthy(index, &e).await;
+                    last_error = e;
+                }
+            }
+        }
+
+        self.failed_requests.fetch_add(1, Ordering::Relaxed);
+        Err(last_error)
+    }
+}
+
+#[async_trait]
+impl NewHeads for FailoverBackend {
+    /// Subscribes on the first healthy endpoint that accepts the subscription, which becomes
+    /// the active one.
+    async fn subscribe_new_heads(&self) -> Result<Option<BoxStream<'_, Block<TxHash>>>> {
+        let mut last_error = anyhow!("No endpoints are configured");
+        for index in self.candidates().await {
+            let res = match self.connect(index).await {
+                Ok(backend) => backend.subscribe_new_heads().await,
+                Err(e) => Err(e),
+            };
+            match res {
+                Ok(heads) => {
+                    *self.endpoints[index].unhealthy_until.lock().await = None;
+                    self.active.store(index, Ordering::Relaxed);
+                    return Ok(heads);
+                }
+                Err(e) => {
+                    self.mark_unhealthy(index, &e).await;
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+#[async_trait]
+impl BatchRequests for FailoverBackend {
+    async fn get_transaction_receipts_batch(
+        &self,
+        transaction_hashes: &[TxHash],
+    ) -> Result<Vec<Option<TransactionReceipt>>> {
+        self.request(|backend| backend.get_transaction_receipts_batch(transaction_hashes))
+            .await
+    }
+
+    async fn call_batch(&self, calls: &[(Address, Bytes)]) -> Result<Vec<Option<Bytes>>> {
+        self.request(|backend| backend.call_batch(calls)).await
+    }
+}
+
+#[async_trait]
+impl RpcBackend for FailoverBackend {
+    async fn get_chain_id(&self) -> Result<u64> {
+        self.request(|backend| backend.get_chain_id()).await
+    }
+
+    async fn get_block_number(&self) -> Result<U64> {
+        self.request(|backend| backend.get_block_number()).await
+    }
+
+    async fn get_block_with_txs(&self, block_id: BlockId) -> Result<Option<Block<Transaction>>> {
+        self.request(|backend| backend.get_block_with_txs(block_id))
+            .await
+    }
+
+    async fn get_transaction(&self, transaction_hash: TxHash) -> Result<Option<Transaction>> {
+        self.request(|backend| backend.get_transaction(transaction_hash))
+            .await
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>> {
+        self.request(|backend| backend.get_transaction_receipt(transaction_hash))
+            .await
+    }
+
+    async fn get_block_receipts(&self, block_id: BlockId) -> Result<Vec<TransactionReceipt>> {
+        self.request(|backend| backend.get_block_receipts(block_id))
+            .await
+    }
+
+    async fn get_balance(&self, address: Address, block_id: Option<BlockId>) -> Result<U256> {
+        self.request(|backend| backend.get_balance(address, block_id))
+            .await
+    }
+
+    async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> Result<Bytes> {
+        self.request(|backend| backend.get_code(address, block_id))
+            .await
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: H256,
+        block_id: Option<BlockId>,
+    ) -> Result<H256> {
+        self.request(|backend| backend.get_storage_at(address, slot, block_id))
+            .await
+    }
+
+    async fn call(&self, to: Address, data: Bytes) -> Result<Bytes> {
+        self.request(|backend| backend.call(to, data.to_owned()))
+            .await
+    }
+
+    async fn lookup_address(&self, address: Address) -> Result<String> {
+        self.request(|backend| backend.lookup_address(address))
+            .await
+    }
+
+    async fn resolve_name(&self, ens_id: &str) -> Result<Address> {
+        self.request(|backend| backend.resolve_name(ens_id)).await
+    }
+
+    async fn resolve_avatar(&self, ens_id: &str) -> Result<Url> {
+        self.request(|backend| backend.resolve_avatar(ens_id)).await
+    }
+
+    fn failed_requests(&self) -> usize {
+        self.failed_requests.load(Ordering::Relaxed)
+    }
+
+    fn endpoint(&self) -> Option<String> {
+        Some(
+            self.endpoints[self.active.load(Ordering::Relaxed)]
+                .url
+                .to_owned(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::fake::{error_response, FakeBackend};
+
+    /// Fails over between `fakes`, which are already connected, without schedulers.
+    fn failover(fakes: &[Arc<FakeBackend>]) -> FailoverBackend {
+        let mut backend = FailoverBackend::new(&[], SchedulerConfig::default());
+        backend.endpoints = fakes
+            .iter()
+            .enumerate()
+            .map(|(i, fake)| Endpoint {
+                url: format!("endpoint {i}"),
+                backend: OnceCell::new_with(Some(fake.to_owned() as Arc<dyn RpcBackend>)),
+                unhealthy_until: Mutex::new(None),
+            })
+            .collect();
+        backend
+    }
+
+    fn fake() -> Arc<FakeBackend> {
+        let fake = Arc::new(FakeBackend::new());
+        fake.mine(vec![]);
+        fake
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_the_next_endpoint() {
+        let fakes = [fake(), fake()];
+        fakes[0].fail_next(anyhow!("connection refused"));
+        let backend = failover(&fakes);
+
+        assert!(backend.get_block_number().await.is_ok());
+        assert_eq!(backend.endpoint().as_deref(), Some("endpoint 1"));
+        assert_eq!(backend.failed_requests(), 0);
+
+        // The failed endpoint is skipped while it cools down.
+        assert!(backend.get_block_number().await.is_ok());
+        assert_eq!(fakes[0].requests(), 1);
+        assert_eq!(fakes[1].requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn returns_final_answers_without_failing_over() {
+        let fakes = [fake(), fake()];
+        fakes[0].fail_next(error_response(3));
+        let backend = failover(&fakes);
+
+        assert!(backend.get_block_number().await.is_err());
+        assert_eq!(backend.endpoint().as_deref(), Some("endpoint 0"));
+        assert_eq!(fakes[1].requests(), 0);
+        assert_eq!(backend.failed_requests(), 0);
+    }
+
+    #[tokio::test]
+    async fn tries_unhealthy_endpoints_when_all_are() {
+        let fakes = [fake(), fake()];
+        fakes[0].fail_next(anyhow!("connection refused"));
+        fakes[1].fail_next(anyhow!("connection refused"));
+        let backend = failover(&fakes);
+
+        assert!(backend.get_block_number().await.is_err());
+        assert_eq!(backend.failed_requests(), 1);
+
+        assert!(backend.get_block_number().await.is_ok());
+        assert_eq!(fakes[0].requests() + fakes[1].requests(), 3);
+    }
+}
@@ -0,0 +1,358 @@
+use super::backend::{BatchRequests, NewHeads, RpcBackend};
+use anyhow::{anyhow, bail, Result};
+use async_trait::async_trait;
+use ethers::{
+    core::{
+        types::{
+            Address, Block, BlockId, BlockNumber, Bytes, Log, Transaction, TransactionReceipt,
+            TxHash, H256, U256, U64,
+        },
+        utils::keccak256,
+    },
+    providers::{HttpClientError, JsonRpcError, ProviderError},
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, MutexGuard,
+    },
+};
+use url::Url;
+
+/// The chain ID the fake answers with, that of a local development node.
+pub const CHAIN_ID: u64 = 31337;
+
+/// A node kept in memory, to test `Network` and the backend layers without one.
+///
+/// It serves the blocks mined with `mine`, along with their receipts, and the code, storage and
+/// call results it's given. Errors queued with `fail_next` are returned by the next requests,
+/// whatever they are.
+#[derive(Default)]
+pub struct FakeBackend {
+    state: Mutex<State>,
+    requests: AtomicUsize,
+}
+
+#[derive(Default)]
+struct State {
+    /// The canonical chain, indexed by block number.
+    blocks: Vec<Block<Transaction>>,
+    /// Receipts of every transaction ever mined, including reorged ones.
+    receipts: HashMap<TxHash, TransactionReceipt>,
+    code: HashMap<Address, Bytes>,
+    storage: HashMap<(Address, H256), H256>,
+    calls: HashMap<(Address, Bytes), Bytes>,
+    errors: VecDeque<anyhow::Error>,
+    /// Blocks whose next request fails.
+    failing_blocks: HashSet<U64>,
+    /// Methods answered with "method not found". `"batch"` stands for batch requests.
+    unsupported: HashSet<&'static str>,
+    /// Incremented on every block mined, so a block replacing another gets a new hash.
+    nonce: u64,
+}
+
+impl FakeBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many requests have been sent to it.
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Appends a block with `transactions` to the chain, and returns it. Their block fields are
+    /// filled in, and each gets a successful receipt.
+    pub fn mine(&self, transactions: Vec<Transaction>) -> Block<Transaction> {
+        let mut state = self.state.lock().unwrap();
+        state.nonce += 1;
+        let number = U64::from(state.blocks.len());
+        let hash = H256::from_low_u64_be(state.nonce);
+
+        let transactions = transactions
+            .into_iter()
+            .enumerate()
+            .map(|(i, tx)| Transaction {
+                block_hash: Some(hash),
+                block_number: Some(number),
+                transaction_index: Some(i.into()),
+                ..tx
+            })
+            .collect::<Vec<_>>();
+        for tx in transactions.iter() {
+            let logs = state
+                .receipts
+                .get(&tx.hash)
+                .map_or(vec![], |receipt| receipt.logs.to_owned());
+            let receipt = TransactionReceipt {
+                transaction_hash: tx.hash,
+                transaction_index: tx.transaction_index.unwrap_or_default(),
+                block_hash: tx.block_hash,
+                block_number: tx.block_number,
+                from: tx.from,
+                to: tx.to,
+                gas_used: Some(tx.gas),
+                status: Some(1.into()),
+                logs,
+                ..Default::default()
+            };
+            state.receipts.insert(tx.hash, receipt);
+        }
+
+        let block = Block {
+            hash: Some(hash),
+            parent_hash: state
+                .blocks
+                .last()
+                .and_then(|block| block.hash)
+                .unwrap_or_default(),
+            number: Some(number),
+            timestamp: U256::from(12) * number.as_u64(),
+            transactions,
+            ..Default::default()
+        };
+        state.blocks.push(block.to_owned());
+        block
+    }
+
+    /// Drops the blocks from `number` on, so that blocks mined next replace them.
+    pub fn reorg(&self, number: u64) {
+        self.state.lock().unwrap().blocks.truncate(number as usize);
+    }
+
+    /// Makes the receipt of `transaction_hash` have `logs`, now and when it's mined again.
+    pub fn set_logs(&self, transaction_hash: TxHash, logs: Vec<Log>) {
+        let mut state = self.state.lock().unwrap();
+        let receipt = state.receipts.entry(transaction_hash).or_default();
+        receipt.transaction_hash = transaction_hash;
+        receipt.logs = logs;
+    }
+
+    pub fn set_code(&self, address: Address, code: Bytes) {
+        self.state.lock().unwrap().code.insert(address, code);
+    }
+
+    pub fn set_storage(&self, address: Address, slot: H256, value: H256) {
+        self.state
+            .lock()
+            .unwrap()
+            .storage
+            .insert((address, slot), value);
+    }
+
+    /// Makes the next request fail with `e`, or the one after if one is already queued.
+    pub fn fail_next(&self, e: anyhow::Error) {
+        self.state.lock().unwrap().errors.push_back(e);
+    }
+
+    /// Makes the next request for block `number` fail.
+    pub fn fail_block(&self, number: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .failing_blocks
+            .insert(number.into());
+    }
+
+    /// Makes `method` fail as it does on nodes without it. `"batch"` disables batch requests.
+    pub fn unsupport(&self, method: &'static str) {
+        self.state.lock().unwrap().unsupported.insert(method);
+    }
+
+    /// Counts a request for `method`, and returns the error it should fail with, if any.
+    fn request(&self, method: &'static str) -> Result<()> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let mut state = self.state.lock().unwrap();
+        if let Some(e) = state.errors.pop_front() {
+            return Err(e);
+        }
+        if state.unsupported.contains(method) {
+            return Err(if method == "batch" {
+                ProviderError::UnsupportedRPC.into()
+            } else {
+                error_response(-32601)
+            });
+        }
+        Ok(())
+    }
+
+    /// Answers a request for `method` from the state of the chain.
+    fn answer(&self, method: &'static str) -> Result<MutexGuard<'_, State>> {
+        self.request(method)?;
+        Ok(self.state.lock().unwrap())
+    }
+}
+
+impl State {
+    fn block(&self, block_id: BlockId) -> Option<&Block<Transaction>> {
+        match block_id {
+            BlockId::Hash(hash) => self.blocks.iter().find(|block| block.hash == Some(hash)),
+            BlockId::Number(BlockNumber::Number(number)) => self.blocks.get(number.as_usize()),
+            BlockId::Number(BlockNumber::Earliest) => self.blocks.first(),
+            BlockId::Number(_) => self.blocks.last(),
+        }
+    }
+
+    /// The receipt of `transaction_hash` if it's in the canonical chain.
+    fn receipt(&self, transaction_hash: TxHash) -> Option<TransactionReceipt> {
+        let receipt = self.receipts.get(&transaction_hash)?;
+        let number = receipt.block_number?;
+        let block = self.blocks.get(number.as_usize())?;
+        (block.hash == receipt.block_hash).then(|| receipt.to_owned())
+    }
+}
+
+/// The error a node answers with when a request fails with JSON-RPC error `code`.
+pub fn error_response(code: i64) -> anyhow::Error {
+    ProviderError::from(HttpClientError::JsonRpcError(JsonRpcError {
+        code,
+        message: "error".to_owned(),
+        data: None,
+    }))
+    .into()
+}
+
+/// A transaction from `from` with `nonce` calling `to` with `input`, not yet mined.
+pub fn transaction(from: Address, nonce: u64, to: Option<Address>, input: Bytes) -> Transaction {
+    let mut preimage = from.as_bytes().to_vec();
+    preimage.extend_from_slice(&nonce.to_be_bytes());
+    Transaction {
+        hash: keccak256(preimage).into(),
+        from,
+        nonce: nonce.into(),
+        to,
+        input,
+        gas: 21000.into(),
+        ..Default::default()
+    }
+}
+
+impl NewHeads for FakeBackend {}
+
+#[async_trait]
+impl BatchRequests for FakeBackend {
+    async fn get_transaction_receipts_batch(
+        &self,
+        transaction_hashes: &[TxHash],
+    ) -> Result<Vec<Option<TransactionReceipt>>> {
+        let state = self.answer("batch")?;
+        Ok(transaction_hashes
+            .iter()
+            .map(|transaction_hash| state.receipt(*transaction_hash))
+            .collect())
+    }
+
+    async fn call_batch(&self, calls: &[(Address, Bytes)]) -> Result<Vec<Option<Bytes>>> {
+        let state = self.answer("batch")?;
+        Ok(calls
+            .iter()
+            .map(|call| state.calls.get(call).cloned())
+            .collect())
+    }
+}
+
+#[async_trait]
+impl RpcBackend for FakeBackend {
+    async fn get_chain_id(&self) -> Result<u64> {
+        self.request("eth_chainId")?;
+        Ok(CHAIN_ID)
+    }
+
+    async fn get_block_number(&self) -> Result<U64> {
+        let state = self.answer("eth_blockNumber")?;
+        state
+            .blocks
+            .last()
+            .and_then(|block| block.number)
+            .ok_or_else(|| anyhow!("no block has been mined"))
+    }
+
+    async fn get_block_with_txs(&self, block_id: BlockId) -> Result<Option<Block<Transaction>>> {
+        let mut state = self.answer("eth_getBlockByNumber")?;
+        let block = state.block(block_id).cloned();
+        if let Some(number) = block.as_ref().and_then(|block| block.number) {
+            if state.failing_blocks.remove(&number) {
+                bail!("block {number} is unavailable");
+            }
+        }
+        Ok(block)
+    }
+
+    async fn get_transaction(&self, transaction_hash: TxHash) -> Result<Option<Transaction>> {
+        let state = self.answer("eth_getTransactionByHash")?;
+        Ok(state
+            .blocks
+            .iter()
+            .flat_map(|block| block.transactions.iter())
+            .find(|tx| tx.hash == transaction_hash)
+            .cloned())
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>> {
+        let state = self.answer("eth_getTransactionReceipt")?;
+        Ok(state.receipt(transaction_hash))
+    }
+
+    async fn get_block_receipts(&self, block_id: BlockId) -> Result<Vec<TransactionReceipt>> {
+        let state = self.answer("eth_getBlockReceipts")?;
+        let Some(block) = state.block(block_id) else {
+            return Ok(vec![]);
+        };
+        Ok(block
+            .transactions
+            .iter()
+            .filter_map(|tx| state.receipt(tx.hash))
+            .collect())
+    }
+
+    async fn get_balance(&self, _address: Address, _block_id: Option<BlockId>) -> Result<U256> {
+        self.request("eth_getBalance")?;
+        Ok(U256::zero())
+    }
+
+    async fn get_code(&self, address: Address, _block_id: Option<BlockId>) -> Result<Bytes> {
+        let state = self.answer("eth_getCode")?;
+        Ok(state.code.get(&address).cloned().unwrap_or_default())
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: H256,
+        _block_id: Option<BlockId>,
+    ) -> Result<H256> {
+        let state = self.answer("eth_getStorageAt")?;
+        Ok(state
+            .storage
+            .get(&(address, slot))
+            .copied()
+            .unwrap_or_default())
+    }
+
+    async fn call(&self, to: Address, data: Bytes) -> Result<Bytes> {
+        let state = self.answer("eth_call")?;
+        match state.calls.get(&(to, data)) {
+            Some(output) => Ok(output.to_owned()),
+            None => Err(error_response(3)),
+        }
+    }
+
+    async fn lookup_address(&self, address: Address) -> Result<String> {
+        self.request("eth_call")?;
+        Err(ProviderError::EnsError(format!("{address:?}")).into())
+    }
+
+    async fn resolve_name(&self, ens_id: &str) -> Result<Address> {
+        self.request("eth_call")?;
+        Err(ProviderError::EnsError(ens_id.to_owned()).into())
+    }
+
+    async fn resolve_avatar(&self, ens_id: &str) -> Result<Url> {
+        self.request("eth_call")?;
+        Err(ProviderError::EnsError(ens_id.to_owned()).into())
+    }
+}
@@ -0,0 +1,557 @@
+use crate::network::backend::{
+    is_ens_not_found, is_error_response, is_unsupported, BatchRequests, NewHeads, RpcBackend,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use ethers::{
+    core::types::{
+        Address, Block, BlockId, Bytes, Transaction, TransactionReceipt, TxHash, H256, U256, U64,
+    },
+    providers::{HttpClientError, JsonRpcError, ProviderError},
+};
+use futures::{stream::BoxStream, Future};
+use log::debug;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex, Semaphore};
+use url::Url;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(16);
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SchedulerConfig {
+    /// Sustained request rate, which must be positive. Bursts of up to one second's worth of
+    /// requests are allowed.
+    pub requests_per_second: f64,
+    pub max_concurrency: usize,
+    /// How many times a rate-limited or failed (5xx) request is retried before giving up.
+    pub max_retries: u32,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 20.0,
+            max_concurrency: 16,
+            max_retries: 5,
+        }
+    }
+}
+
+/// Whether `requests_per_second` is a rate the token bucket can refill at.
+pub fn is_positive(requests_per_second: f64) -> bool {
+    requests_per_second.is_finite() && requests_per_second > 0.0
+}
+
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_rate: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests_per_second: f64) -> Self {
+        let capacity = requests_per_second.max(1.0);
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_rate: requests_per_second,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes `n` tokens, at most `capacity` of them, or returns how long to wait until they're
+    /// available.
+    fn try_take(&mut self, n: f64) -> Option<Duration> {
+        let now = Instant::now();
+        self.tokens = (self.tokens + (now - self.last_refill).as_secs_f64() * self.refill_rate)
+            .min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= n {
+            self.tokens -= n;
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (n - self.tokens) / self.refill_rate,
+            ))
+        }
+    }
+}
+
+/// Which of the bulk receipt methods an endpoint supports, once it's known.
+#[derive(Default)]
+struct ReceiptsSupport {
+    block_receipts: Option<bool>,
+    batch: Option<bool>,
+}
+
+/// A token bucket shared by everything that sends requests to one API.
+pub struct RateLimiter {
+    bucket: Mutex<TokenBucket>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_second: f64) -> Self {
+        Self {
+            bucket: Mutex::new(TokenBucket::new(requests_per_second)),
+        }
+    }
+
+    /// Waits until a request may be sent.
+    pub async fn wait(&self) {
+        self.wait_for(1).await;
+    }
+
+    /// Waits until `n` requests may be sent, e.g. the calls in a batch. More than a bucketful
+    /// are taken a bucketful at a time.
+    pub async fn wait_for(&self, n: usize) {
+        let mut remaining = n as f64;
+        while remaining > 0.0 {
+            let (wait, taken) = {
+                let mut bucket = self.bucket.lock().await;
+                let taken = remaining.min(bucket.capacity);
+                (bucket.try_take(taken), taken)
+            };
+            match wait {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => remaining -= taken,
+            }
+        }
+    }
+}
+
+/// Wraps a backend so that every request goes through a token bucket and a concurrency cap,
+/// and rate-limited or 5xx responses are retried with exponential backoff.
+///
+/// It also remembers which bulk receipt methods the endpoint supports, so that unsupported ones
+/// fail without a request.
+pub struct ScheduledBackend {
+    inner: Arc<dyn RpcBackend>,
+    rate_limiter: RateLimiter,
+    semaphore: Semaphore,
+    max_retries: u32,
+    failed_requests: AtomicUsize,
+    receipts_support: Mutex<ReceiptsSupport>,
+}
+
+impl ScheduledBackend {
+    pub fn new(inner: Arc<dyn RpcBackend>, config: SchedulerConfig) -> Self {
+        Self {
+            inner,
+            rate_limiter: RateLimiter::new(config.requests_per_second),
+            semaphore: Semaphore::new(config.max_concurrency.max(1)),
+            max_retries: config.max_retries,
+            failed_requests: AtomicUsize::new(0),
+            receipts_support: Mutex::new(ReceiptsSupport::default()),
+        }
+    }
+
+    async fn schedule<T, F, Fut>(&self, request: F) -> Result<T>
+    where
+        F: Fn() -> Fut + Send,
+        Fut: Future<Output = Result<T>> + Send,
+    {
+        self.schedule_batch(1, request).await
+    }
+
+    /// Schedules a batch of `size` requests, which costs as many tokens as sending them one by
+    /// one would.
+    async fn schedule_batch<T, F, Fut>(&self, size: usize, request: F) -> Result<T>
+    where
+        F: Fn() -> Fut + Send,
+        Fut: Future<Output = Result<T>> + Send,
+    {
+        let mut backoff = INITIAL_BACKOFF;
+        let mut retries = 0;
+        loop {
+            // The permit is only held for the attempt, so that requests backing off don't hold
+            // up others.
+            let res = {
+                let _permit = self.semaphore.acquire().await?;
+                self.rate_limiter.wait_for(size.max(1)).await;
+                request().await
+            };
+
+            match res {
+                Ok(res) => return Ok(res),
+                Err(e) if retries < self.max_retries && is_retryable(&e) => {
+                    debug!("Retrying in {backoff:?}: {e}");
+                    tokio::time::sleep(backoff).await;
+                    backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+                    retries += 1;
+                }
+                Err(e) => {
+                    if !is_final_answer(&e) {
+                        self.failed_requests.fetch_add(1, Ordering::Relaxed);
+                    }
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+/// Records in `support` whether `res` shows the method it came from is supported.
+fn record_support<T>(support: &mut Option<bool>, res: &Result<T>) {
+    match res {
+        Ok(_) => *support = Some(true),
+        Err(e) if is_unsupported(e) => *support = Some(false),
+        Err(_) => {}
+    }
+}
+
+/// Whether `e` is a rate limit or a transient server-side failure, going by its HTTP status or
+/// JSON-RPC error code.
+pub fn is_retryable(e: &anyhow::Error) -> bool {
+    if let Some(e) = e.downcast_ref::<reqwest::Error>() {
+        return is_retryable_http_error(e);
+    }
+    match e.downcast_ref::<ProviderError>() {
+        Some(ProviderError::HTTPError(e)) => is_retryable_http_error(e),
+        Some(ProviderError::JsonRpcClientError(e)) => {
+            if let Some(response) = e.as_error_response() {
+                return is_rate_limit(response.code);
+            }
+            // Some endpoints send the error object without a valid response around it, e.g.
+            // without an `id`.
+            let e: &(dyn std::error::Error + Send + Sync + 'static) = e.as_ref();
+            match e.downcast_ref::<HttpClientError>() {
+                Some(HttpClientError::SerdeJson { text, .. }) => {
+                    serde_json::from_str::<ErrorResponse>(text)
+                        .is_ok_and(|response| is_rate_limit(response.error.code))
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Whether the node answered the request with `e`, so neither retrying it nor asking another
+/// node would help.
+pub fn is_final_answer(e: &anyhow::Error) -> bool {
+    is_ens_not_found(e) || is_unsupported(e) || (is_error_response(e) && !is_retryable(e))
+}
+
+fn is_retryable_http_error(e: &reqwest::Error) -> bool {
+    e.is_timeout()
+        || e.status().is_some_and(|status| {
+            status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        })
+}
+
+/// Some endpoints use 429 as a JSON-RPC error code too. -32005 is the "limit exceeded" code used
+/// by Infura, Alchemy and others.
+fn is_rate_limit(code: i64) -> bool {
+    matches!(code, 429 | -32005)
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: JsonRpcError,
+}
+
+#[async_trait]
+impl NewHeads for ScheduledBackend {
+    async fn subscribe_new_heads(&self) -> Result<Option<BoxStream<'_, Block<TxHash>>>> {
+        self.inner.subscribe_new_heads().await
+    }
+}
+
+#[async_trait]
+impl BatchRequests for ScheduledBackend {
+    async fn get_transaction_receipts_batch(
+        &self,
+        transaction_hashes: &[TxHash],
+    ) -> Result<Vec<Option<TransactionReceipt>>> {
+        if self.receipts_support.lock().await.batch == Some(false) {
+            return Err(ProviderError::UnsupportedRPC.into());
+        }
+        let res = self
+            .schedule_batch(transaction_hashes.len(), || {
+                self.inner
+                    .get_transaction_receipts_batch(transaction_hashes)
+            })
+            .await;
+        record_support(&mut self.receipts_support.lock().await.batch, &res);
+        res
+    }
+
+    async fn call_batch(&self, calls: &[(Address, Bytes)]) -> Result<Vec<Option<Bytes>>> {
+        self.schedule_batch(calls.len(), || self.inner.call_batch(calls))
+            .await
+    }
+}
+
+#[async_trait]
+impl RpcBackend for ScheduledBackend {
+    async fn get_chain_id(&self) -> Result<u64> {
+        self.schedule(|| self.inner.get_chain_id()).await
+    }
+
+    async fn get_block_number(&self) -> Result<U64> {
+        self.schedule(|| self.inner.get_block_number()).await
+    }
+
+    async fn get_block_with_txs(&self, block_id: BlockId) -> Result<Option<Block<Transaction>>> {
+        self.schedule(|| self.inner.get_block_with_txs(block_id))
+            .await
+    }
+
+    async fn get_transaction(&self, transaction_hash: TxHash) -> Result<Option<Transaction>> {
+        self.schedule(|| self.inner.get_transaction(transaction_hash))
+            .await
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        transaction_hash: TxHash,
+    ) -> Result<Option<TransactionReceipt>> {
+        self.schedule(|| self.inner.get_transaction_receipt(transaction_hash))
+            .await
+    }
+
+    async fn get_block_receipts(&self, block_id: BlockId) -> Result<Vec<TransactionReceipt>> {
+        if self.receipts_support.lock().await.block_receipts == Some(false) {
+            return Err(ProviderError::UnsupportedRPC.into());
+        }
+        let res = self
+            .schedule(|| self.inner.get_block_receipts(block_id))
+            .await;
+        record_support(&mut self.receipts_support.lock().await.block_receipts, &res);
+        res
+    }
+
+    async fn get_balance(&self, address: Address, block_id: Option<BlockId>) -> Result<U256> {
+        self.schedule(|| self.inner.get_balance(address, block_id))
+            .await
+    }
+
+    async fn get_code(&self, address: Address, block_id: Option<BlockId>) -> Result<Bytes> {
+        self.schedule(|| self.inner.get_code(address, block_id))
+            .await
+    }
+
+    async fn get_storage_at(
+        &self,
+        address: Address,
+        slot: H256,
+        block_id: Option<BlockId>,
+    ) -> Result<H256> {
+        self.schedule(|| self.inner.get_storage_at(address, slot, block_id))
+            .await
+    }
+
+    async fn call(&self, to: Address, data: Bytes) -> Result<Bytes> {
+        self.schedule(|| self.inner.call(to, data.to_owned())).await
+    }
+
+    async fn lookup_address(&self, address: Address) -> Result<String> {
+        self.schedule(|| self.inner.lookup_address(address)).await
+    }
+
+    async fn resolve_name(&self, ens_id: &str) -> Result<Address> {
+        self.schedule(|| self.inner.resolve_name(ens_id)).await
+    }
+
+    async fn resolve_avatar(&self, ens_id: &str) -> Result<Url> {
+        self.schedule(|| self.inner.resolve_avatar(ens_id)).await
+    }
+
+    fn failed_requests(&self) -> usize {
+        self.failed_requests.load(Ordering::Relaxed)
+    }
+
+    fn endpoint(&self) -> Option<String> {
+        self.inner.endpoint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::fake::{error_response, FakeBackend};
+    use futures::future::join;
+
+    /// A node whose chain only has a genesis block.
+    fn fake() -> Arc<FakeBackend> {
+        let fake = Arc::new(FakeBackend::new());
+        fake.mine(vec![]);
+        fake
+    }
+
+    fn scheduled(fake: &Arc<FakeBackend>, max_retries: u32) -> ScheduledBackend {
+        ScheduledBackend::new(
+            fake.to_owned(),
+            SchedulerConfig {
+                requests_per_second: 1000.0,
+                max_concurrency: 1,
+                max_retries,
+            },
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_rate_limits_with_exponential_backoff() {
+        let fake = fake();
+        fake.fail_next(error_response(429));
+        fake.fail_next(error_response(-32005));
+        let backend = scheduled(&fake, 5);
+
+        let started_at = tokio::time::Instant::now();
+        assert_eq!(backend.get_block_number().await.unwrap(), U64::zero());
+        assert_eq!(started_at.elapsed(), INITIAL_BACKOFF * 3);
+        assert_eq!(fake.requests(), 3);
+        assert_eq!(backend.failed_requests(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn caps_the_backoff() {
+        let fake = fake();
+        for _ in 0..8 {
+            fake.fail_next(error_response(429));
+        }
+        let backend = scheduled(&fake, 7);
+
+        let started_at = tokio::time::Instant::now();
+        assert!(backend.get_block_number().await.is_err());
+        // 0.5 + 1 + 2 + 4 + 8 seconds, and then twice the maximum.
+        assert_eq!(started_at.elapsed(), INITIAL_BACKOFF * 31 + MAX_BACKOFF * 2);
+        assert_eq!(fake.requests(), 8);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_after_max_retries() {
+        let fake = fake();
+        for _ in 0..5 {
+            fake.fail_next(error_response(429));
+        }
+        let backend = scheduled(&fake, 2);
+
+        assert!(backend.get_block_number().await.is_err());
+        assert_eq!(fake.requests(), 3);
+        assert_eq!(backend.failed_requests(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn does_not_retry_final_answers() {
+        // e.g. a reverted call.
+        let fake = fake();
+        fake.fail_next(error_response(3));
+        let backend = scheduled(&fake, 5);
+
+        assert!(backend.get_block_number().await.is_err());
+        assert_eq!(fake.requests(), 1);
+        // The node answered, so it isn't counted against its health.
+        assert_eq!(backend.failed_requests(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn does_not_retry_other_failures() {
+        let fake = fake();
+        fake.fail_next(anyhow::anyhow!("connection refused"));
+        let backend = scheduled(&fake, 5);
+
+        assert!(backend.get_block_number().await.is_err());
+        assert_eq!(fake.requests(), 1);
+        assert_eq!(backend.failed_requests(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn lets_other_requests_through_while_backing_off() {
+        let fake = fake();
+        fake.fail_next(error_response(429));
+        let backend = scheduled(&fake, 5);
+
+        let started_at = tokio::time::Instant::now();
+        let (first, second) = join(backend.get_block_number(), async {
+            backend.get_block_number().await.unwrap();
+            started_at.elapsed()
+        })
+        .await;
+        assert!(first.is_ok());
+        assert_eq!(second, Duration::ZERO);
+        assert_eq!(started_at.elapsed(), INITIAL_BACKOFF);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn remembers_which_receipt_methods_are_unsupported() {
+        let fake = fake();
+        let block_hash = fake.mine(vec![]).hash.unwrap();
+        let backend = scheduled(&fake, 5);
+
+        // eth_getBlockReceipts isn't supported, and the batch fails for another reason.
+        fake.fail_next(error_response(-32601));
+        fake.fail_next(error_response(-32000));
+        assert!(backend.get_block_receipts(block_hash.into()).await.is_err());
+        assert!(backend.get_transaction_receipts_batch(&[]).await.is_err());
+        assert_eq!(fake.requests(), 2);
+
+        // Only batches are tried again.
+        let e = backend
+            .get_block_receipts(block_hash.into())
+            .await
+            .unwrap_err();
+        assert!(is_unsupported(&e));
+        assert!(backend.get_transaction_receipts_batch(&[]).await.is_ok());
+        assert_eq!(fake.requests(), 3);
+    }
+
+    #[test]
+    fn retries_by_error_code() {
+        assert!(is_retryable(&error_response(429)));
+        assert!(is_retryable(&error_response(-32005)));
+        assert!(!is_retryable(&error_response(-32000)));
+        assert!(!is_retryable(&error_response(-32601)));
+        assert!(is_final_answer(&error_response(-32000)));
+        assert!(is_final_answer(&error_response(-32601)));
+        assert!(!is_final_answer(&error_response(429)));
+    }
+
+    #[test]
+    fn token_bucket_allows_a_burst_and_then_waits() {
+        let mut bucket = TokenBucket::new(10.0);
+        for _ in 0..10 {
+            assert_eq!(bucket.try_take(1.0), None);
+        }
+        let wait = bucket.try_take(1.0).unwrap();
+        assert!(wait > Duration::from_millis(90) && wait <= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn token_bucket_charges_batches_per_call() {
+        let mut bucket = TokenBucket::new(10.0);
+        assert_eq!(bucket.try_take(4.0), None);
+        let wait = bucket.try_take(10.0).unwrap();
+        assert!(wait > Duration::from_millis(390) && wait <= Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn waits_for_batches_larger_than_the_bucket() {
+        let rate_limiter = RateLimiter::new(100.0);
+        let start = Instant::now();
+        rate_limiter.wait_for(120).await;
+        // A bucketful is taken at once, and the other 20 calls wait for it to refill.
+        assert!(start.elapsed() >= Duration::from_millis(190));
+    }
+
+    #[test]
+    fn rejects_non_positive_rates() {
+        assert!(is_positive(0.5));
+        assert!(!is_positive(0.0));
+        assert!(!is_positive(-1.0));
+        assert!(!is_positive(f64::NAN));
+        assert!(!is_positive(f64::INFINITY));
+    }
+}
@@ -1,5 +1,11 @@
-use crate::ethers::types::{AddressInfo, BlockWithTransactionReceipts, TransactionWithReceipt};
+use crate::{
+    app::notification::Notification,
+    ethers::types::{AddressInfo, BlockWithTransactionReceipts, TransactionWithReceipt},
+};
 use ethers::core::types::Transaction;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_ROUTE_KEY: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Clone)]
 pub enum RouteId {
@@ -11,6 +17,7 @@ pub enum RouteId {
     WithdrawalsOfBlock(Option<BlockWithTransactionReceipts<Transaction>>),
     Transaction(Option<TransactionWithReceipt>),
     InputDataOfTransaction(Option<TransactionWithReceipt>),
+    Failed(Notification),
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -25,11 +32,38 @@ pub enum ActiveBlock {
 pub struct Route {
     id: RouteId,
     active_block: ActiveBlock,
+    /// Identifies the screen across updates of its content or focus, so that responses can
+    /// tell whether the user is still on the screen that requested them.
+    key: u64,
 }
 
 impl Route {
     pub fn new(id: RouteId, active_block: ActiveBlock) -> Self {
-        Self { id, active_block }
+        Self {
+            id,
+            active_block,
+            key: NEXT_ROUTE_KEY.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// The same screen with updated content.
+    pub fn with_id(&self, id: RouteId) -> Self {
+        Self {
+            id,
+            ..self.to_owned()
+        }
+    }
+
+    /// The same screen with another block focused.
+    pub fn with_active_block(&self, active_block: ActiveBlock) -> Self {
+        Self {
+            active_block,
+            ..self.to_owned()
+        }
+    }
+
+    pub fn get_key(&self) -> u64 {
+        self.key
     }
 
     pub fn get_active_block(&self) -> ActiveBlock {
@@ -43,9 +77,6 @@ impl Route {
 
 impl Default for Route {
     fn default() -> Self {
-        Self {
-            id: RouteId::Welcome,
-            active_block: ActiveBlock::SearchBar,
-        }
+        Self::new(RouteId::Welcome, ActiveBlock::SearchBar)
     }
 }
@@ -1,4 +1,5 @@
 mod home;
+pub mod theme;
 use crate::app::App;
 use ratatui::prelude::*;
 
@@ -1,12 +1,13 @@
 mod address_info;
 mod block;
+mod failed;
 mod latest_status;
 mod searching;
 mod statistics;
 mod transaction;
 mod welcome;
 use crate::{
-    app::{App, InputMode},
+    app::{keymap::Action, App, InputMode},
     route::{ActiveBlock, RouteId},
 };
 use ratatui::{prelude::*, widgets::*};
@@ -26,30 +27,65 @@ pub fn render_home_layout<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     };
 
     let searchbar_block = Block::default()
-        .border_style(Style::default().fg(
-            if let ActiveBlock::SearchBar = app.get_current_route().get_active_block() {
-                Color::Green
-            } else {
-                Color::White
-            },
-        ))
+        .border_style(app.theme.border(matches!(
+            app.get_current_route().get_active_block(),
+            ActiveBlock::SearchBar
+        )))
         .title(format!(
-            "Search by Address / Txn Hash / Block / Token / Domain Name ({})",
+            "Search {} by Address / Txn Hash / Block / Token / Domain Name ({})",
+            app.chain.name(),
             match app.input_mode {
-                InputMode::Normal => "Press 'q' to exit, 'i' to start editing.",
-                InputMode::Editing => "Press 'Esc' to stop editing, 'Enter' to search.",
+                InputMode::Normal => format!(
+                    "Press '{}' to exit, '{}' to start editing.",
+                    keys(app, Action::Quit),
+                    keys(app, Action::EditSearch)
+                ),
+                InputMode::Editing => "Press 'Esc' to stop editing, 'Enter' to search.".to_owned(),
             }
         ))
         .borders(Borders::ALL)
         .border_type(BorderType::Plain);
 
     let input = Paragraph::new(app.input.as_str())
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(app.theme.text))
         .block(searchbar_block);
     f.render_widget(input, searchbar);
 
-    let message = Paragraph::new(" <up>/<down>: k/j, <esc>: Cancel, q: Quit, ?: Keybindings, 1-2: Jump to panel, s: Focus on the Search bar")
-        .style(Style::default().fg(Color::White));
+    // A recent failure takes the place of the key hints until it expires.
+    let mut message = vec![if let Some(notification) = app
+        .notifications
+        .last()
+        .filter(|notification| notification.is_recent())
+    {
+        Span::raw(format!(" {notification}")).fg(app.theme.error)
+    } else {
+        let route_id = app.get_current_route().get_id();
+        let active_block = app.get_current_route().get_active_block();
+        // The help comes first so that it isn't cut off on narrow terminals.
+        let hints = std::iter::once(Action::ShowHelp)
+            .chain(
+                Action::ALL
+                    .into_iter()
+                    .filter(|action| *action != Action::ShowHelp),
+            )
+            .filter(|action| match action {
+                // Esc only closes the help when nothing is being searched.
+                Action::Cancel => matches!(route_id, RouteId::Searching(_)),
+                _ => action.describe(&route_id, active_block).is_some(),
+            })
+            .filter_map(|action| {
+                let keys = keys(app, action);
+                (!keys.is_empty()).then(|| format!("{keys}: {}", action.label()))
+            })
+            .collect::<Vec<_>>();
+        Span::raw(format!(" {}", hints.join(", ")))
+    }];
+    if app.failed_requests > 0 {
+        message.push(
+            Span::raw(format!(" | {} requests failed", app.failed_requests)).fg(app.theme.error),
+        );
+    }
+    let message = Paragraph::new(Line::from(message)).style(Style::default().fg(app.theme.text));
     f.render_widget(message, navigation_bar);
 
     match app.input_mode {
@@ -90,13 +126,23 @@ pub fn render_home_layout<B: Backend>(f: &mut Frame<B>, app: &mut App) {
                 welcome::render(f, app, rest);
             }
             RouteId::Searching(message) => {
-                searching::render(f, &message, rest);
+                searching::render(f, app, &message, rest);
+            }
+            RouteId::Failed(notification) => {
+                failed::render(f, app, &notification, rest);
             }
         }
     } else {
+        let (numerator, denominator) = app.layout.sidebar_ratio;
         let [sidebar, detail] = *Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)].as_ref())
+            .constraints(
+                [
+                    Constraint::Ratio(numerator, denominator),
+                    Constraint::Ratio(denominator.saturating_sub(numerator), denominator),
+                ]
+                .as_ref(),
+            )
             .split(rest)
         else {
             return;
@@ -134,45 +180,73 @@ pub fn render_home_layout<B: Backend>(f: &mut Frame<B>, app: &mut App) {
                 welcome::render(f, app, detail);
             }
             RouteId::Searching(message) => {
-                searching::render(f, &message, detail);
+                searching::render(f, app, &message, detail);
+            }
+            RouteId::Failed(notification) => {
+                failed::render(f, app, &notification, detail);
             }
         }
     }
 
-    let size = f.size();
     if app.show_popup {
-        let block = Block::default()
-            .title("Keybindings - Press Esc to close the popup")
-            .borders(Borders::ALL);
-
-        //TODO: Enter
-        let input = Paragraph::new(vec![
-            Line::from(Span::raw(format!(" {:<4}: {}", "j", "Down")).fg(Color::White)),
-            Line::from(Span::raw(format!(" {:<4}: {}", "k", "Up")).fg(Color::White)),
-            Line::from(
-                Span::raw(format!(" {:<4}: {}", "s", "Move to the Search Bar")).fg(Color::White),
-            ),
-            Line::from(
-                Span::raw(format!(" {:<4}: {}", "1", "Move to the Latest Blocks")).fg(Color::White),
-            ),
-            Line::from(
-                Span::raw(format!(
-                    " {:<4}: {}",
-                    "2", "Move to the Latest Transactions"
-                ))
-                .fg(Color::White),
-            ),
-        ])
-        .style(Style::default().fg(Color::Green))
-        .block(block.to_owned());
-
-        let area = centered_rect(60, 20, size);
-        f.render_widget(Clear, area); //this clears out the background
-        f.render_widget(block, area);
-        f.render_widget(input, area);
+        render_help(f, app);
     }
 }
 
+/// Lists what each key does on the current screen, as bound in the keymap.
+fn render_help<B: Backend>(f: &mut Frame<B>, app: &App) {
+    let route_id = app.get_current_route().get_id();
+    let active_block = app.get_current_route().get_active_block();
+    let rows = Action::ALL
+        .into_iter()
+        .filter_map(|action| {
+            let description = action.describe(&route_id, active_block)?;
+            let keys = keys(app, action);
+            (!keys.is_empty()).then_some((keys, description))
+        })
+        .collect::<Vec<_>>();
+    let width = rows
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let block = Block::default()
+        .title(format!(
+            "Keybindings - Press {} to close the popup",
+            keys(app, Action::Cancel)
+        ))
+        .borders(Borders::ALL);
+
+    let lines = rows
+        .iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::raw(format!(" {keys:<width$}")).fg(app.theme.focus),
+                Span::raw(format!(" : {description}")).fg(app.theme.text),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let size = f.size();
+    let mut area = centered_rect(60, 100, size);
+    area.height = (lines.len() as u16 + 2).min(size.height);
+    area.y = size.y + (size.height - area.height) / 2;
+
+    let help = Paragraph::new(lines).block(block);
+    f.render_widget(Clear, area); //this clears out the background
+    f.render_widget(help, area);
+}
+
+/// The keys bound to `action`, e.g. `j/Down`.
+fn keys(app: &App, action: Action) -> String {
+    app.keymap
+        .keys(action)
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 /// helper function to create a centered rect using up certain percentage of the available rect `r`
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
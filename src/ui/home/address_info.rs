@@ -1,6 +1,11 @@
+mod bytecode;
+mod storage;
+mod tokens;
+mod transactions;
 use crate::{
     app::{address::SelectableContractDetailItem, App},
     ethers::types::AddressInfo,
+    network::chain::ExplorerStatus,
     route::ActiveBlock,
 };
 use ethers::core::utils::format_ether;
@@ -15,20 +20,17 @@ pub fn render<B: Backend>(
     if let Some(address_info) = address_info {
         let detail_block = Block::default()
             .title(format!("Address {:#x}", address_info.address))
-            .border_style(
-                if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                },
-            )
+            .border_style(app.theme.border(matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            )))
             .padding(Padding::new(2, 2, 1, 1))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain);
 
         let [detail_rect, contract_detail_rect] = *Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Max(7), Constraint::Min(3)].as_ref())
+            .constraints([Constraint::Max(10), Constraint::Min(3)].as_ref())
             .split(rect)
         else {
             return;
@@ -46,31 +48,75 @@ pub fn render<B: Backend>(
                     "{:<17}: {} ({})",
                     "ERC20", token.name, token.ticker
                 ))
-                .fg(Color::White),
+                .fg(app.theme.text),
             ));
         }
 
         if let Some(ens_id) = address_info.ens_id {
             details.push(Line::from(
-                Span::raw(format!("{:<17}: {ens_id}", "FULL NAME")).fg(Color::White),
+                Span::raw(format!("{:<17}: {ens_id}", "FULL NAME")).fg(app.theme.text),
             ));
         }
 
         if let Some(avatar_url) = address_info.avatar_url {
             details.push(Line::from(
-                Span::raw(format!("{:<17}: {avatar_url}", "AVATAR URL")).fg(Color::White),
+                Span::raw(format!("{:<17}: {avatar_url}", "AVATAR URL")).fg(app.theme.text),
             ));
         }
 
         details.push(Line::from(
             Span::raw(format!(
-                "{:<17}: {} ETH",
-                "ETH BALANCE",
-                format_ether(address_info.balance)
+                "{:<17}: {} {}",
+                "BALANCE",
+                format_ether(address_info.balance),
+                app.chain.native_currency_symbol()
             ))
-            .fg(Color::White),
+            .fg(app.theme.text),
         ));
 
+        details.push(Line::from(
+            Span::raw(if address_info.code.is_empty() {
+                format!("{:<17}: none (externally owned account)", "CODE")
+            } else {
+                format!("{:<17}: {} bytes", "CODE", address_info.code.len())
+            })
+            .fg(app.theme.text),
+        ));
+
+        if let Some(proxy) = address_info.proxy.as_ref() {
+            let mut spans = vec![
+                Span::raw(format!("{:<17}: {} → ", "PROXY", proxy.kind)).fg(app.theme.text),
+                Span::raw(format!("{:#x}", proxy.implementation)).fg(app.theme.address),
+            ];
+            for (label, address) in [("beacon", proxy.beacon), ("admin", proxy.admin)] {
+                if let Some(address) = address {
+                    spans.push(Span::raw(format!(", {label} ")).fg(app.theme.text));
+                    spans.push(Span::raw(format!("{address:#x}")).fg(app.theme.address));
+                }
+            }
+            details.push(Line::from(spans));
+        }
+
+        details.push(Line::from(vec![
+            Span::raw(format!("{:<17}: ", "EXPLORER")).fg(app.theme.text),
+            Span::raw(app.explorer_status.to_string()).fg(match app.explorer_status {
+                ExplorerStatus::Enabled => app.theme.success,
+                ExplorerStatus::Disabled => app.theme.muted,
+                ExplorerStatus::Failing(_) => app.theme.error,
+            }),
+        ]));
+
+        // Without an explorer there's no source code or ABI, so say why.
+        let unavailable = match app.explorer_status {
+            ExplorerStatus::Enabled => vec![],
+            ExplorerStatus::Disabled => vec![Line::from(
+                Span::raw("Set an explorer API key to see verified contracts.").fg(app.theme.muted),
+            )],
+            ExplorerStatus::Failing(ref message) => vec![Line::from(
+                Span::raw(format!("The explorer failed: {message}")).fg(app.theme.error),
+            )],
+        };
+
         let source_code_lines =
             if let Some(contract_source_code) = address_info.contract_source_code {
                 let mut details = vec![];
@@ -81,13 +127,13 @@ pub fn render<B: Backend>(
 
                 for (idx, line) in source_code.iter().enumerate() {
                     details.push(Line::from(vec![
-                        Span::raw(format!("{:>3}  ", idx + 1)).fg(Color::Gray),
-                        Span::raw(line.to_string()).fg(Color::White),
+                        Span::raw(format!("{:>3}  ", idx + 1)).fg(app.theme.muted),
+                        Span::raw(line.to_string()).fg(app.theme.text),
                     ]));
                 }
                 details
             } else {
-                vec![]
+                unavailable.to_owned()
             };
         app.source_code_scroll_state = app
             .source_code_scroll_state
@@ -101,17 +147,55 @@ pub fn render<B: Backend>(
 
             for (idx, line) in contract_abi_lines.iter().enumerate() {
                 details.push(Line::from(vec![
-                    Span::raw(format!("{:>3}  ", idx + 1)).fg(Color::Gray),
-                    Span::raw(line.to_string()).fg(Color::White),
+                    Span::raw(format!("{:>3}  ", idx + 1)).fg(app.theme.muted),
+                    Span::raw(line.to_string()).fg(app.theme.text),
                 ]));
             }
             details
         } else {
-            vec![]
+            unavailable
         };
         app.abi_scroll_state = app.abi_scroll_state.content_length(abi_lines.len() as u16);
 
-        if app.is_toggled {
+        let selected = SelectableContractDetailItem::from(
+            app.contract_list_state
+                .selected()
+                .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
+        );
+        if let (true, SelectableContractDetailItem::Transactions) = (app.is_toggled, selected) {
+            let block = focused_block(app, "TRANSACTIONS");
+            transactions::render(
+                f,
+                app,
+                address_info.address,
+                address_info.transactions,
+                block,
+                contract_detail_rect,
+            );
+        } else if let (true, SelectableContractDetailItem::Tokens) = (app.is_toggled, selected) {
+            let block = focused_block(app, "TOKENS");
+            tokens::render(
+                f,
+                app,
+                address_info.address,
+                address_info.token_balances,
+                block,
+                contract_detail_rect,
+            );
+        } else if let (true, SelectableContractDetailItem::Bytecode) = (app.is_toggled, selected) {
+            let block = focused_block(app, "BYTECODE");
+            bytecode::render(f, app, &address_info.code, block, contract_detail_rect);
+        } else if let (true, SelectableContractDetailItem::Storage) = (app.is_toggled, selected) {
+            let block = focused_block(app, "STORAGE");
+            storage::render(
+                f,
+                app,
+                address_info.address,
+                &address_info.storage_slots,
+                block,
+                contract_detail_rect,
+            );
+        } else if app.is_toggled {
             let chunks = Layout::default()
                 .direction(Direction::Horizontal)
                 .constraints([Constraint::Ratio(2, 3), Constraint::Ratio(1, 3)])
@@ -132,15 +216,17 @@ pub fn render<B: Backend>(
                         {
                             Block::default()
                                 .borders(Borders::ALL)
-                                .green()
+                                .fg(app.theme.focus)
                                 .title(Span::styled(
                                     "SOURCE CODE",
-                                    Style::default().add_modifier(Modifier::BOLD).green(),
+                                    Style::default()
+                                        .add_modifier(Modifier::BOLD)
+                                        .fg(app.theme.focus),
                                 ))
                         } else {
                             Block::default()
                                 .borders(Borders::ALL)
-                                .gray()
+                                .fg(app.theme.muted)
                                 .title(Span::styled(
                                     "SOURCE CODE",
                                     Style::default().add_modifier(Modifier::BOLD),
@@ -176,15 +262,17 @@ pub fn render<B: Backend>(
                         {
                             Block::default()
                                 .borders(Borders::ALL)
-                                .green()
+                                .fg(app.theme.focus)
                                 .title(Span::styled(
                                     "ABI",
-                                    Style::default().add_modifier(Modifier::BOLD).green(),
+                                    Style::default()
+                                        .add_modifier(Modifier::BOLD)
+                                        .fg(app.theme.focus),
                                 ))
                         } else {
                             Block::default()
                                 .borders(Borders::ALL)
-                                .gray()
+                                .fg(app.theme.muted)
                                 .title(Span::styled(
                                     "ABI",
                                     Style::default().add_modifier(Modifier::BOLD),
@@ -212,65 +300,90 @@ pub fn render<B: Backend>(
 
             let block = Block::default().padding(Padding::horizontal(2));
 
-            let titles = ["SOURCE CODE", "ABI"]
-                .iter()
-                .map(|t| Line::from(t.to_owned()))
-                .collect();
+            let titles = [
+                "SOURCE CODE",
+                "ABI",
+                "TRANSACTIONS",
+                "TOKENS",
+                "BYTECODE",
+                "STORAGE",
+            ]
+            .iter()
+            .map(|t| Line::from(t.to_owned()))
+            .collect();
 
             let tabs = Tabs::new(titles)
                 .block(Block::default().borders(Borders::RIGHT | Borders::LEFT | Borders::TOP))
-                .select(
-                    app.contract_list_state
-                        .selected()
-                        .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
-                )
+                .select(selected.into())
                 .style(Style::default())
-                .highlight_style(Style::default().bold().green());
+                .highlight_style(Style::default().bold().fg(app.theme.focus));
             f.render_widget(tabs, block.inner(chunks[0]));
 
-            let inner = match SelectableContractDetailItem::from(
-                app.contract_list_state
-                    .selected()
-                    .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
-            ) {
-                SelectableContractDetailItem::ContractSourceCode => {
-                    Paragraph::new(source_code_lines.to_owned())
+            let block = Block::default().padding(Padding::new(2, 2, 0, 1));
+            let pane = Block::default()
+                .borders(Borders::RIGHT | Borders::LEFT | Borders::BOTTOM)
+                .padding(Padding::horizontal(1));
+            if let SelectableContractDetailItem::Transactions = selected {
+                transactions::render(
+                    f,
+                    app,
+                    address_info.address,
+                    address_info.transactions,
+                    pane,
+                    block.inner(chunks[1]),
+                );
+            } else if let SelectableContractDetailItem::Tokens = selected {
+                tokens::render(
+                    f,
+                    app,
+                    address_info.address,
+                    address_info.token_balances,
+                    pane,
+                    block.inner(chunks[1]),
+                );
+            } else if let SelectableContractDetailItem::Bytecode = selected {
+                bytecode::render(f, app, &address_info.code, pane, block.inner(chunks[1]));
+            } else if let SelectableContractDetailItem::Storage = selected {
+                storage::render(
+                    f,
+                    app,
+                    address_info.address,
+                    &address_info.storage_slots,
+                    pane,
+                    block.inner(chunks[1]),
+                );
+            } else {
+                let (lines, scroll, mut scroll_state) =
+                    if let SelectableContractDetailItem::ContractAbi = selected {
+                        (abi_lines, app.abi_scroll, app.abi_scroll_state)
+                    } else {
+                        (
+                            source_code_lines,
+                            app.source_code_scroll,
+                            app.source_code_scroll_state,
+                        )
+                    };
+                f.render_widget(
+                    Paragraph::new(lines)
                         .block(
                             Block::default()
                                 .borders(Borders::RIGHT | Borders::LEFT | Borders::BOTTOM),
                         )
                         .alignment(Alignment::Left)
-                        .scroll((app.source_code_scroll, 0))
-                        .wrap(Wrap { trim: false })
-                }
-                SelectableContractDetailItem::ContractAbi => Paragraph::new(abi_lines.to_owned())
-                    .block(
-                        Block::default().borders(Borders::RIGHT | Borders::LEFT | Borders::BOTTOM),
-                    )
-                    .alignment(Alignment::Left)
-                    .scroll((app.abi_scroll, 0))
-                    .wrap(Wrap { trim: false }),
-            };
-            let block = Block::default().padding(Padding::new(2, 2, 0, 1));
-            f.render_widget(inner, block.inner(chunks[1]));
+                        .scroll((scroll, 0))
+                        .wrap(Wrap { trim: false }),
+                    block.inner(chunks[1]),
+                );
 
-            f.render_stateful_widget(
-                Scrollbar::default()
-                    .orientation(ScrollbarOrientation::VerticalRight)
-                    .begin_symbol(Some("▲"))
-                    .end_symbol(Some("▼")),
-                block.inner(chunks[1]),
-                &mut match SelectableContractDetailItem::from(
-                    app.contract_list_state
-                        .selected()
-                        .unwrap_or(SelectableContractDetailItem::ContractSourceCode.into()),
-                ) {
-                    SelectableContractDetailItem::ContractSourceCode => {
-                        app.source_code_scroll_state
-                    }
-                    SelectableContractDetailItem::ContractAbi => app.abi_scroll_state,
-                },
-            );
+                f.render_stateful_widget(
+                    Scrollbar::default()
+                        .orientation(ScrollbarOrientation::VerticalRight)
+                        .begin_symbol(Some("▲"))
+                        .end_symbol(Some("▼")),
+                    block.inner(chunks[1]),
+                    &mut scroll_state,
+                );
+            }
         }
 
         let details = Paragraph::new(details)
@@ -283,13 +396,10 @@ pub fn render<B: Backend>(
     } else {
         let detail_block = Block::default()
             .title("Address Not Found")
-            .border_style(
-                if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                },
-            )
+            .border_style(app.theme.border(matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            )))
             .padding(Padding::new(2, 2, 1, 1))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain);
@@ -297,3 +407,17 @@ pub fn render<B: Backend>(
         f.render_widget(detail_block, rect);
     }
 }
+
+/// The border of a pane that takes up the whole contract detail area.
+fn focused_block(app: &App, title: &'static str) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .fg(app.theme.focus)
+        .padding(Padding::horizontal(1))
+        .title(Span::styled(
+            title,
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(app.theme.focus),
+        ))
+}
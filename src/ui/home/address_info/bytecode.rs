@@ -0,0 +1,127 @@
+use crate::{
+    app::App,
+    ethers::bytecode::{self, Instruction},
+};
+use ethers::core::{types::Bytes, utils::hex};
+use ratatui::{prelude::*, widgets::*};
+
+pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, code: &Bytes, block: Block, rect: Rect) {
+    let lines = if code.is_empty() {
+        vec![Line::from(
+            Span::raw("No code is deployed at this address.").fg(app.theme.muted),
+        )]
+    } else {
+        lines(app, code)
+    };
+    app.bytecode_scroll_state = app.bytecode_scroll_state.content_length(lines.len() as u16);
+
+    let inner = block.inner(rect);
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Left)
+            .scroll((app.bytecode_scroll, 0)),
+        rect,
+    );
+
+    f.render_stateful_widget(
+        Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("▲"))
+            .end_symbol(Some("▼")),
+        inner,
+        &mut app.bytecode_scroll_state,
+    );
+}
+
+/// A summary of the code, the selectors it dispatches on and then its disassembly.
+fn lines<'a>(app: &App, code: &'a Bytes) -> Vec<Line<'a>> {
+    let (executable, metadata) = bytecode::split_metadata(code);
+    let instructions = bytecode::disassemble(executable);
+    let jumpdests = instructions
+        .iter()
+        .filter(|instruction| instruction.is_jumpdest())
+        .count();
+
+    let mut lines = vec![Line::from(
+        Span::raw(format!(
+            "{} bytes, {} instructions, {jumpdests} JUMPDESTs",
+            code.len(),
+            instructions.len()
+        ))
+        .fg(app.theme.text),
+    )];
+
+    if let Some(metadata) = metadata {
+        let mut entries = vec![];
+        if let Some(compiler) = metadata.compiler {
+            entries.push(("COMPILER", compiler));
+        }
+        if let Some(ipfs) = metadata.ipfs {
+            entries.push(("IPFS", ipfs));
+        }
+        if let Some(bzz) = metadata.bzz {
+            entries.push(("SWARM", bzz));
+        }
+        if metadata.experimental {
+            entries.push(("EXPERIMENTAL", "true".to_owned()));
+        }
+        for (label, value) in entries {
+            lines.push(Line::from(vec![
+                Span::raw(format!("{label:<13}: ")).fg(app.theme.text),
+                Span::raw(value).fg(app.theme.address),
+            ]));
+        }
+    }
+
+    let selectors = bytecode::selectors(&instructions);
+    lines.push(Line::from(""));
+    lines.push(Line::from(
+        Span::raw(format!("SELECTORS ({})", selectors.len())).bold(),
+    ));
+    for selector in selectors {
+        let signatures = app
+            .signatures
+            .find_functions(&selector)
+            .iter()
+            .map(|function| function.signature())
+            .collect::<Vec<_>>();
+        lines.push(Line::from(vec![
+            Span::raw(format!("  0x{}  ", hex::encode(selector))).fg(app.theme.address),
+            if signatures.is_empty() {
+                Span::raw("unknown").fg(app.theme.muted)
+            } else {
+                Span::raw(signatures.join(" | ")).fg(app.theme.method)
+            },
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::raw("DISASSEMBLY").bold()));
+    lines.extend(
+        instructions
+            .iter()
+            .map(|instruction| line(app, instruction)),
+    );
+    if let Some(metadata) = code.get(executable.len()..).filter(|rest| !rest.is_empty()) {
+        lines.push(Line::from(vec![
+            Span::raw(format!("{:#06x}  ", executable.len())).fg(app.theme.muted),
+            Span::raw(format!("metadata 0x{}", hex::encode(metadata))).fg(app.theme.muted),
+        ]));
+    }
+    lines
+}
+
+/// An instruction at its offset, with jump destinations marked.
+fn line<'a>(app: &App, instruction: &Instruction) -> Line<'a> {
+    let (marker, color) = if instruction.is_jumpdest() {
+        ("▶ ", app.theme.highlight)
+    } else {
+        ("  ", app.theme.text)
+    };
+    Line::from(vec![
+        Span::raw(format!("{:#06x}", instruction.offset)).fg(app.theme.muted),
+        Span::raw(format!(" {marker}")).fg(color),
+        Span::raw(instruction.to_string()).fg(color),
+    ])
+}
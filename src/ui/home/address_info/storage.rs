@@ -0,0 +1,184 @@
+use crate::{
+    app::{keymap::Action, App},
+    ethers::{
+        storage::{self, StorageLayout},
+        types::StorageSlot,
+    },
+    network::IoEvent,
+    ui::home::keys,
+    widget::Spinner,
+};
+use ethers::core::{
+    types::{Address, H256, U256},
+    utils::hex,
+};
+use ratatui::{prelude::*, widgets::*};
+
+const PROMPT: &str = "Slot: ";
+
+pub fn render<B: Backend>(
+    f: &mut Frame<B>,
+    app: &mut App,
+    address: Address,
+    storage_slots: &[StorageSlot],
+    block: Block,
+    rect: Rect,
+) {
+    let inner = block.inner(rect);
+    f.render_widget(block, rect);
+
+    let [input_rect, slots_rect] = *Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)].as_ref())
+        .split(inner)
+    else {
+        return;
+    };
+
+    let input = if app.is_editing_storage_slot {
+        Line::from(vec![
+            Span::raw(PROMPT).fg(app.theme.text),
+            Span::raw(app.storage_slot_input.to_owned()).fg(app.theme.focus),
+        ])
+    } else if let Some(error) = app.storage_slot_error.as_ref() {
+        Line::from(Span::raw(error.to_owned()).fg(app.theme.error))
+    } else {
+        Line::from(
+            Span::raw(format!(
+                "{}: Enter a slot, e.g. 0, 0x2a, _balances[0x...], 3#2+1. {}: Reload",
+                keys(app, Action::Select),
+                keys(app, Action::Refresh)
+            ))
+            .fg(app.theme.muted),
+        )
+    };
+    f.render_widget(Paragraph::new(input), input_rect);
+    if app.is_editing_storage_slot {
+        f.set_cursor(
+            input_rect.x + (PROMPT.len() + app.storage_slot_input.chars().count()) as u16,
+            input_rect.y,
+        );
+    }
+
+    let layout = app.storage_layouts.get(&address);
+    let mut lines = vec![];
+    for storage_slot in storage_slots {
+        lines.extend(slot_lines(app, address, layout, storage_slot));
+        lines.push(Line::from(""));
+    }
+    app.storage_scroll_state = app.storage_scroll_state.content_length(lines.len() as u16);
+
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Left)
+            .scroll((app.storage_scroll, 0)),
+        slots_rect,
+    );
+
+    f.render_stateful_widget(
+        Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("▲"))
+            .end_symbol(Some("▼")),
+        slots_rect,
+        &mut app.storage_scroll_state,
+    );
+}
+
+/// A slot and its value as each of the types it may hold, along with the variables the
+/// storage layout puts in it.
+fn slot_lines<'a>(
+    app: &App,
+    address: Address,
+    layout: Option<&StorageLayout>,
+    storage_slot: &StorageSlot,
+) -> Vec<Line<'a>> {
+    let names = layout
+        .map(|layout| {
+            layout
+                .variables_at(storage_slot.slot)
+                .iter()
+                .map(|variable| variable.label.to_owned())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let mut title = vec![Span::raw(storage_slot.query.to_owned()).bold()];
+    if !names.is_empty() && !names.contains(&storage_slot.query) {
+        title.push(Span::raw(format!("  ({})", names.join(", "))).fg(app.theme.method));
+    }
+    let mut lines = vec![
+        Line::from(title),
+        field(app, "slot", format!("{:#x}", storage_slot.slot)),
+    ];
+
+    let Some(value) = storage_slot.value else {
+        let failure = app.find_failure(|io_event| {
+            matches!(
+                io_event,
+                IoEvent::GetStorageAt { address: failed, slot }
+                    if *failed == address && *slot == storage_slot.slot
+            )
+        });
+        lines.push(Line::from(if let Some(notification) = failure {
+            Span::raw(format!(
+                "  {notification} ({}: Retry)",
+                keys(app, Action::Refresh)
+            ))
+            .fg(app.theme.error)
+        } else {
+            Span::raw(format!("  Loading {}", Spinner::default())).fg(app.theme.text)
+        }));
+        return lines;
+    };
+
+    lines.push(field(app, "bytes", format!("{value:#x}")));
+    lines.push(field(app, "uint", storage::as_uint(&value).to_string()));
+    lines.push(field(
+        app,
+        "address",
+        format!("{:#x}", storage::as_address(&value)),
+    ));
+    lines.push(field(app, "bool", storage::as_bool(&value).to_string()));
+
+    // Variables packed into the slot, each decoded as its own type.
+    if let Some(layout) = layout {
+        for variable in layout.variables_at(storage_slot.slot) {
+            let Some(kind) = layout.type_of(variable) else {
+                continue;
+            };
+            let size = kind.number_of_bytes.parse().unwrap_or(32).min(32);
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {} ", variable.label)).fg(app.theme.method),
+                Span::raw(format!("({}): ", kind.label)).fg(app.theme.muted),
+                Span::raw(decode(
+                    &kind.label,
+                    storage::packed(&value, variable.offset, size),
+                ))
+                .fg(app.theme.address),
+            ]));
+        }
+    }
+    lines
+}
+
+fn field<'a>(app: &App, name: &str, value: String) -> Line<'a> {
+    Line::from(vec![
+        Span::raw(format!("  {name:<8}")).fg(app.theme.muted),
+        Span::raw(value).fg(app.theme.address),
+    ])
+}
+
+/// Decodes the bytes of a variable of a value type, or shows them as hex.
+fn decode(kind: &str, bytes: &[u8]) -> String {
+    if kind == "bool" {
+        bytes.iter().any(|byte| *byte != 0).to_string()
+    } else if kind.starts_with("address") || kind.starts_with("contract ") {
+        let mut word = [0; 32];
+        word[32 - bytes.len()..].copy_from_slice(bytes);
+        format!("{:#x}", storage::as_address(&H256(word)))
+    } else if kind.starts_with("uint") || kind.starts_with("enum ") {
+        U256::from_big_endian(bytes).to_string()
+    } else {
+        format!("0x{}", hex::encode(bytes))
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    app::{keymap::Action, App},
+    ethers::types::TokenBalance,
+    network::IoEvent,
+    ui::home::keys,
+    widget::Spinner,
+};
+use ethers::core::types::Address;
+use ratatui::{prelude::*, widgets::*};
+
+pub fn render<B: Backend>(
+    f: &mut Frame<B>,
+    app: &mut App,
+    address: Address,
+    token_balances: Option<Vec<TokenBalance>>,
+    block: Block,
+    rect: Rect,
+) {
+    let inner = block.inner(rect);
+    f.render_widget(block, rect);
+
+    let [status_rect, table_rect] = *Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)].as_ref())
+        .split(inner)
+    else {
+        return;
+    };
+
+    let failure = app.find_failure(|io_event| {
+        matches!(
+            io_event,
+            IoEvent::GetTokenBalances { address: failed } if *failed == address
+        )
+    });
+    let status = if let Some(notification) = failure {
+        Span::raw(format!(
+            "{notification} ({}: Retry)",
+            keys(app, Action::Refresh)
+        ))
+        .fg(app.theme.error)
+    } else if let Some(token_balances) = token_balances.as_ref() {
+        Span::raw(format!(
+            "Holds {} of the {} known tokens",
+            token_balances.len(),
+            app.erc20_tokens.len()
+        ))
+        .fg(app.theme.text)
+    } else {
+        Span::raw(format!("Loading token balances {}", Spinner::default())).fg(app.theme.text)
+    };
+    f.render_widget(Paragraph::new(Line::from(status)), status_rect);
+
+    let Some(token_balances) = token_balances else {
+        return;
+    };
+    let header = Row::new(
+        ["", "Token", "Ticker", "Balance", "Contract"]
+            .iter()
+            .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD))),
+    )
+    .style(Style::default().fg(app.theme.text))
+    .height(1)
+    .bottom_margin(1);
+
+    let rows = token_balances
+        .iter()
+        .enumerate()
+        .map(|(i, token_balance)| {
+            Row::new(vec![
+                Cell::from(format!(" {} ", i + 1)).fg(app.theme.text),
+                Cell::from(token_balance.token.name.to_owned()).fg(app.theme.text),
+                Cell::from(token_balance.token.ticker.to_owned()).fg(app.theme.address),
+                Cell::from(token_balance.amount()).fg(app.theme.text),
+                Cell::from(format!("{:#x}", token_balance.token.contract_address))
+                    .fg(app.theme.muted),
+            ])
+            .height(1)
+            .bottom_margin(1)
+        })
+        .collect::<Vec<_>>();
+
+    let widths = [
+        Constraint::Max(4),
+        Constraint::Max(24), //Token
+        Constraint::Max(10), //Ticker
+        Constraint::Max(30), //Balance
+        Constraint::Max(44), //Contract
+    ];
+    let t = Table::new(rows)
+        .header(header)
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&widths);
+
+    f.render_stateful_widget(t, table_rect, &mut app.token_balances_table_state);
+}
@@ -0,0 +1,98 @@
+use crate::{
+    app::{keymap::Action, App},
+    ethers::types::{AddressTransactions, TransactionHistorySource},
+    network::IoEvent,
+    ui::home::{block::transactions, keys},
+    widget::Spinner,
+};
+use ethers::core::types::Address;
+use ratatui::{prelude::*, widgets::*};
+
+pub fn render<B: Backend>(
+    f: &mut Frame<B>,
+    app: &mut App,
+    address: Address,
+    address_transactions: Option<AddressTransactions>,
+    block: Block,
+    rect: Rect,
+) {
+    let inner = block.inner(rect);
+    f.render_widget(block, rect);
+
+    let [status_rect, table_rect] = *Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)].as_ref())
+        .split(inner)
+    else {
+        return;
+    };
+
+    let failure = app.find_failure(|io_event| {
+        matches!(
+            io_event,
+            IoEvent::GetAddressTransactions { address: failed, .. } if *failed == address
+        )
+    });
+    let status = if let Some(notification) = failure {
+        Line::from(
+            Span::raw(format!(
+                "{notification} ({}: Retry)",
+                keys(app, Action::Refresh)
+            ))
+            .fg(app.theme.error),
+        )
+    } else if let Some(address_transactions) = address_transactions.as_ref() {
+        let range = match address_transactions.source {
+            TransactionHistorySource::Explorer { .. } => "from the explorer".to_owned(),
+            TransactionHistorySource::BlockScan { from, to } => {
+                format!("in blocks #{from} to #{to}")
+            }
+        };
+        Line::from(vec![
+            Span::raw(format!(
+                "Page {}, {} transactions {range}",
+                address_transactions.page + 1,
+                address_transactions.transactions.len()
+            ))
+            .fg(app.theme.text),
+            Span::raw(format!(
+                "  {}: Older, {}: Newer",
+                keys(app, Action::NextPage),
+                keys(app, Action::PrevPage)
+            ))
+            .fg(app.theme.muted),
+        ])
+    } else {
+        Line::from(
+            Span::raw(format!("Loading transactions {}", Spinner::default())).fg(app.theme.text),
+        )
+    };
+    f.render_widget(Paragraph::new(status), status_rect);
+
+    let Some(address_transactions) = address_transactions else {
+        return;
+    };
+    let rows = address_transactions
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(i, transaction)| {
+            Row::new(transactions::create_row(
+                i,
+                &transaction.transaction,
+                app,
+                Some(&transaction.transaction_receipt),
+            ))
+            .height(1)
+            .bottom_margin(1)
+        })
+        .collect::<Vec<_>>();
+
+    let widths = transactions::widths(app);
+    let t = Table::new(rows)
+        .header(transactions::header(app))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .widths(&widths);
+
+    f.render_stateful_widget(t, table_rect, &mut app.address_transactions_table_state);
+}
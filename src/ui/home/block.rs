@@ -1,7 +1,7 @@
 mod block_info;
 mod fee_info;
 mod gas_info;
-mod transactions;
+pub(super) mod transactions;
 mod withdrawals;
 use crate::{
     app::App,
@@ -63,13 +63,10 @@ pub fn render<B: Backend>(
 
         let detail_block = Block::default()
             .title(format!("Block #{}", block.number.unwrap()))
-            .border_style(
-                if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                },
-            )
+            .border_style(app.theme.border(matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            )))
             .padding(Padding::new(2, 2, 1, 1))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain);
@@ -82,13 +79,10 @@ pub fn render<B: Backend>(
     } else {
         let detail_block = Block::default()
             .title("Block Not Found")
-            .border_style(
-                if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                },
-            )
+            .border_style(app.theme.border(matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            )))
             .padding(Padding::new(2, 2, 1, 1))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain);
@@ -12,17 +12,12 @@ pub fn render<B: Backend>(
     rect: Rect,
 ) {
     let detail_block = Block::default()
-        .border_style(
-            if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                if let RouteId::Block(_) = app.get_current_route().get_id() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                }
-            } else {
-                Style::default().fg(Color::White)
-            },
-        )
+        .border_style(app.theme.border(
+            matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            ) && matches!(app.get_current_route().get_id(), RouteId::Block(_)),
+        ))
         .padding(Padding::new(2, 2, 2, 0))
         .borders(Borders::BOTTOM)
         .border_type(BorderType::Plain);
@@ -30,7 +25,7 @@ pub fn render<B: Backend>(
     let mut lines = vec![
         Line::from(
             Span::raw(format!("{:<20}: {}", "Block Height", block.number.unwrap()))
-                .fg(Color::White),
+                .fg(app.theme.text),
         ),
         //format!("{:<20}: {}", "Status", TODO),
         Line::from(
@@ -39,7 +34,7 @@ pub fn render<B: Backend>(
                 "Timestamp",
                 block.time().map_or("".to_string(), |time| time.to_string())
             ))
-            .fg(Color::White),
+            .fg(app.theme.text),
         ),
         //format!("{:<20}: Block proposed on slot {}, epoch {}", "Proposed On", TODO),
     ];
@@ -54,7 +49,7 @@ pub fn render<B: Backend>(
         },
         block.transactions.len()
     ))
-    .fg(Color::White);
+    .fg(app.theme.text);
 
     lines.push(
         if let RouteId::TransactionsOfBlock(_) = app.get_current_route().get_id() {
@@ -80,7 +75,7 @@ pub fn render<B: Backend>(
             },
             withdrawals.len()
         ))
-        .fg(Color::White);
+        .fg(app.theme.text);
         lines.push(Line::from(
             if app.block_detail_list_state.selected()
                 == Some(SelectableBlockDetailItem::Withdrawls.into())
@@ -12,23 +12,18 @@ pub fn render<B: Backend>(
     rect: Rect,
 ) {
     let detail_block = Block::default()
-        .border_style(
-            if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                if let RouteId::Block(_) = app.get_current_route().get_id() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                }
-            } else {
-                Style::default().fg(Color::White)
-            },
-        )
+        .border_style(app.theme.border(
+            matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            ) && matches!(app.get_current_route().get_id(), RouteId::Block(_)),
+        ))
         .padding(Padding::horizontal(2))
         .borders(Borders::BOTTOM)
         .border_type(BorderType::Plain);
 
     let fee_recipient_spans = vec![
-        Span::raw(format!("{:<20}: ", "Fee Recipient")).fg(Color::White),
+        Span::raw(format!("{:<20}: ", "Fee Recipient")).fg(app.theme.text),
         Span::styled(
             (if let Some(addr) = block.author {
                 format!("{:#x}", addr)
@@ -36,7 +31,7 @@ pub fn render<B: Backend>(
                 "pending...".to_string()
             })
             .to_string(),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(app.theme.address),
         ),
     ];
 
@@ -54,13 +49,14 @@ pub fn render<B: Backend>(
             },
         ),
         Line::from(
-            Span::raw(format!("{:<20}: {} bytes", "Size", block.size.unwrap())).fg(Color::White),
+            Span::raw(format!("{:<20}: {} bytes", "Size", block.size.unwrap())).fg(app.theme.text),
         ),
     ];
 
     if let Some(total_difficulty) = block.total_difficulty {
         details.push(Line::from(
-            Span::raw(format!("{:<20}: {}", "Total Difficulty", total_difficulty)).fg(Color::White),
+            Span::raw(format!("{:<20}: {}", "Total Difficulty", total_difficulty))
+                .fg(app.theme.text),
         ));
     }
 
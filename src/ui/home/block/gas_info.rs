@@ -15,17 +15,12 @@ pub fn render<B: Backend>(
     rect: Rect,
 ) {
     let detail_block = Block::default()
-        .border_style(
-            if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                if let RouteId::Block(_) = app.get_current_route().get_id() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                }
-            } else {
-                Style::default().fg(Color::White)
-            },
-        )
+        .border_style(app.theme.border(
+            matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            ) && matches!(app.get_current_route().get_id(), RouteId::Block(_)),
+        ))
         .padding(Padding::horizontal(2))
         .borders(Borders::NONE)
         .border_type(BorderType::Plain);
@@ -38,37 +33,40 @@ pub fn render<B: Backend>(
                 block.gas_used,
                 block.gas_used * 100 / block.gas_limit
             ))
-            .fg(Color::White),
+            .fg(app.theme.text),
+        ),
+        Line::from(
+            Span::raw(format!("{:<20}: {}", "Gas Limit", block.gas_limit)).fg(app.theme.text),
         ),
-        Line::from(Span::raw(format!("{:<20}: {}", "Gas Limit", block.gas_limit)).fg(Color::White)),
     ];
 
     // if past London
     if let Some(base_fee_per_gas) = block.base_fee_per_gas {
         details.push(Line::from(
             Span::raw(format!(
-                "{:<20}: {} ETH ({} Gwei)",
+                "{:<20}: {} {} ({} Gwei)",
                 "Base Fee Per Gas",
                 format_ether(base_fee_per_gas),
+                app.chain.native_currency_symbol(),
                 format_units(base_fee_per_gas, "gwei").unwrap()
             ))
-            .fg(Color::White),
+            .fg(app.theme.text),
         ));
     }
 
     let parent_hash_spans = vec![
-        Span::raw(format!("{:<20}: ", "Parent Hash")).fg(Color::White),
+        Span::raw(format!("{:<20}: ", "Parent Hash")).fg(app.theme.text),
         Span::styled(
             format!("{:#x}", block.parent_hash),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(app.theme.address),
         ),
     ];
     details.append(&mut vec![
         //format!("{:<20}: {}", "Burnt Fees", TODO),
         //format!("{:<20}: {}", "Extra Data", TODO),
-        Line::from(Span::raw("More Details".to_string()).fg(Color::White)),
+        Line::from(Span::raw("More Details".to_string()).fg(app.theme.text)),
         Line::from(
-            Span::raw(format!("{:<20}: {:#x}", "Hash", block.hash.unwrap())).fg(Color::White),
+            Span::raw(format!("{:<20}: {:#x}", "Hash", block.hash.unwrap())).fg(app.theme.text),
         ),
         Line::from(
             if app.block_detail_list_state.selected()
@@ -83,7 +81,7 @@ pub fn render<B: Backend>(
             },
         ),
         Line::from(
-            Span::raw(format!("{:<20}: {:#x}", "StateRoot", block.state_root)).fg(Color::White),
+            Span::raw(format!("{:<20}: {:#x}", "StateRoot", block.state_root)).fg(app.theme.text),
         ),
     ]);
 
@@ -94,12 +92,12 @@ pub fn render<B: Backend>(
                 "{:<20}: {:#x}",
                 "WithdrawalsRoot", withdrawals_root
             ))
-            .fg(Color::White),
+            .fg(app.theme.text),
         ));
     }
 
     details.push(Line::from(
-        Span::raw(format!("{:<20}: {:#x}", "Nonce", block.nonce.unwrap())).fg(Color::White),
+        Span::raw(format!("{:<20}: {:#x}", "Nonce", block.nonce.unwrap())).fg(app.theme.text),
     ));
 
     let paragraph = Paragraph::new(details)
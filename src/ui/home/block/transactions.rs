@@ -7,7 +7,7 @@ use crate::{
 use anyhow::Result;
 use ethers::core::{
     types::{Transaction, TransactionReceipt, U64},
-    utils::{format_ether, format_units},
+    utils::{format_ether, format_units, hex},
 };
 use ratatui::{prelude::*, widgets::*};
 
@@ -23,7 +23,68 @@ pub fn render<B: Backend>(
     } = block_with_transaction_receipts;
 
     let selected_style = Style::default().add_modifier(Modifier::BOLD);
-    let normal_style = Style::default().fg(Color::White);
+    let items = block
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(i, tx)| {
+            if let Some(transaction_receipts) = transaction_receipts {
+                create_row(
+                    i,
+                    tx,
+                    app,
+                    transaction_receipts
+                        .iter()
+                        .find(|receipt| receipt.transaction_hash == tx.hash),
+                )
+            } else {
+                create_row(i, tx, app, None)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let rows = items
+        .iter()
+        .map(|cells| Row::new(cells.to_owned()).height(1).bottom_margin(1));
+
+    let widths = widths(app);
+    let t = Table::new(rows.to_owned())
+        .header(header(app))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(
+                    match block
+                        .transactions
+                        .iter()
+                        .filter(|tx| app.failed_receipts.contains(&tx.hash))
+                        .count()
+                    {
+                        0 => "Transactions".to_owned(),
+                        n => format!("Transactions ({n} receipts failed to load)"),
+                    },
+                )
+                .border_style(app.theme.border(
+                    matches!(
+                        app.get_current_route().get_active_block(),
+                        ActiveBlock::Main
+                    ) && matches!(
+                        app.get_current_route().get_id(),
+                        RouteId::TransactionsOfBlock(_)
+                    ),
+                )),
+        )
+        .highlight_style(selected_style)
+        .widths(&widths);
+
+    f.render_stateful_widget(t, rect, &mut app.transactions_table_state);
+    Ok(())
+}
+
+/// The column headers, shared with the transactions of an address.
+pub fn header(app: &App) -> Row<'static> {
+    let normal_style = Style::default().fg(app.theme.text);
+    let value_title = format!("Value ({})", app.chain.native_currency_symbol());
     let header = if app.is_toggled {
         vec![
             "",
@@ -32,7 +93,7 @@ pub fn render<B: Backend>(
             "Type",
             "From",
             "To",
-            "Value (ETH)",
+            value_title.as_str(),
             "Fee",
             "Gas Price (Gwei)",
             "Gas Used",
@@ -47,7 +108,7 @@ pub fn render<B: Backend>(
             "Type",
             "From",
             "To",
-            "Value (ETH)",
+            value_title.as_str(),
             //"Fee",
             "Gas Price (Gwei)",
             //"Gas Used",
@@ -58,44 +119,23 @@ pub fn render<B: Backend>(
 
     let header_cells = header
         .iter()
-        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
-    let header = Row::new(header_cells)
+        .map(|h| Cell::from(h.to_string()).style(Style::default().add_modifier(Modifier::BOLD)));
+    Row::new(header_cells.collect::<Vec<_>>())
         .style(normal_style)
         .height(1)
-        .bottom_margin(1);
-    let items = block
-        .transactions
-        .iter()
-        .enumerate()
-        .map(|(i, tx)| {
-            if let Some(transaction_receipts) = transaction_receipts {
-                create_row(
-                    i,
-                    tx,
-                    app,
-                    transaction_receipts
-                        .iter()
-                        .find(|receipt| receipt.transaction_hash == tx.hash),
-                )
-            } else {
-                create_row(i, tx, app, None)
-            }
-        })
-        .collect::<Vec<_>>();
-
-    let rows = items
-        .iter()
-        .map(|cells| Row::new(cells.to_owned()).height(1).bottom_margin(1));
+        .bottom_margin(1)
+}
 
-    let widths = if app.is_toggled {
+pub fn widths(app: &App) -> Vec<Constraint> {
+    if app.is_toggled {
         vec![
             Constraint::Max(4),
             Constraint::Max(12), //Hash
-            Constraint::Max(18), //Method
+            Constraint::Max(32), //Method
             Constraint::Max(10), //Type
             Constraint::Max(12), //From
             Constraint::Max(12), //To
-            Constraint::Max(20), //Value (ETH)
+            Constraint::Max(20), //Value
             Constraint::Max(10), //Fee
             Constraint::Max(20), //Gas Price (Gwei)
             Constraint::Max(10), //Gas Used
@@ -106,57 +146,51 @@ pub fn render<B: Backend>(
         vec![
             Constraint::Max(4),
             Constraint::Max(12), //Hash
-            Constraint::Max(18), //Method
+            Constraint::Max(32), //Method
             Constraint::Max(10), //Type
             Constraint::Max(12), //From
             Constraint::Max(12), //To
-            Constraint::Max(20), //Value (ETH)
+            Constraint::Max(20), //Value
             Constraint::Max(20), //Gas Price (Gwei)
         ]
-    };
-
-    let t = Table::new(rows.to_owned())
-        .header(header)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Transactions")
-                .fg(
-                    if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                        if let RouteId::TransactionsOfBlock(_) = app.get_current_route().get_id() {
-                            Color::Green
-                        } else {
-                            Color::White
-                        }
-                    } else {
-                        Color::White
-                    },
-                ),
-        )
-        .highlight_style(selected_style)
-        .widths(&widths);
-
-    f.render_stateful_widget(t, rect, &mut app.transactions_table_state);
-    Ok(())
+    }
 }
 
-fn create_row<'a>(
+/// The cells of transaction `i`, or placeholders for the parts of `transaction_receipt` that
+/// haven't loaded.
+pub fn create_row<'a>(
     i: usize,
     tx: &Transaction,
     app: &App,
     transaction_receipt: Option<&TransactionReceipt>,
 ) -> Vec<Cell<'a>> {
+    let receipt_placeholder = || {
+        if app.failed_receipts.contains(&tx.hash) {
+            "Failed".to_owned()
+        } else {
+            Spinner::default().to_string()
+        }
+    };
+
     let mut row = vec![
-        Cell::from(format!(" {} ", i + 1)).fg(Color::White),
-        Cell::from(format!("{}", tx.hash)).fg(Color::White),
+        Cell::from(format!(" {} ", i + 1)).fg(app.theme.text),
+        Cell::from(format!("{}", tx.hash)).fg(app.theme.text),
         if tx.to.is_some() {
             if tx.input.len() >= 4 {
-                Cell::from("ContractExecution").fg(Color::LightYellow)
+                Cell::from(
+                    app.signatures
+                        .find_functions(&tx.input[..4])
+                        .first()
+                        .map_or(format!("0x{}", hex::encode(&tx.input[..4])), |function| {
+                            function.signature()
+                        }),
+                )
+                .fg(app.theme.method)
             } else {
-                Cell::from("Transfer").fg(Color::LightMagenta)
+                Cell::from("Transfer").fg(app.theme.transfer)
             }
         } else {
-            Cell::from("ContractDeployment").fg(Color::LightCyan)
+            Cell::from("ContractDeployment").fg(app.theme.contract_creation)
         },
         Cell::from(
             (match tx.transaction_type {
@@ -173,7 +207,7 @@ fn create_row<'a>(
             })
             .to_string(),
         )
-        .fg(Color::White),
+        .fg(app.theme.text),
         Cell::from(
             if let Some(token) = ERC20Token::find_by_address(&app.erc20_tokens, tx.from) {
                 token.ticker.to_string()
@@ -187,15 +221,15 @@ fn create_row<'a>(
         )
         .fg(
             if ERC20Token::find_by_address(&app.erc20_tokens, tx.from).is_some() {
-                Color::Cyan
+                app.theme.address
             } else if let Some(ens_id) = app.address2ens_id.get(&tx.from) {
                 if ens_id.is_some() {
-                    Color::Cyan
+                    app.theme.address
                 } else {
-                    Color::White
+                    app.theme.text
                 }
             } else {
-                Color::White
+                app.theme.text
             },
         ),
         Cell::from(tx.to.map_or("".to_owned(), |to| {
@@ -209,34 +243,33 @@ fn create_row<'a>(
                 format!("{to}")
             }
         }))
-        .fg(tx.to.map_or(Color::White, |to| {
+        .fg(tx.to.map_or(app.theme.text, |to| {
             if ERC20Token::find_by_address(&app.erc20_tokens, to).is_some() {
-                Color::Cyan
+                app.theme.address
             } else if let Some(ens_id) = app.address2ens_id.get(&to) {
                 if ens_id.is_some() {
-                    Color::Cyan
+                    app.theme.address
                 } else {
-                    Color::White
+                    app.theme.text
                 }
             } else {
-                Color::White
+                app.theme.text
             }
         })),
-        Cell::from(format_ether(tx.value).to_string()).fg(Color::White),
+        Cell::from(format_ether(tx.value).to_string()).fg(app.theme.text),
     ];
 
     if app.is_toggled {
         row.push(
             Cell::from(if let Some(transaction_receipt) = transaction_receipt {
-                transaction_receipt
-                    .gas_used
-                    .map_or(Spinner::default().to_string(), |gas_used| {
-                        format_ether(tx.gas_price.unwrap() * gas_used)
-                    })
+                transaction_receipt.gas_used.map_or_else(
+                    || Spinner::default().to_string(),
+                    |gas_used| format_ether(tx.gas_price.unwrap() * gas_used),
+                )
             } else {
-                Spinner::default().to_string()
+                receipt_placeholder()
             })
-            .fg(Color::White),
+            .fg(app.theme.text),
         );
     }
 
@@ -246,7 +279,7 @@ fn create_row<'a>(
                 .unwrap()
                 .to_string(),
         )
-        .fg(Color::White),
+        .fg(app.theme.text),
     );
 
     if app.is_toggled {
@@ -258,29 +291,29 @@ fn create_row<'a>(
                         format_units(gas_used, "gwei").unwrap().to_string()
                     })
             } else {
-                Spinner::default().to_string()
+                receipt_placeholder()
             })
-            .fg(Color::White),
+            .fg(app.theme.text),
             if let Some(transaction_receipt) = transaction_receipt {
-                transaction_receipt.status.map_or(
-                    Cell::from(Spinner::default().to_string()).fg(Color::White),
+                transaction_receipt.status.map_or_else(
+                    || Cell::from(Spinner::default().to_string()).fg(app.theme.text),
                     |status| {
                         if status == U64::from(0) {
-                            Cell::from("Failure".to_string()).fg(Color::Red)
+                            Cell::from("Failure".to_string()).fg(app.theme.error)
                         } else {
-                            Cell::from("Success".to_string()).fg(Color::Green)
+                            Cell::from("Success".to_string()).fg(app.theme.success)
                         }
                     },
                 )
             } else {
-                Cell::from(Spinner::default().to_string()).fg(Color::White)
+                Cell::from(receipt_placeholder()).fg(app.theme.text)
             },
             Cell::from(if let Some(transaction_receipt) = transaction_receipt {
                 transaction_receipt.logs.len().to_string()
             } else {
-                Spinner::default().to_string()
+                receipt_placeholder()
             })
-            .fg(Color::White),
+            .fg(app.theme.text),
         ]);
     }
 
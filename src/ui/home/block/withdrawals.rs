@@ -18,7 +18,7 @@ pub fn render<B: Backend>(
     } = block_with_transaction_receipts;
 
     let selected_style = Style::default().add_modifier(Modifier::BOLD);
-    let normal_style = Style::default().fg(Color::White);
+    let normal_style = Style::default().fg(app.theme.text);
     let header_cells = ["", "Index", "Validator Index", "Address", "Amount"]
         .iter()
         .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
@@ -32,11 +32,11 @@ pub fn render<B: Backend>(
             .enumerate()
             .map(|(i, withdrawal)| {
                 vec![
-                    Cell::from(format!("{}", i + 1)).fg(Color::White),
-                    Cell::from(format!("{}", withdrawal.index)).fg(Color::White),
-                    Cell::from(format!("{}", withdrawal.validator_index)).fg(Color::White),
-                    Cell::from(format!("{}", withdrawal.address)).fg(Color::White),
-                    Cell::from(format!("{}", withdrawal.amount)).fg(Color::White),
+                    Cell::from(format!("{}", i + 1)).fg(app.theme.text),
+                    Cell::from(format!("{}", withdrawal.index)).fg(app.theme.text),
+                    Cell::from(format!("{}", withdrawal.validator_index)).fg(app.theme.text),
+                    Cell::from(format!("{}", withdrawal.address)).fg(app.theme.text),
+                    Cell::from(format!("{}", withdrawal.amount)).fg(app.theme.text),
                 ]
             })
             .collect::<Vec<_>>()
@@ -52,17 +52,15 @@ pub fn render<B: Backend>(
             Block::default()
                 .borders(Borders::ALL)
                 .title("Withdrawals")
-                .fg(
-                    if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                        if let RouteId::WithdrawalsOfBlock(_) = app.get_current_route().get_id() {
-                            Color::Green
-                        } else {
-                            Color::White
-                        }
-                    } else {
-                        Color::White
-                    },
-                ),
+                .border_style(app.theme.border(
+                    matches!(
+                        app.get_current_route().get_active_block(),
+                        ActiveBlock::Main
+                    ) && matches!(
+                        app.get_current_route().get_id(),
+                        RouteId::WithdrawalsOfBlock(_)
+                    ),
+                )),
         )
         .highlight_style(selected_style)
         .widths(&[
@@ -0,0 +1,31 @@
+use crate::{
+    app::{keymap::Action, notification::Notification, App},
+    ui::home::keys,
+};
+use ratatui::{prelude::*, widgets::*};
+
+pub fn render<B: Backend>(f: &mut Frame<B>, app: &App, notification: &Notification, rect: Rect) {
+    let failed_block = Block::default()
+        .title(format!("Failed to load {}", notification.io_event))
+        .border_style(Style::default().fg(app.theme.error))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Plain);
+
+    let details = Paragraph::new(vec![
+        Line::from(
+            Span::raw(format!(" {:<9}: {}", "Endpoint", notification.endpoint)).fg(app.theme.text),
+        ),
+        Line::from(
+            Span::raw(format!(" {:<9}: {}", "Error", notification.message)).fg(app.theme.error),
+        ),
+        Line::from(""),
+        Line::from(
+            Span::raw(format!(" Press {} to retry.", keys(app, Action::Refresh)))
+                .fg(app.theme.text),
+        ),
+    ])
+    .block(failed_block)
+    .wrap(Wrap { trim: false });
+
+    f.render_widget(details, rect);
+}
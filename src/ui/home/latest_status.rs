@@ -1,7 +1,9 @@
 use crate::{
-    app::App,
+    app::{keymap::Action, App},
     ethers::types::{BlockWithTransactionReceipts, ERC20Token},
+    network::{IoEvent, NEW_BLOCK_HIGHLIGHT_SECS},
     route::ActiveBlock,
+    ui::home::keys,
     widget::Spinner,
 };
 use chrono::Utc;
@@ -19,26 +21,23 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, rect: Rect) {
     };
 
     let latest_blocks_block = Block::default()
-        .title("Latest Blocks")
-        .border_style(Style::default().fg(
-            if let ActiveBlock::LatestBlocks = app.get_current_route().get_active_block() {
-                Color::Green
-            } else {
-                Color::White
-            },
-        ))
+        .title(match app.failed_latest_blocks {
+            0 => "Latest Blocks".to_owned(),
+            n => format!("Latest Blocks ({n} failed to load)"),
+        })
+        .border_style(app.theme.border(matches!(
+            app.get_current_route().get_active_block(),
+            ActiveBlock::LatestBlocks
+        )))
         .borders(Borders::ALL)
         .border_type(BorderType::Plain);
 
     let latest_transactions_block = Block::default()
         .title("Latest Transactions")
-        .border_style(Style::default().fg(
-            if let ActiveBlock::LatestTransactions = app.get_current_route().get_active_block() {
-                Color::Green
-            } else {
-                Color::White
-            },
-        ))
+        .border_style(app.theme.border(matches!(
+            app.get_current_route().get_active_block(),
+            ActiveBlock::LatestTransactions
+        )))
         .borders(Borders::ALL)
         .border_type(BorderType::Plain);
 
@@ -64,25 +63,47 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, rect: Rect) {
                 transaction_receipts: _,
             } = block_with_transaction_receipts;
 
-            res.push(ListItem::new(format!(
-                "{:>13} | {:>12} | {:>7} txns | {:>4} secs ago |",
-                block.number.unwrap(),
-                block.hash.unwrap(),
-                block.transactions.len(),
-                (Utc::now() - block.time().unwrap()).num_seconds()
-            )));
+            let is_new = block.number.is_some_and(|number| {
+                app.new_block_arrivals.get(&number).is_some_and(|arrival| {
+                    (Utc::now() - *arrival).num_seconds() < NEW_BLOCK_HIGHLIGHT_SECS
+                })
+            });
+
+            res.push(
+                ListItem::new(format!(
+                    "{:>13} | {:>12} | {:>7} txns | {:>4} secs ago |",
+                    block.number.unwrap(),
+                    block.hash.unwrap(),
+                    block.transactions.len(),
+                    (Utc::now() - block.time().unwrap()).num_seconds()
+                ))
+                .style(if is_new {
+                    Style::default().fg(app.theme.highlight)
+                } else {
+                    Style::default()
+                }),
+            );
         }
         List::new(res)
     } else {
         let mut res = header.to_owned();
-        res.push(ListItem::new(format!(
-            " Loading {}",
-            Spinner::default().to_string()
-        )));
+        res.push(
+            if let Some(notification) =
+                app.find_failure(|io_event| matches!(io_event, IoEvent::GetLatestBlocks { .. }))
+            {
+                ListItem::new(format!(
+                    " {notification} ({}: Retry)",
+                    keys(app, Action::Refresh)
+                ))
+                .fg(app.theme.error)
+            } else {
+                ListItem::new(format!(" Loading {}", Spinner::default()))
+            },
+        );
         List::new(res)
     }
     .block(latest_blocks_block.to_owned())
-    .style(Style::default().fg(Color::White))
+    .style(Style::default().fg(app.theme.text))
     .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     f.render_stateful_widget(
@@ -96,7 +117,9 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, rect: Rect) {
     let header = vec![
         ListItem::new(format!(
             "{:^22} | {:^22} | {:^11} |",
-            "From", "To", "Value (ETH)"
+            "From",
+            "To",
+            format!("Value ({})", app.chain.native_currency_symbol())
         )),
         ListItem::new(format!(
             "{}+{}+{}|",
@@ -141,14 +164,23 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, rect: Rect) {
         List::new(res)
     } else {
         let mut res = header.to_owned();
-        res.push(ListItem::new(format!(
-            " Loading {}",
-            Spinner::default().to_string()
-        )));
+        res.push(
+            if let Some(notification) = app
+                .find_failure(|io_event| matches!(io_event, IoEvent::GetLatestTransactions { .. }))
+            {
+                ListItem::new(format!(
+                    " {notification} ({}: Retry)",
+                    keys(app, Action::Refresh)
+                ))
+                .fg(app.theme.error)
+            } else {
+                ListItem::new(format!(" Loading {}", Spinner::default()))
+            },
+        );
         List::new(res)
     }
     .block(latest_transactions_block.to_owned())
-    .style(Style::default().fg(Color::White))
+    .style(Style::default().fg(app.theme.text))
     .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     f.render_stateful_widget(
@@ -1,13 +1,10 @@
-use crate::widget::Spinner;
+use crate::{app::App, widget::Spinner};
 use ratatui::{prelude::*, widgets::*};
 
-pub fn render<B: Backend>(f: &mut Frame<B>, word: &str, rect: Rect) {
+pub fn render<B: Backend>(f: &mut Frame<B>, app: &App, word: &str, rect: Rect) {
     let searching_block = Block::default()
-        .title(format!(
-            "{} Searching for {word}",
-            Spinner::default().to_string()
-        ))
-        .border_style(Style::default().fg(Color::Green))
+        .title(format!("{} Searching for {word}", Spinner::default()))
+        .border_style(app.theme.border(true))
         .borders(Borders::ALL)
         .border_type(BorderType::Plain);
 
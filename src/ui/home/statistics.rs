@@ -1,5 +1,6 @@
 use crate::{
     app::{statistics::Statistics, App},
+    network::{chain::ExplorerStatus, IoEvent},
     widget::Spinner,
 };
 use anyhow::{bail, Context, Result};
@@ -57,8 +58,9 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, rect: Rect) -> Result
         statistics5,
     ];
 
+    let price_title = format!("{} PRICE", app.chain.native_currency_symbol());
     let statistic_titles = [
-        "ETHER PRICE",
+        price_title.as_str(),
         "SUGGESTED BASE FEE",
         "LAST SAFE BLOCK",
         "NODE COUNT",
@@ -66,10 +68,26 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, rect: Rect) -> Result
         "LAST FINALIZED BLOCK",
     ];
 
+    let is_failed = app
+        .find_failure(|io_event| matches!(io_event, IoEvent::GetStatistics))
+        .is_some();
+    let placeholder = || {
+        if is_failed {
+            "Failed".to_owned()
+        } else {
+            Spinner::default().to_string()
+        }
+    };
+    let explorer_placeholder = || match app.explorer_status {
+        ExplorerStatus::Enabled => placeholder(),
+        ExplorerStatus::Disabled => "No explorer API key".to_owned(),
+        ExplorerStatus::Failing(_) => "Explorer failed".to_owned(),
+    };
+
     for (i, &statistic_item) in statistic_items.iter().enumerate() {
         let block = Block::default()
             .title(statistic_titles[i])
-            .border_style(Style::default().fg(Color::White))
+            .border_style(Style::default().fg(app.theme.text))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain);
 
@@ -84,27 +102,27 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, rect: Rect) -> Result
 
         let text = if i == Statistics::ETHUSD_INDEX {
             if let Some(ethusd) = app.statistics.ethusd.as_ref() {
-                format!("{:.4} USD/ETH", ethusd)
+                format!("{:.4} USD/{}", ethusd, app.chain.native_currency_symbol())
             } else {
-                Spinner::default().to_string()
+                explorer_placeholder()
             }
         } else if i == Statistics::SUGGESTED_BASE_FEE_INDEX {
             if let Some(suggested_base_fee) = app.statistics.suggested_base_fee {
                 format!("{} Gwei", format_units(suggested_base_fee, "gwei")?)
             } else {
-                Spinner::default().to_string()
+                explorer_placeholder()
             }
         } else if i == Statistics::NODE_COUNT_INDEX {
             if let Some(node_count) = app.statistics.node_count.as_ref() {
                 format!("{node_count} nodes")
             } else {
-                Spinner::default().to_string()
+                explorer_placeholder()
             }
         } else if i == Statistics::LAST_SAFE_BLOCK_INDEX {
             if let Some(block) = app.statistics.last_safe_block.as_ref() {
                 format!("#{}", block.number.context("Block Number is None")?)
             } else {
-                Spinner::default().to_string()
+                placeholder()
             }
         } else if i == Statistics::MED_GAS_PRICE_INDEX {
             if let Some(med_gas_price) = app.statistics.med_gas_price {
@@ -113,19 +131,19 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &mut App, rect: Rect) -> Result
                     format_units(med_gas_price, "gwei").context("Failed to parse gas price")?
                 )
             } else {
-                Spinner::default().to_string()
+                explorer_placeholder()
             }
         } else if i == Statistics::LAST_FINALIZED_BLOCK_INDEX {
             if let Some(block) = app.statistics.last_finalized_block.as_ref() {
                 format!("#{}", block.number.context("Block Number is None")?)
             } else {
-                Spinner::default().to_string()
+                placeholder()
             }
         } else {
-            Spinner::default().to_string()
+            placeholder()
         };
 
-        let paragraph = Paragraph::new(vec![Line::from(Span::raw(text).fg(Color::White))])
+        let paragraph = Paragraph::new(vec![Line::from(Span::raw(text).fg(app.theme.text))])
             .block(block.to_owned())
             .alignment(Alignment::Right)
             .wrap(Wrap { trim: true });
@@ -2,13 +2,14 @@ use crate::{
     app::transaction::{SelectableInputDataDetailItem, SelectableTransactionDetailItem},
     ethers::{
         transaction::calculate_transaction_fee,
-        types::{ERC20Token, TransactionWithReceipt},
+        types::{DecodedEvent, DecodedParam, ERC20Token, TransactionWithReceipt},
     },
     route::{ActiveBlock, RouteId},
+    ui::theme::Theme,
     App,
 };
 use ethers::core::{
-    types::U64,
+    types::{Log, U64},
     utils::{format_ether, format_units},
 };
 use ratatui::{prelude::*, widgets::*};
@@ -24,17 +25,15 @@ pub fn render<B: Backend>(
             transaction,
             transaction_receipt,
             decoded_input_data,
+            decoded_logs,
         } = transaction_with_receipt;
 
         let detail_block = Block::default()
             .title("Transaction Details")
-            .border_style(
-                if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                },
-            )
+            .border_style(app.theme.border(matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            )))
             .padding(Padding::new(2, 2, 1, 1))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain);
@@ -53,15 +52,15 @@ pub fn render<B: Backend>(
                     "{:<17}: {:#x}",
                     "Transaction Hash", transaction.hash
                 ))
-                .fg(Color::White),
+                .fg(app.theme.text),
             ),
             Line::from(vec![
-                Span::raw(format!("{:<17}: ", "Status")).fg(Color::White),
+                Span::raw(format!("{:<17}: ", "Status")).fg(app.theme.text),
                 transaction_receipt.status.map_or(Span::raw(""), |status| {
                     if status == U64::from(0) {
-                        Span::styled("Failure", Style::default().fg(Color::Red))
+                        Span::styled("Failure", Style::default().fg(app.theme.error))
                     } else {
-                        Span::styled("Success", Style::default().fg(Color::Green))
+                        Span::styled("Success", Style::default().fg(app.theme.success))
                     }
                 }),
             ]),
@@ -73,7 +72,7 @@ pub fn render<B: Backend>(
                         .block_number
                         .map_or("pending...".to_owned(), |number| number.to_string())
                 ))
-                .fg(Color::White),
+                .fg(app.theme.text),
             ),
             Line::from(
                 if app.transaction_detail_list_state.selected()
@@ -81,7 +80,7 @@ pub fn render<B: Backend>(
                 {
                     vec![
                         Span::raw(format!("{:<17}: ", "From"))
-                            .fg(Color::White)
+                            .fg(app.theme.text)
                             .add_modifier(Modifier::BOLD),
                         Span::styled(
                             format!(
@@ -102,13 +101,13 @@ pub fn render<B: Backend>(
                                 }
                             ),
                             Style::default()
-                                .fg(Color::Cyan)
+                                .fg(app.theme.address)
                                 .add_modifier(Modifier::BOLD),
                         ),
                     ]
                 } else {
                     vec![
-                        Span::raw(format!("{:<17}: ", "From")).fg(Color::White),
+                        Span::raw(format!("{:<17}: ", "From")).fg(app.theme.text),
                         Span::styled(
                             format!(
                                 "{:#x} {}",
@@ -127,7 +126,7 @@ pub fn render<B: Backend>(
                                     "".to_owned()
                                 }
                             ),
-                            Style::default().fg(Color::Cyan),
+                            Style::default().fg(app.theme.address),
                         ),
                     ]
                 },
@@ -138,7 +137,7 @@ pub fn render<B: Backend>(
                 {
                     vec![
                         Span::raw(format!("{:<17}: ", "To"))
-                            .fg(Color::White)
+                            .fg(app.theme.text)
                             .add_modifier(Modifier::BOLD),
                         Span::styled(
                             transaction
@@ -162,13 +161,13 @@ pub fn render<B: Backend>(
                                 })
                                 .to_string(),
                             Style::default()
-                                .fg(Color::Cyan)
+                                .fg(app.theme.address)
                                 .add_modifier(Modifier::BOLD),
                         ),
                     ]
                 } else {
                     vec![
-                        Span::raw(format!("{:<17}: ", "To")).fg(Color::White),
+                        Span::raw(format!("{:<17}: ", "To")).fg(app.theme.text),
                         Span::styled(
                             transaction
                                 .to
@@ -190,7 +189,7 @@ pub fn render<B: Backend>(
                                     )
                                 })
                                 .to_string(),
-                            Style::default().fg(Color::Cyan),
+                            Style::default().fg(app.theme.address),
                         ),
                     ]
                 },
@@ -207,25 +206,27 @@ pub fn render<B: Backend>(
                         }
                     })
                 ))
-                .fg(Color::White),
+                .fg(app.theme.text),
             ),
-            Line::from(Span::raw(format!("{:<17}: {}", "Gas", transaction.gas)).fg(Color::White)),
+            Line::from(Span::raw(format!("{:<17}: {}", "Gas", transaction.gas)).fg(app.theme.text)),
             Line::from(
                 Span::raw(format!(
-                    "{:<17}: {} ETH",
+                    "{:<17}: {} {}",
                     "Value",
-                    format_ether(transaction.value)
+                    format_ether(transaction.value),
+                    app.chain.native_currency_symbol()
                 ))
-                .fg(Color::White),
+                .fg(app.theme.text),
             ),
             Line::from(
                 Span::raw(format!(
-                    "{:<17}: {} ETH",
+                    "{:<17}: {} {}",
                     "Transaction Fee",
                     calculate_transaction_fee(&transaction, &transaction_receipt, None)
-                        .unwrap_or("".to_string())
+                        .unwrap_or("".to_string()),
+                    app.chain.native_currency_symbol()
                 ))
-                .fg(Color::White),
+                .fg(app.theme.text),
             ),
         ];
 
@@ -236,7 +237,7 @@ pub fn render<B: Backend>(
                     "Gas Price",
                     format_units(gas_price, "gwei").unwrap()
                 ))
-                .fg(Color::White),
+                .fg(app.theme.text),
             ));
         }
 
@@ -253,7 +254,7 @@ pub fn render<B: Backend>(
                         "▶"
                     }
                 ))
-                .fg(Color::White)
+                .fg(app.theme.text)
                 .add_modifier(Modifier::BOLD)
             } else {
                 Span::raw(format!(
@@ -265,7 +266,7 @@ pub fn render<B: Backend>(
                         "▶"
                     }
                 ))
-                .fg(Color::White)
+                .fg(app.theme.text)
             },
         ));
 
@@ -281,22 +282,38 @@ pub fn render<B: Backend>(
         let mut raw_input_data = vec![];
         for (idx, line) in input_data.iter().enumerate() {
             raw_input_data.push(Line::from(vec![
-                Span::raw(format!("{:>3}  ", idx + 1)).fg(Color::Gray),
-                Span::raw(line.to_string()).fg(Color::White),
+                Span::raw(format!("{:>3}  ", idx + 1)).fg(app.theme.muted),
+                Span::raw(line.to_string()).fg(app.theme.text),
             ]));
         }
 
         let mut raw_decoded_input_data = vec![];
 
         if let Some(decoded_input_data) = decoded_input_data {
-            for (idx, line) in decoded_input_data.split('\n').enumerate() {
-                raw_decoded_input_data.push(Line::from(vec![
-                    Span::raw(format!("{:>3}  ", idx + 1)).fg(Color::Gray),
-                    Span::raw(line.to_string()).fg(Color::White),
-                ]));
+            for (idx, line) in decoded_lines(
+                &app.theme,
+                &decoded_input_data.signature,
+                &decoded_input_data.params,
+            )
+            .into_iter()
+            .enumerate()
+            {
+                let mut spans = vec![Span::raw(format!("{:>3}  ", idx + 1)).fg(app.theme.muted)];
+                spans.extend(line.spans);
+                raw_decoded_input_data.push(Line::from(spans));
             }
         }
 
+        let mut raw_logs = vec![];
+        for (idx, line) in log_lines(app, &transaction_receipt.logs, &decoded_logs)
+            .into_iter()
+            .enumerate()
+        {
+            let mut spans = vec![Span::raw(format!("{:>3}  ", idx + 1)).fg(app.theme.muted)];
+            spans.extend(line.spans);
+            raw_logs.push(Line::from(spans));
+        }
+
         app.input_data_scroll_state = app
             .input_data_scroll_state
             .content_length(raw_input_data.len() as u16);
@@ -305,6 +322,14 @@ pub fn render<B: Backend>(
             .decoded_input_data_scroll_state
             .content_length(raw_decoded_input_data.len() as u16);
 
+        app.logs_scroll_state = app.logs_scroll_state.content_length(raw_logs.len() as u16);
+
+        let selected_item = SelectableInputDataDetailItem::from(
+            app.input_data_detail_list_state
+                .selected()
+                .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
+        );
+
         if app.is_toggled {
             let chunks = Layout::default()
                 .direction(Direction::Horizontal)
@@ -326,15 +351,17 @@ pub fn render<B: Backend>(
                         {
                             Block::default()
                                 .borders(Borders::ALL)
-                                .green()
+                                .fg(app.theme.focus)
                                 .title(Span::styled(
                                     "INPUT DATA",
-                                    Style::default().add_modifier(Modifier::BOLD).green(),
+                                    Style::default()
+                                        .add_modifier(Modifier::BOLD)
+                                        .fg(app.theme.focus),
                                 ))
                         } else {
                             Block::default()
                                 .borders(Borders::ALL)
-                                .gray()
+                                .fg(app.theme.muted)
                                 .title(Span::styled(
                                     "INPUT DATA",
                                     Style::default().add_modifier(Modifier::BOLD),
@@ -355,38 +382,54 @@ pub fn render<B: Backend>(
                 &mut app.input_data_scroll_state,
             );
 
-            // render DECODED INPUT DATA
+            // render DECODED INPUT DATA, or LOGS while they are selected
+            let (title, lines, scroll, scroll_state, is_selected) = match selected_item {
+                SelectableInputDataDetailItem::Logs => (
+                    "LOGS",
+                    raw_logs,
+                    app.logs_scroll,
+                    &mut app.logs_scroll_state,
+                    true,
+                ),
+                SelectableInputDataDetailItem::DecodedInputData => (
+                    "DECODED INPUT DATA",
+                    raw_decoded_input_data,
+                    app.decoded_input_data_scroll,
+                    &mut app.decoded_input_data_scroll_state,
+                    true,
+                ),
+                SelectableInputDataDetailItem::InputData => (
+                    "DECODED INPUT DATA",
+                    raw_decoded_input_data,
+                    app.decoded_input_data_scroll,
+                    &mut app.decoded_input_data_scroll_state,
+                    false,
+                ),
+            };
             let block = Block::default().padding(Padding::new(0, 1, 0, 1));
             f.render_widget(
-                Paragraph::new(raw_decoded_input_data)
+                Paragraph::new(lines)
                     .alignment(Alignment::Left)
-                    .block(
-                        //FIXME
-                        if let SelectableInputDataDetailItem::DecodedInputData =
-                            SelectableInputDataDetailItem::from(
-                                app.input_data_detail_list_state
-                                    .selected()
-                                    .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
-                            )
-                        {
-                            Block::default()
-                                .borders(Borders::ALL)
-                                .green()
-                                .title(Span::styled(
-                                    "DECODED INPUT DATA",
-                                    Style::default().add_modifier(Modifier::BOLD).green(),
-                                ))
-                        } else {
-                            Block::default()
-                                .borders(Borders::ALL)
-                                .gray()
-                                .title(Span::styled(
-                                    "DECODED INPUT DATA",
-                                    Style::default().add_modifier(Modifier::BOLD),
-                                ))
-                        },
-                    )
-                    .scroll((app.decoded_input_data_scroll, 0))
+                    .block(if is_selected {
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .fg(app.theme.focus)
+                            .title(Span::styled(
+                                title,
+                                Style::default()
+                                    .add_modifier(Modifier::BOLD)
+                                    .fg(app.theme.focus),
+                            ))
+                    } else {
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .fg(app.theme.muted)
+                            .title(Span::styled(
+                                title,
+                                Style::default().add_modifier(Modifier::BOLD),
+                            ))
+                    })
+                    .scroll((scroll, 0))
                     .wrap(Wrap { trim: false }),
                 block.inner(chunks[1]),
             );
@@ -397,7 +440,7 @@ pub fn render<B: Backend>(
                     .begin_symbol(Some("▲"))
                     .end_symbol(Some("▼")),
                 block.inner(chunks[1]),
-                &mut app.decoded_input_data_scroll_state,
+                scroll_state,
             );
         } else {
             let chunks = Layout::default()
@@ -405,7 +448,7 @@ pub fn render<B: Backend>(
                 .constraints([Constraint::Length(2), Constraint::Min(0)])
                 .split(input_data_rect);
 
-            let titles = ["INPUT DATA", "DECODED INPUT DATA"]
+            let titles = ["INPUT DATA", "DECODED INPUT DATA", "LOGS"]
                 .iter()
                 .map(|t| Line::from(t.to_owned()))
                 .collect();
@@ -414,19 +457,15 @@ pub fn render<B: Backend>(
                 .block(
                     Block::default()
                         .borders(Borders::RIGHT | Borders::LEFT | Borders::TOP)
-                        .border_style(
-                            if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                                if let RouteId::InputDataOfTransaction(_) =
-                                    app.get_current_route().get_id()
-                                {
-                                    Style::default().fg(Color::Green)
-                                } else {
-                                    Style::default().fg(Color::White)
-                                }
-                            } else {
-                                Style::default().fg(Color::White)
-                            },
-                        ),
+                        .border_style(app.theme.border(
+                            matches!(
+                                app.get_current_route().get_active_block(),
+                                ActiveBlock::Main
+                            ) && matches!(
+                                app.get_current_route().get_id(),
+                                RouteId::InputDataOfTransaction(_)
+                            ),
+                        )),
                 )
                 .select(
                     app.input_data_detail_list_state
@@ -434,7 +473,7 @@ pub fn render<B: Backend>(
                         .unwrap_or(SelectableInputDataDetailItem::InputData.into()),
                 )
                 .style(Style::default())
-                .highlight_style(Style::default().bold().green());
+                .highlight_style(Style::default().bold().fg(app.theme.focus));
             f.render_widget(
                 tabs,
                 Block::default()
@@ -442,41 +481,32 @@ pub fn render<B: Backend>(
                     .inner(chunks[0]),
             );
 
+            let (lines, scroll) = match selected_item {
+                SelectableInputDataDetailItem::InputData => (raw_input_data, app.input_data_scroll),
+                SelectableInputDataDetailItem::DecodedInputData => {
+                    (raw_decoded_input_data, app.decoded_input_data_scroll)
+                }
+                SelectableInputDataDetailItem::Logs => (raw_logs, app.logs_scroll),
+            };
             let block = Block::default().padding(Padding::new(1, 1, 0, 1));
             f.render_widget(
-                Paragraph::new(
-                    match app
-                        .input_data_detail_list_state
-                        .selected()
-                        .map_or(SelectableInputDataDetailItem::InputData, |i| {
-                            SelectableInputDataDetailItem::from(i)
-                        }) {
-                        SelectableInputDataDetailItem::InputData => raw_input_data.to_owned(),
-                        SelectableInputDataDetailItem::DecodedInputData => {
-                            raw_decoded_input_data.to_owned()
-                        }
-                    },
-                )
-                .alignment(Alignment::Left)
-                .block(
-                    Block::default()
-                        .borders(Borders::RIGHT | Borders::LEFT | Borders::BOTTOM)
-                        .border_style(
-                            if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                                if let RouteId::InputDataOfTransaction(_) =
-                                    app.get_current_route().get_id()
-                                {
-                                    Style::default().fg(Color::Green)
-                                } else {
-                                    Style::default().fg(Color::White)
-                                }
-                            } else {
-                                Style::default().fg(Color::White)
-                            },
-                        ),
-                )
-                .scroll((app.input_data_scroll, 0))
-                .wrap(Wrap { trim: false }),
+                Paragraph::new(lines)
+                    .alignment(Alignment::Left)
+                    .block(
+                        Block::default()
+                            .borders(Borders::RIGHT | Borders::LEFT | Borders::BOTTOM)
+                            .border_style(app.theme.border(
+                                matches!(
+                                    app.get_current_route().get_active_block(),
+                                    ActiveBlock::Main
+                                ) && matches!(
+                                    app.get_current_route().get_id(),
+                                    RouteId::InputDataOfTransaction(_)
+                                ),
+                            )),
+                    )
+                    .scroll((scroll, 0))
+                    .wrap(Wrap { trim: false }),
                 block.inner(chunks[1]),
             );
 
@@ -486,7 +516,13 @@ pub fn render<B: Backend>(
                     .begin_symbol(Some("▲"))
                     .end_symbol(Some("▼")),
                 block.inner(chunks[1]),
-                &mut app.input_data_scroll_state,
+                match selected_item {
+                    SelectableInputDataDetailItem::InputData => &mut app.input_data_scroll_state,
+                    SelectableInputDataDetailItem::DecodedInputData => {
+                        &mut app.decoded_input_data_scroll_state
+                    }
+                    SelectableInputDataDetailItem::Logs => &mut app.logs_scroll_state,
+                },
             );
         }
 
@@ -500,13 +536,10 @@ pub fn render<B: Backend>(
     } else {
         let detail_block = Block::default()
             .title("Transaction Not Found")
-            .border_style(
-                if let ActiveBlock::Main = app.get_current_route().get_active_block() {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                },
-            )
+            .border_style(app.theme.border(matches!(
+                app.get_current_route().get_active_block(),
+                ActiveBlock::Main
+            )))
             .padding(Padding::new(2, 2, 1, 1))
             .borders(Borders::ALL)
             .border_type(BorderType::Plain);
@@ -514,3 +547,79 @@ pub fn render<B: Backend>(
         f.render_widget(detail_block, rect);
     }
 }
+
+/// Renders a decoded call or event as a tree, one parameter per line.
+fn decoded_lines<'a>(theme: &Theme, signature: &str, params: &[DecodedParam]) -> Vec<Line<'a>> {
+    let mut lines = vec![Line::from(Span::raw(signature.to_owned()).fg(theme.method))];
+    for (i, param) in params.iter().enumerate() {
+        push_param_lines(theme, &mut lines, param, "", i + 1 == params.len());
+    }
+    lines
+}
+
+/// Renders each log with its emitter, the decoded event if there is one, and its raw topics and
+/// data.
+fn log_lines<'a>(app: &App, logs: &[Log], decoded_logs: &[Option<DecodedEvent>]) -> Vec<Line<'a>> {
+    let mut lines = vec![];
+    for (i, log) in logs.iter().enumerate() {
+        let label = if let Some(token) = ERC20Token::find_by_address(&app.erc20_tokens, log.address)
+        {
+            format!(" ({}: {})", token.ticker, token.name)
+        } else if let Some(Some(ens_id)) = app.address2ens_id.get(&log.address) {
+            format!(" ({ens_id})")
+        } else {
+            "".to_owned()
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!("Log #{i}  ")).fg(app.theme.text).bold(),
+            Span::raw(format!("{:#x}{label}", log.address)).fg(app.theme.address),
+        ]));
+
+        if let Some(Some(event)) = decoded_logs.get(i) {
+            lines.extend(decoded_lines(&app.theme, &event.signature, &event.params));
+        }
+
+        lines.push(Line::from(Span::raw("Topics").fg(app.theme.muted)));
+        for (j, topic) in log.topics.iter().enumerate() {
+            lines.push(Line::from(vec![
+                Span::raw(format!("  [{j}] ")).fg(app.theme.muted),
+                Span::raw(format!("{topic:#x}")).fg(app.theme.text),
+            ]));
+        }
+
+        lines.push(Line::from(Span::raw("Data").fg(app.theme.muted)));
+        for chunk in log.data.to_string().chars().collect::<Vec<_>>().chunks(64) {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::raw(chunk.iter().collect::<String>()).fg(app.theme.text),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+fn push_param_lines<'a>(
+    theme: &Theme,
+    lines: &mut Vec<Line<'a>>,
+    param: &DecodedParam,
+    prefix: &str,
+    is_last: bool,
+) {
+    let mut spans = vec![
+        Span::raw(format!("{prefix}{}", if is_last { "└─ " } else { "├─ " })).fg(theme.muted),
+        Span::raw(param.name.to_owned()).fg(theme.address),
+        Span::raw(format!(": {}", param.kind)).fg(theme.muted),
+    ];
+    if let Some(value) = param.value.as_ref() {
+        spans.push(Span::raw(" = ").fg(theme.muted));
+        spans.push(Span::raw(value.to_owned()).fg(theme.text));
+    }
+    lines.push(Line::from(spans));
+
+    let prefix = format!("{prefix}{}", if is_last { "   " } else { "│  " });
+    for (i, child) in param.children.iter().enumerate() {
+        push_param_lines(theme, lines, child, &prefix, i + 1 == param.children.len());
+    }
+}
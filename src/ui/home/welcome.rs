@@ -4,7 +4,7 @@ use ratatui::{prelude::*, widgets::*};
 pub fn render<B: Backend>(f: &mut Frame<B>, app: &App, rect: Rect) {
     let welcome_block = Block::default()
         .title("Welcome")
-        .border_style(Style::default().fg(Color::White))
+        .border_style(Style::default().fg(app.theme.text))
         .borders(Borders::ALL)
         .border_type(BorderType::Plain);
 
@@ -32,22 +32,28 @@ pub fn render<B: Backend>(f: &mut Frame<B>, app: &App, rect: Rect) {
 
     let details = Paragraph::new(vec![
         Line::from(
-            Span::raw(format!("   {:<13}: {}", "RPC Endpoint", app.endpoint)).fg(Color::White),
+            Span::raw(format!("   {:<13}: {}", "RPC Endpoint", app.endpoint)).fg(app.theme.text),
         ),
-        Line::from(Span::raw(format!("   {:<13}: {}", "Version", "v0.1.0")).fg(Color::White)),
+        Line::from(
+            Span::raw(format!("   {:<13}: {}", "Chain", app.chain.name())).fg(app.theme.text),
+        ),
+        Line::from(
+            Span::raw(format!("   {:<13}: {}", "Explorer", app.explorer_status)).fg(app.theme.text),
+        ),
+        Line::from(Span::raw(format!("   {:<13}: {}", "Version", "v0.1.0")).fg(app.theme.text)),
         Line::from(
             Span::raw(format!(
                 "   {:<13}: {}",
                 "Document", "https://woxjro.github.io/lazy-etherscan"
             ))
-            .fg(Color::White),
+            .fg(app.theme.text),
         ),
         Line::from(
             Span::raw(format!(
                 "   {:<13}: {}",
                 "Repository", "https://github.com/woxjro/lazy-etherscan"
             ))
-            .fg(Color::White),
+            .fg(app.theme.text),
         ),
     ])
     .block(details_block.to_owned())
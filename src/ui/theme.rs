@@ -0,0 +1,112 @@
+use ratatui::prelude::*;
+use serde::{de, Deserialize, Deserializer};
+use std::str::FromStr;
+
+/// Colors the UI is drawn with. Custom themes are read from `[themes.<name>]` in the config
+/// file, where colors are names like `lightcyan`, indexes like `208` or hex codes like
+/// `#268bd2`. Colors left out are taken from the dark theme.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    #[serde(deserialize_with = "color")]
+    pub text: Color,
+    /// Labels, line numbers and other secondary text.
+    #[serde(deserialize_with = "color")]
+    pub muted: Color,
+    /// The border and selected item of the focused pane.
+    #[serde(deserialize_with = "color")]
+    pub focus: Color,
+    /// Addresses, hashes and parameter names.
+    #[serde(deserialize_with = "color")]
+    pub address: Color,
+    #[serde(deserialize_with = "color")]
+    pub success: Color,
+    #[serde(deserialize_with = "color")]
+    pub error: Color,
+    /// Newly arrived blocks.
+    #[serde(deserialize_with = "color")]
+    pub highlight: Color,
+    /// Method names and event signatures.
+    #[serde(deserialize_with = "color")]
+    pub method: Color,
+    #[serde(deserialize_with = "color")]
+    pub transfer: Color,
+    #[serde(deserialize_with = "color")]
+    pub contract_creation: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+impl Theme {
+    pub const DARK: Self = Self {
+        text: Color::White,
+        muted: Color::Gray,
+        focus: Color::Green,
+        address: Color::Cyan,
+        success: Color::Green,
+        error: Color::Red,
+        highlight: Color::Yellow,
+        method: Color::LightYellow,
+        transfer: Color::LightMagenta,
+        contract_creation: Color::LightCyan,
+    };
+
+    pub const LIGHT: Self = Self {
+        text: Color::Black,
+        muted: Color::DarkGray,
+        focus: Color::Blue,
+        address: Color::Rgb(0x00, 0x5f, 0x87),
+        success: Color::Rgb(0x00, 0x87, 0x00),
+        error: Color::Red,
+        highlight: Color::Rgb(0xaf, 0x5f, 0x00),
+        method: Color::Rgb(0x87, 0x5f, 0x00),
+        transfer: Color::Magenta,
+        contract_creation: Color::Rgb(0x00, 0x87, 0x87),
+    };
+
+    /// Uses the terminal's own colors. Focus and selections are still shown in bold.
+    pub const MONOCHROME: Self = Self {
+        text: Color::Reset,
+        muted: Color::Reset,
+        focus: Color::Reset,
+        address: Color::Reset,
+        success: Color::Reset,
+        error: Color::Reset,
+        highlight: Color::Reset,
+        method: Color::Reset,
+        transfer: Color::Reset,
+        contract_creation: Color::Reset,
+    };
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::DARK),
+            "light" => Some(Self::LIGHT),
+            "monochrome" => Some(Self::MONOCHROME),
+            _ => None,
+        }
+    }
+
+    /// Whether colors should be left out, as asked by the `NO_COLOR` environment variable.
+    pub fn is_no_color() -> bool {
+        std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+    }
+
+    /// The border of a pane, bold when it's focused.
+    pub fn border(&self, is_focused: bool) -> Style {
+        if is_focused {
+            Style::default().fg(self.focus).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.text)
+        }
+    }
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    Color::from_str(&name).map_err(|_| de::Error::custom(format!("Unknown color `{name}`")))
+}
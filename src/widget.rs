@@ -1,5 +1,6 @@
 use chrono::Utc;
 use ratatui::widgets::ListState;
+use std::fmt;
 
 const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
@@ -15,12 +16,13 @@ impl Default for Spinner {
     }
 }
 
-impl ToString for Spinner {
-    fn to_string(&self) -> String {
+impl fmt::Display for Spinner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let cycle = 1500; //millisec
-        self.elements[((Utc::now().timestamp_millis() % cycle)
-            / (cycle / self.elements.len() as i64)) as usize]
-            .to_owned()
+        f.write_str(
+            &self.elements[((Utc::now().timestamp_millis() % cycle)
+                / (cycle / self.elements.len() as i64)) as usize],
+        )
     }
 }
 
@@ -68,6 +70,25 @@ impl<T> StatefulList<T> {
         self.state.select(Some(i));
     }
 
+    /// Inserts `items` at the top, keeping at most `capacity` items and the current selection
+    /// on the same item where possible.
+    pub fn prepend(&mut self, items: Vec<T>, capacity: usize) {
+        let n = items.len();
+        self.items.splice(0..0, items);
+        self.items.truncate(capacity);
+
+        if let Some(i) = self.state.selected() {
+            if self.items.is_empty() {
+                self.state.select(None);
+            } else {
+                self.state.select(Some(std::cmp::min(
+                    i + n,
+                    self.items.len() - 1 + self.header_size,
+                )));
+            }
+        }
+    }
+
     pub fn get_selected_item_index(&self) -> Option<usize> {
         self.state.selected().map(|state| state - self.header_size)
     }